@@ -2,23 +2,19 @@
 async fn main() -> udisks2::Result<()> {
     let client = udisks2::Client::new().await?;
 
-    for object in client
-        .object_manager()
-        .get_managed_objects()
-        .await?
-        .into_iter()
-        .filter_map(|(object_path, _)| client.object(object_path).ok())
-    {
-        //only use objects that have a drive
-        let Ok(drive) = object.drive().await else {
-            continue;
-        };
-
-        // print model and size
+    for entry in client.storage_report().await {
+        // print device, filesystem type, size and mount points
+        let mount_points: Vec<_> = entry
+            .mount_points
+            .iter()
+            .map(|mount_point| mount_point.display().to_string())
+            .collect();
         println!(
-            "{}: {}",
-            drive.model().await?,
-            client.size_for_display(drive.size().await?, false, false)
+            "{} ({}): {} {}",
+            entry.device.display(),
+            entry.filesystem_type,
+            client.size_for_display(entry.size, false, false),
+            mount_points.join(", ")
         );
     }
     Ok(())
@@ -0,0 +1,50 @@
+use std::{collections::HashMap, path::PathBuf, process::ExitCode};
+
+use udisks2::r#loop::LoopSetupOptions;
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let mut args = std::env::args();
+    let bin_name = args.next().unwrap();
+
+    let Some(path) = args.next().map(PathBuf::from) else {
+        eprintln!("Usage: {} <ISO image>", bin_name);
+        return ExitCode::FAILURE;
+    };
+
+    let Ok(client) = udisks2::Client::new().await else {
+        eprintln!("Error connecting to the udisks daemon");
+        return ExitCode::FAILURE;
+    };
+
+    let object = match client
+        .create_loop_device(&path, LoopSetupOptions::new().read_only(true))
+        .await
+    {
+        Ok(object) => object,
+        Err(err) => {
+            eprintln!(
+                "Error setting up loop device for {}: {}",
+                path.display(),
+                err
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let Ok(filesystem) = object.filesystem().await else {
+        eprintln!("{} does not contain a mountable filesystem", path.display());
+        return ExitCode::FAILURE;
+    };
+
+    match filesystem.mount(HashMap::new()).await {
+        Ok(mount_point) => {
+            println!("Mounted {} at {}", path.display(), mount_point);
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("Error mounting {}: {}", path.display(), err);
+            ExitCode::FAILURE
+        }
+    }
+}
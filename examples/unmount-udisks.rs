@@ -94,7 +94,7 @@ pub fn major(dev: u64) -> u32 {
 
 pub fn minor(dev: u64) -> u32 {
     let mut minor = 0;
-    minor |= (dev & 0x00000000000000ff) >> 0;
+    minor |= dev & 0x00000000000000ff;
     minor |= (dev & 0x00000ffffff00000) >> 12;
     minor as u32
 }
@@ -10,10 +10,442 @@
 //! section of the zbus documentation.
 //!
 
-use zbus::proxy;
+use std::{
+    str::FromStr,
+    time::{Duration, SystemTime},
+};
+
+use serde::{de::IntoDeserializer, Deserialize, Serialize};
+use zbus::{proxy, zvariant::Type};
 
 use crate::error;
 
+/// Status of the last ATA SMART self-test.
+///
+/// See [`crate::SelfTestResult`] for a classification shared with NVMe self-tests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Type)]
+#[zvariant(signature = "s")]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum SelfTestStatus {
+    /// Last self-test was a success (or never ran).
+    Success,
+    /// Last self-test was aborted.
+    Aborted,
+    /// Last self-test was interrupted.
+    Interrupted,
+    /// Last self-test did not complete.
+    Fatal,
+    /// Last self-test failed (Unknown).
+    ErrorUnknown,
+    /// Last self-test failed (Electrical).
+    ErrorElectrical,
+    /// Last self-test failed (Servo).
+    ErrorServo,
+    /// Last self-test failed (Read).
+    ErrorRead,
+    /// Last self-test failed (Damage).
+    ErrorHandling,
+    /// Self-test is currently in progress.
+    #[serde(rename = "inprogress")]
+    InProgress,
+    /// Status is unknown, e.g. because SMART is not supported.
+    #[serde(rename(deserialize = ""))]
+    Unknown,
+}
+
+impl FromStr for SelfTestStatus {
+    type Err = serde::de::value::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let res: Result<_, Self::Err> = Self::deserialize(s.into_deserializer());
+        Ok(res.unwrap_or(Self::Unknown))
+    }
+}
+
+impl_try_from_string_value!(SelfTestStatus);
+
+/// Power mode of an ATA drive, as returned by [`AtaProxy::pm_get_state`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PowerState {
+    /// The drive is in standby mode, i.e. spun down.
+    Standby,
+    /// The drive is idle.
+    Idle,
+    /// The drive is active or idle.
+    Active,
+    /// Any other power mode, containing the raw value reported by the drive.
+    Other(u8),
+}
+
+impl From<u8> for PowerState {
+    fn from(value: u8) -> Self {
+        match value {
+            0x00 => Self::Standby,
+            0x80 => Self::Idle,
+            0xff => Self::Active,
+            other => Self::Other(other),
+        }
+    }
+}
+
+/// Summary of the power management capabilities and state of a drive.
+///
+/// Returned by [`crate::Client::power_management_status`].
+/// For drives that are not ATA drives, only [`Self::can_power_off`] is set.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PowerStatus {
+    /// Whether the drive is an ATA drive.
+    pub is_ata: bool,
+    /// Whether the drive can be powered off, see [`crate::drive::DriveProxy::can_power_off`].
+    pub can_power_off: bool,
+    /// Whether the drive supports power management.
+    pub pm_supported: bool,
+    /// Whether power management is enabled.
+    pub pm_enabled: bool,
+    /// Whether the drive supports Advanced Power Management (APM).
+    pub apm_supported: bool,
+    /// Whether Advanced Power Management is enabled.
+    pub apm_enabled: bool,
+    /// Whether the drive supports Automatic Acoustic Management (AAM).
+    pub aam_supported: bool,
+    /// Whether Automatic Acoustic Management is enabled.
+    pub aam_enabled: bool,
+    /// The current power mode, if it was queried.
+    pub state: Option<PowerState>,
+}
+
+/// Estimated duration of an ATA secure erase.
+///
+/// Returned by [`crate::Client::secure_erase_estimate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EraseEstimate {
+    /// The erase takes about the given duration.
+    Bounded(Duration),
+    /// The erase takes at least the given duration, as it takes longer than the drive can report.
+    Unbounded(Duration),
+}
+
+impl EraseEstimate {
+    /// Returns the estimated duration, which is a lower bound for [`EraseEstimate::Unbounded`].
+    pub fn duration(&self) -> Duration {
+        match self {
+            Self::Bounded(duration) | Self::Unbounded(duration) => *duration,
+        }
+    }
+
+    /// Whether the actual duration may be arbitrarily longer than [`Self::duration`].
+    pub fn is_unbounded(&self) -> bool {
+        matches!(self, Self::Unbounded(_))
+    }
+}
+
+/// Interpreted raw value of a [`SmartAttribute`].
+///
+/// Returned by [`SmartAttribute::pretty_value`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PrettyValue {
+    /// A value without unit, e.g. a count of events.
+    Dimensionless(i64),
+    /// A duration, e.g. the power-on time.
+    Duration(Duration),
+    /// A number of sectors, e.g. reallocated sectors.
+    Sectors(i64),
+    /// A temperature in degrees Celsius.
+    Temperature(f64),
+    /// A value in an unknown unit, as reported by the daemon.
+    Unknown(i64),
+}
+
+/// Well-known identifier of an ATA SMART attribute.
+///
+/// Identifiers are not standardized, but the ones listed here are used consistently
+/// by most vendors. Use [`SmartAttribute::attribute_id`] to get the identifier of an attribute.
+///
+/// # Examples
+/// ```
+/// # use udisks2::ata::SmartAttributeId;
+/// assert_eq!(SmartAttributeId::from(5), SmartAttributeId::ReallocatedSectorCount);
+/// assert_eq!(SmartAttributeId::from(194), SmartAttributeId::Temperature);
+/// assert_eq!(SmartAttributeId::from(197), SmartAttributeId::CurrentPendingSectorCount);
+/// assert_eq!(SmartAttributeId::from(231), SmartAttributeId::Vendor(231));
+///
+/// for id in 0..=u8::MAX {
+///     assert_eq!(u8::from(SmartAttributeId::from(id)), id);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SmartAttributeId {
+    /// Rate of hardware read errors.
+    RawReadErrorRate,
+    /// Time needed to spin up the drive.
+    SpinUpTime,
+    /// Number of spindle start/stop cycles.
+    StartStopCount,
+    /// Number of sectors that were remapped to the spare area.
+    ReallocatedSectorCount,
+    /// Rate of seek errors of the heads.
+    SeekErrorRate,
+    /// Time the drive was powered on.
+    PowerOnHours,
+    /// Number of retries to spin up the drive.
+    SpinRetryCount,
+    /// Number of power-on/power-off cycles.
+    PowerCycleCount,
+    /// Remaining endurance of the flash memory of an SSD.
+    WearLevelingCount,
+    /// Number of parity errors in the data path of the drive.
+    EndToEndError,
+    /// Number of errors that could not be corrected using ECC.
+    ReportedUncorrectableErrors,
+    /// Number of aborted operations due to a timeout.
+    CommandTimeout,
+    /// Temperature of the air flowing through the drive.
+    AirflowTemperature,
+    /// Number of load/unload cycles of the heads.
+    LoadCycleCount,
+    /// Temperature of the drive.
+    Temperature,
+    /// Number of remap operations.
+    ReallocationEventCount,
+    /// Number of unstable sectors waiting to be remapped.
+    CurrentPendingSectorCount,
+    /// Number of uncorrectable errors found during offline scans.
+    OfflineUncorrectable,
+    /// Number of CRC errors during UDMA transfers, usually caused by the cable.
+    UdmaCrcErrorCount,
+    /// Total number of LBAs written.
+    TotalLbasWritten,
+    /// Total number of LBAs read.
+    TotalLbasRead,
+    /// Any other, vendor-specific attribute, containing the raw identifier.
+    Vendor(u8),
+}
+
+impl From<u8> for SmartAttributeId {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => Self::RawReadErrorRate,
+            3 => Self::SpinUpTime,
+            4 => Self::StartStopCount,
+            5 => Self::ReallocatedSectorCount,
+            7 => Self::SeekErrorRate,
+            9 => Self::PowerOnHours,
+            10 => Self::SpinRetryCount,
+            12 => Self::PowerCycleCount,
+            177 => Self::WearLevelingCount,
+            184 => Self::EndToEndError,
+            187 => Self::ReportedUncorrectableErrors,
+            188 => Self::CommandTimeout,
+            190 => Self::AirflowTemperature,
+            193 => Self::LoadCycleCount,
+            194 => Self::Temperature,
+            196 => Self::ReallocationEventCount,
+            197 => Self::CurrentPendingSectorCount,
+            198 => Self::OfflineUncorrectable,
+            199 => Self::UdmaCrcErrorCount,
+            241 => Self::TotalLbasWritten,
+            242 => Self::TotalLbasRead,
+            other => Self::Vendor(other),
+        }
+    }
+}
+
+impl From<SmartAttributeId> for u8 {
+    fn from(value: SmartAttributeId) -> Self {
+        match value {
+            SmartAttributeId::RawReadErrorRate => 1,
+            SmartAttributeId::SpinUpTime => 3,
+            SmartAttributeId::StartStopCount => 4,
+            SmartAttributeId::ReallocatedSectorCount => 5,
+            SmartAttributeId::SeekErrorRate => 7,
+            SmartAttributeId::PowerOnHours => 9,
+            SmartAttributeId::SpinRetryCount => 10,
+            SmartAttributeId::PowerCycleCount => 12,
+            SmartAttributeId::WearLevelingCount => 177,
+            SmartAttributeId::EndToEndError => 184,
+            SmartAttributeId::ReportedUncorrectableErrors => 187,
+            SmartAttributeId::CommandTimeout => 188,
+            SmartAttributeId::AirflowTemperature => 190,
+            SmartAttributeId::LoadCycleCount => 193,
+            SmartAttributeId::Temperature => 194,
+            SmartAttributeId::ReallocationEventCount => 196,
+            SmartAttributeId::CurrentPendingSectorCount => 197,
+            SmartAttributeId::OfflineUncorrectable => 198,
+            SmartAttributeId::UdmaCrcErrorCount => 199,
+            SmartAttributeId::TotalLbasWritten => 241,
+            SmartAttributeId::TotalLbasRead => 242,
+            SmartAttributeId::Vendor(other) => other,
+        }
+    }
+}
+
+/// A SMART attribute of an ATA drive, as returned by [`AtaProxy::smart_attributes`].
+///
+/// Each attribute has a normalized value, its worst value and a threshold, which are
+/// calculated by the drive and range from 1 to 253 (higher is better), as well as a raw value,
+/// which is interpreted by the daemon ([`Self::pretty`] and [`Self::pretty_unit`]).
+/// Display the raw value using [`Self::pretty_value`], and use the normalized values to
+/// determine the health of the attribute, see [`Self::is_failing`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SmartAttribute {
+    /// Attribute identifier.
+    pub id: u8,
+    /// Name of the attribute, e.g. `reallocated-sector-count`.
+    pub name: String,
+    /// Flags of the attribute, as reported by the drive.
+    pub flags: u16,
+    /// Current normalized value, or `-1` if unknown.
+    pub value: i32,
+    /// Worst normalized value, or `-1` if unknown.
+    pub worst: i32,
+    /// Threshold of the normalized value, or `-1` if unknown.
+    pub threshold: i32,
+    /// Interpretation of the raw value, in the unit of [`Self::pretty_unit`].
+    pub pretty: i64,
+    /// Unit of [`Self::pretty`]: 0 (unknown), 1 (dimensionless), 2 (milliseconds),
+    /// 3 (sectors) or 4 (millikelvin).
+    pub pretty_unit: i32,
+}
+
+impl SmartAttribute {
+    /// Returns the [`Self::id`] as a [`SmartAttributeId`].
+    pub fn attribute_id(&self) -> SmartAttributeId {
+        self.id.into()
+    }
+
+    /// Returns the current normalized value, or `None` if it is unknown.
+    ///
+    /// # Examples
+    /// ```
+    /// # use udisks2::ata::SmartAttribute;
+    /// let mut attribute = SmartAttribute {
+    ///     id: 5,
+    ///     name: "reallocated-sector-count".to_owned(),
+    ///     flags: 0x0033,
+    ///     value: 100,
+    ///     worst: 100,
+    ///     threshold: 10,
+    ///     pretty: 0,
+    ///     pretty_unit: 3,
+    /// };
+    /// assert_eq!(attribute.normalized_value(), Some(100));
+    ///
+    /// attribute.value = -1;
+    /// assert_eq!(attribute.normalized_value(), None);
+    /// ```
+    pub fn normalized_value(&self) -> Option<u8> {
+        u8::try_from(self.value).ok()
+    }
+
+    /// Returns the interpreted raw value.
+    ///
+    /// Temperatures are converted from millikelvin to degrees Celsius.
+    /// If the unit is unknown, [`PrettyValue::Unknown`] is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// # use udisks2::ata::{PrettyValue, SmartAttribute};
+    /// let mut attribute = SmartAttribute {
+    ///     id: 194,
+    ///     name: "temperature-celsius-2".to_owned(),
+    ///     flags: 0x0022,
+    ///     value: 64,
+    ///     worst: 50,
+    ///     threshold: 0,
+    ///     pretty: 309_150,
+    ///     pretty_unit: 4,
+    /// };
+    /// assert_eq!(attribute.pretty_value(), PrettyValue::Temperature(36.0));
+    ///
+    /// attribute.pretty_unit = 0;
+    /// assert_eq!(attribute.pretty_value(), PrettyValue::Unknown(309_150));
+    /// ```
+    pub fn pretty_value(&self) -> PrettyValue {
+        match self.pretty_unit {
+            1 => PrettyValue::Dimensionless(self.pretty),
+            2 => match u64::try_from(self.pretty) {
+                Ok(millis) => PrettyValue::Duration(Duration::from_millis(millis)),
+                Err(_) => PrettyValue::Unknown(self.pretty),
+            },
+            3 => PrettyValue::Sectors(self.pretty),
+            4 => PrettyValue::Temperature(self.pretty as f64 / 1000.0 - 273.15),
+            _ => PrettyValue::Unknown(self.pretty),
+        }
+    }
+
+    /// Whether the attribute is failing, i.e. its normalized value is at or below the threshold.
+    ///
+    /// Attributes with an unknown value or threshold, or a threshold of 0 (which the drive
+    /// uses for informational attributes that can not fail), are never failing.
+    ///
+    /// # Examples
+    /// ```
+    /// # use udisks2::ata::SmartAttribute;
+    /// let mut attribute = SmartAttribute {
+    ///     id: 5,
+    ///     name: "reallocated-sector-count".to_owned(),
+    ///     flags: 0x0033,
+    ///     value: 5,
+    ///     worst: 5,
+    ///     threshold: 10,
+    ///     pretty: 2000,
+    ///     pretty_unit: 3,
+    /// };
+    /// assert!(attribute.is_failing());
+    ///
+    /// attribute.threshold = -1;
+    /// assert!(!attribute.is_failing());
+    ///
+    /// attribute.threshold = 10;
+    /// attribute.value = -1;
+    /// assert!(!attribute.is_failing());
+    /// ```
+    pub fn is_failing(&self) -> bool {
+        match (self.normalized_value(), u8::try_from(self.threshold)) {
+            (Some(value), Ok(threshold)) => threshold > 0 && value <= threshold,
+            _ => false,
+        }
+    }
+}
+
+impl AtaProxy<'_> {
+    /// Returns the [`AtaProxy::smart_updated`] as [`SystemTime`].
+    ///
+    /// If the SMART data has never been collected, [`None`] is returned.
+    pub async fn smart_updated_system_time(&self) -> error::Result<Option<SystemTime>> {
+        Ok(crate::system_time_from_secs(self.smart_updated().await?))
+    }
+
+    /// Returns the SMART attributes of the drive.
+    ///
+    /// See [`AtaProxy::smart_get_attributes`] for the raw data.
+    pub async fn smart_attributes(
+        &self,
+        options: std::collections::HashMap<&str, zbus::zvariant::Value<'_>>,
+    ) -> error::Result<Vec<SmartAttribute>> {
+        Ok(self
+            .smart_get_attributes(options)
+            .await?
+            .into_iter()
+            .map(
+                |(id, name, flags, value, worst, threshold, pretty, pretty_unit, _)| {
+                    SmartAttribute {
+                        id,
+                        name,
+                        flags,
+                        value,
+                        worst,
+                        threshold,
+                        pretty,
+                        pretty_unit,
+                    }
+                },
+            )
+            .collect())
+    }
+}
+
 #[proxy(
     interface = "org.freedesktop.UDisks2.Drive.Ata",
     default_service = "org.freedesktop.UDisks2",
@@ -21,6 +453,8 @@ use crate::error;
 )]
 pub trait Ata {
     /// PmGetState method
+    ///
+    /// The returned value can be converted into a [`PowerState`].
     fn pm_get_state(
         &self,
         options: std::collections::HashMap<&str, zbus::zvariant::Value<'_>>,
@@ -167,7 +601,7 @@ pub trait Ata {
 
     /// SmartSelftestStatus property
     #[zbus(property)]
-    fn smart_selftest_status(&self) -> error::Result<String>;
+    fn smart_selftest_status(&self) -> error::Result<SelfTestStatus>;
 
     /// SmartSupported property
     #[zbus(property)]
@@ -6,9 +6,543 @@
 //! is also used for block devices that do not correspond to drives at all
 //! (e.g. [Loop Devices](https://en.wikipedia.org/wiki/Loop_device)).
 
-use zbus::proxy;
+use std::{collections::HashMap, path::PathBuf, str::FromStr};
+
+use serde::{de::IntoDeserializer, Deserialize, Serialize};
+use zbus::{
+    proxy,
+    zvariant::{OwnedValue, Type, Value},
+};
+
+use crate::{bytestring_path, encrypted::Credential, error};
+
+/// Mode used to erase a device before formatting it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EraseMode {
+    /// Write zeroes over the entire device.
+    Zero,
+    /// Perform an ATA secure erase.
+    AtaSecureErase,
+    /// Perform an enhanced ATA secure erase.
+    AtaSecureEraseEnhanced,
+}
+
+impl EraseMode {
+    /// Returns the value of the `erase` option, as used by [`BlockProxy::format`].
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Zero => "zero",
+            Self::AtaSecureErase => "ata-secure-erase",
+            Self::AtaSecureEraseEnhanced => "ata-secure-erase-enhanced",
+        }
+    }
+}
+
+/// Type of encryption created when formatting a device.
+///
+/// See [`EncryptConfig`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EncryptType {
+    /// LUKS version 1
+    Luks1,
+    /// LUKS version 2
+    Luks2,
+}
+
+impl EncryptType {
+    /// Returns the value of the `encrypt.type` option, as used by [`BlockProxy::format`].
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Luks1 => "luks1",
+            Self::Luks2 => "luks2",
+        }
+    }
+}
+
+/// Encryption of a device created when formatting it.
+///
+/// The filesystem is then created on the unlocked device, which is left unlocked.
+/// See [`FormatOptions::encrypt`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncryptConfig {
+    credential: Credential,
+    encrypt_type: Option<EncryptType>,
+}
+
+impl EncryptConfig {
+    /// Creates a new configuration, encrypting the device with `credential`.
+    pub fn new(credential: Credential) -> Self {
+        Self {
+            credential,
+            encrypt_type: None,
+        }
+    }
+
+    /// Type of encryption to use.
+    ///
+    /// If unset, the default of the daemon is used, see [`crate::manager::ManagerProxy::default_encryption_type`].
+    pub fn encrypt_type(mut self, encrypt_type: EncryptType) -> Self {
+        self.encrypt_type = Some(encrypt_type);
+        self
+    }
+}
+
+/// Result of probing a block device for known signatures.
+///
+/// Returned by [`BlockProxy::id_usage`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Type)]
+#[zvariant(signature = "s")]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum IdUsage {
+    /// A mountable filesystem.
+    Filesystem,
+    /// Encrypted data, e.g. a LUKS device.
+    Crypto,
+    /// A RAID member or similar, e.g. an LVM physical volume.
+    Raid,
+    /// Something else, e.g. swap space.
+    Other,
+    /// No known signature was detected.
+    ///
+    /// This doesn't necessarily mean the device contains no structured data.
+    #[default]
+    #[serde(rename(deserialize = ""))]
+    Unknown,
+}
+
+impl FromStr for IdUsage {
+    type Err = serde::de::value::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let res: Result<_, Self::Err> = Self::deserialize(s.into_deserializer());
+        Ok(res.unwrap_or(Self::Unknown))
+    }
+}
+
+impl_try_from_string_value!(IdUsage);
+
+/// High-level role of a block device, describing its contents.
+///
+/// Returned by [`crate::Client::device_role`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DeviceRole {
+    /// The device contains a partition table.
+    PartitionTable,
+    /// The device is a member of a RAID array.
+    RaidMember,
+    /// The device is encrypted, e.g. a LUKS container.
+    Encrypted,
+    /// The device contains swap space.
+    Swap,
+    /// The device contains a filesystem.
+    Filesystem,
+    /// The device contains other known content, e.g. an LVM physical volume.
+    Other {
+        /// The usage of the content, see [`BlockProxy::id_usage`].
+        usage: IdUsage,
+        /// The type of the content, see [`BlockProxy::id_type`].
+        ty: String,
+    },
+    /// No known content was detected on the device.
+    Empty,
+}
+
+/// Options for formatting a block device.
+///
+/// Can be converted into the options of [`BlockProxy::format`].
+/// See the documentation of [`BlockProxy::format_raw`] for a detailed explanation of each option.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FormatOptions {
+    label: Option<String>,
+    uuid: Option<String>,
+    erase: Option<EraseMode>,
+    encrypt: Option<EncryptConfig>,
+    mkfs_args: Vec<String>,
+    take_ownership: bool,
+    update_partition_type: bool,
+    no_block: bool,
+    dry_run_first: bool,
+    no_discard: bool,
+    tear_down: bool,
+    no_user_interaction: bool,
+}
+
+impl FormatOptions {
+    /// Creates new, empty options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Label of the created filesystem.
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// UUID of the created filesystem.
+    ///
+    /// The format must match the [`BlockProxy::id_uuid`] property.
+    pub fn uuid(mut self, uuid: impl Into<String>) -> Self {
+        self.uuid = Some(uuid.into());
+        self
+    }
+
+    /// How the device should be erased before formatting.
+    ///
+    /// If unset, the device is not erased.
+    pub fn erase(mut self, erase: EraseMode) -> Self {
+        self.erase = Some(erase);
+        self
+    }
+
+    /// Encrypts the device, creating the filesystem on the unlocked device.
+    pub fn encrypt(mut self, encrypt: EncryptConfig) -> Self {
+        self.encrypt = Some(encrypt);
+        self
+    }
+
+    /// Additional arguments passed to the `mkfs` program.
+    ///
+    /// The arguments are not validated and their position on the command line is not defined,
+    /// so this should only be used as a last resort.
+    pub fn mkfs_args<I, S>(mut self, mkfs_args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.mkfs_args = mkfs_args.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Whether the root directory of the created filesystem should be owned by the caller.
+    pub fn take_ownership(mut self, take_ownership: bool) -> Self {
+        self.take_ownership = take_ownership;
+        self
+    }
+
+    /// Whether the partition type should be set to the type matching the filesystem.
+    pub fn update_partition_type(mut self, update_partition_type: bool) -> Self {
+        self.update_partition_type = update_partition_type;
+        self
+    }
+
+    /// Whether to return just before the actual formatting takes place,
+    /// but after authorization and other checks are done.
+    pub fn no_block(mut self, no_block: bool) -> Self {
+        self.no_block = no_block;
+        self
+    }
+
+    /// Whether a dry run of the formatting command should be performed first,
+    /// allowing a deeper check of the parameters.
+    ///
+    /// Note that the block device has already been modified (wiped) when the dry run check is performed.
+    pub fn dry_run_first(mut self, dry_run_first: bool) -> Self {
+        self.dry_run_first = dry_run_first;
+        self
+    }
+
+    /// Whether the formatting utility should not issue `BLKDISCARD` ioctls.
+    pub fn no_discard(mut self, no_discard: bool) -> Self {
+        self.no_discard = no_discard;
+        self
+    }
+
+    /// Whether the block device and all its children should be cleaned up before formatting.
+    pub fn tear_down(mut self, tear_down: bool) -> Self {
+        self.tear_down = tear_down;
+        self
+    }
+
+    /// Whether no user interaction should happen, when checking if the operation is authorized.
+    ///
+    /// See [`crate::standard_options`].
+    pub fn no_user_interaction(mut self, no_user_interaction: bool) -> Self {
+        self.no_user_interaction = no_user_interaction;
+        self
+    }
+}
+
+impl From<FormatOptions> for HashMap<&'static str, Value<'static>> {
+    fn from(options: FormatOptions) -> Self {
+        let mut map = crate::standard_options(options.no_user_interaction);
+        if let Some(label) = options.label {
+            map.insert("label", label.into());
+        }
+        if let Some(uuid) = options.uuid {
+            map.insert("uuid", uuid.into());
+        }
+        if let Some(erase) = options.erase {
+            map.insert("erase", erase.as_str().into());
+        }
+        if let Some(encrypt) = options.encrypt {
+            let passphrase = match encrypt.credential {
+                Credential::Passphrase(passphrase) => passphrase.into(),
+                Credential::Keyfile(contents) => contents.into(),
+            };
+            map.insert("encrypt.passphrase", passphrase);
+            if let Some(encrypt_type) = encrypt.encrypt_type {
+                map.insert("encrypt.type", encrypt_type.as_str().into());
+            }
+        }
+        if !options.mkfs_args.is_empty() {
+            map.insert("mkfs-args", options.mkfs_args.into());
+        }
+        if options.take_ownership {
+            map.insert("take-ownership", true.into());
+        }
+        if options.update_partition_type {
+            map.insert("update-partition-type", true.into());
+        }
+        if options.no_block {
+            map.insert("no-block", true.into());
+        }
+        if options.dry_run_first {
+            map.insert("dry-run-first", true.into());
+        }
+        if options.no_discard {
+            map.insert("no-discard", true.into());
+        }
+        if options.tear_down {
+            map.insert("tear-down", true.into());
+        }
+        map
+    }
+}
+
+/// Options for [`crate::Client::set_mount_at_boot`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MountAtBootOptions {
+    dir: Option<String>,
+    mount_options: Option<String>,
+    no_user_interaction: bool,
+}
+
+impl MountAtBootOptions {
+    /// Default mount options of created entries.
+    pub const DEFAULT_MOUNT_OPTIONS: &'static str = "nosuid,nodev,nofail,x-gvfs-show";
 
-use crate::error;
+    /// Creates new, empty options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Directory to mount the filesystem at.
+    ///
+    /// If unset, `/mnt/<uuid>` is used.
+    pub fn dir(mut self, dir: impl Into<String>) -> Self {
+        self.dir = Some(dir.into());
+        self
+    }
+
+    /// Comma-separated mount options.
+    ///
+    /// If unset, [`Self::DEFAULT_MOUNT_OPTIONS`] are used.
+    pub fn mount_options(mut self, mount_options: impl Into<String>) -> Self {
+        self.mount_options = Some(mount_options.into());
+        self
+    }
+
+    /// Whether no user interaction should happen, when checking if the operation is authorized.
+    ///
+    /// See [`crate::standard_options`].
+    pub fn no_user_interaction(mut self, no_user_interaction: bool) -> Self {
+        self.no_user_interaction = no_user_interaction;
+        self
+    }
+
+    pub(crate) fn dir_or(&self, default: impl FnOnce() -> String) -> String {
+        self.dir.clone().unwrap_or_else(default)
+    }
+
+    pub(crate) fn mount_options_or_default(&self) -> String {
+        self.mount_options
+            .clone()
+            .unwrap_or_else(|| Self::DEFAULT_MOUNT_OPTIONS.to_owned())
+    }
+}
+
+impl From<MountAtBootOptions> for HashMap<&'static str, Value<'static>> {
+    fn from(options: MountAtBootOptions) -> Self {
+        let mut map = crate::standard_options(options.no_user_interaction);
+        map.insert("track-parents", true.into());
+        map
+    }
+}
+
+/// An entry in `/etc/fstab` referencing a block device.
+///
+/// See [`BlockProxy::configuration`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FstabEntry {
+    /// The special device, e.g. `UUID=...`.
+    pub fsname: String,
+    /// The mount point.
+    pub dir: String,
+    /// The filesystem type.
+    pub type_: String,
+    /// Comma-separated mount options.
+    pub opts: String,
+    /// Dump frequency in days.
+    pub freq: i32,
+    /// Pass number of parallel `fsck`.
+    pub passno: i32,
+}
+
+/// An entry in `/etc/crypttab` referencing a block device.
+///
+/// See [`BlockProxy::configuration`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CrypttabEntry {
+    /// The name to set the device up as.
+    pub name: String,
+    /// The special device, e.g. `UUID=...`.
+    pub device: String,
+    /// Either empty to specify that no password is set,
+    /// otherwise a path to a file containing the encryption password.
+    pub passphrase_path: String,
+    /// Comma-separated options.
+    pub options: String,
+}
+
+/// A typed configuration item of a block device.
+///
+/// See [`BlockProxy::configuration`] for details.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConfigurationItem {
+    /// The block device is referenced in `/etc/fstab`.
+    Fstab(FstabEntry),
+    /// The block device is referenced in `/etc/crypttab`.
+    Crypttab(CrypttabEntry),
+}
+
+impl ConfigurationItem {
+    /// Creates a configuration item from the `(type, details)` tuple used by the daemon.
+    ///
+    /// Returns [`None`] for unknown types.
+    pub fn from_raw(ty: &str, details: &HashMap<String, OwnedValue>) -> Option<Self> {
+        let bytes = |key: &str| {
+            details
+                .get(key)
+                .and_then(|value| value.try_clone().ok())
+                .and_then(|value| Vec::<u8>::try_from(value).ok())
+                .map(|mut bytes| {
+                    if bytes.last() == Some(&0) {
+                        bytes.pop();
+                    }
+                    String::from_utf8_lossy(&bytes).into_owned()
+                })
+                .unwrap_or_default()
+        };
+        let int = |key: &str| {
+            details
+                .get(key)
+                .and_then(|value| i32::try_from(value).ok())
+                .unwrap_or_default()
+        };
+
+        match ty {
+            "fstab" => Some(Self::Fstab(FstabEntry {
+                fsname: bytes("fsname"),
+                dir: bytes("dir"),
+                type_: bytes("type"),
+                opts: bytes("opts"),
+                freq: int("freq"),
+                passno: int("passno"),
+            })),
+            "crypttab" => Some(Self::Crypttab(CrypttabEntry {
+                name: bytes("name"),
+                device: bytes("device"),
+                passphrase_path: bytes("passphrase-path"),
+                options: bytes("options"),
+            })),
+            _ => None,
+        }
+    }
+
+    /// Converts the item into the `(type, details)` tuple used by the daemon,
+    /// e.g. for [`BlockProxy::add_configuration_item`].
+    pub fn into_raw(self) -> (&'static str, HashMap<&'static str, Value<'static>>) {
+        // byte strings are expected to be NUL-terminated
+        fn bytes(s: String) -> Value<'static> {
+            let mut bytes = s.into_bytes();
+            bytes.push(0);
+            bytes.into()
+        }
+
+        match self {
+            Self::Fstab(entry) => (
+                "fstab",
+                HashMap::from([
+                    ("fsname", bytes(entry.fsname)),
+                    ("dir", bytes(entry.dir)),
+                    ("type", bytes(entry.type_)),
+                    ("opts", bytes(entry.opts)),
+                    ("freq", entry.freq.into()),
+                    ("passno", entry.passno.into()),
+                ]),
+            ),
+            Self::Crypttab(entry) => (
+                "crypttab",
+                HashMap::from([
+                    ("name", bytes(entry.name)),
+                    ("device", bytes(entry.device)),
+                    ("passphrase-path", bytes(entry.passphrase_path)),
+                    ("options", bytes(entry.options)),
+                ]),
+            ),
+        }
+    }
+}
+
+impl BlockProxy<'_> {
+    /// Returns the [`Self::device`] as path, e.g. `/dev/sda`.
+    pub async fn device_path(&self) -> error::Result<PathBuf> {
+        Ok(bytestring_path(self.device().await?))
+    }
+
+    /// Returns the [`Self::preferred_device`] as path, e.g. `/dev/mapper/luks-<uuid>`.
+    pub async fn preferred_device_path(&self) -> error::Result<PathBuf> {
+        Ok(bytestring_path(self.preferred_device().await?))
+    }
+
+    /// Returns the [`Self::symlinks`] as paths.
+    pub async fn symlink_paths(&self) -> error::Result<Vec<PathBuf>> {
+        Ok(self
+            .symlinks()
+            .await?
+            .into_iter()
+            .map(bytestring_path)
+            .collect())
+    }
+
+    /// Formats the device with the given `type_`, e.g. `ext4` or `gpt`.
+    ///
+    /// The options can be created using [`FormatOptions`], or passed as map, see
+    /// [`Self::format_raw`] for all options and a detailed explanation.
+    pub async fn format<'a>(
+        &self,
+        type_: &str,
+        options: impl Into<HashMap<&'a str, Value<'a>>>,
+    ) -> error::Result<()> {
+        self.format_raw(type_, options.into()).await
+    }
+
+    /// Returns the [`Self::configuration`] as typed [`ConfigurationItem`]s.
+    ///
+    /// Items of unknown types are skipped.
+    pub async fn configuration_items(&self) -> error::Result<Vec<ConfigurationItem>> {
+        Ok(self
+            .configuration()
+            .await?
+            .iter()
+            .filter_map(|(ty, details)| ConfigurationItem::from_raw(ty, details))
+            .collect())
+    }
+}
 
 #[proxy(
     interface = "org.freedesktop.UDisks2.Block",
@@ -111,7 +645,10 @@ pub trait Block {
     /// In case the `tear-down` option is not set and the block device being formatted is partitioned and contains mounted filesystem
     /// or an active layered structure inside then the Format operation may not fail, yet could still overwrite nested foreign data regions.
     /// It is the caller responsibility to ensure the device is ready for destructive operations. This may be subject to further restrictions in the future.
-    fn format(
+    ///
+    /// The options can be created using [`FormatOptions`] and passed to [`BlockProxy::format`].
+    #[zbus(name = "Format")]
+    fn format_raw(
         &self,
         type_: &str,
         options: std::collections::HashMap<&str, zbus::zvariant::Value<'_>>,
@@ -248,6 +785,8 @@ pub trait Block {
     ///
     /// For security reasons, when creating a new `crypttab` entry (via the [`Self::add_configuration_item`] method), then the `passphrase-path`
     /// must reference an unexisting file in the `/etc/luks-keys` directory.
+    ///
+    /// See [`BlockProxy::configuration_items`] for a typed version.
     #[zbus(property)]
     fn configuration(
         &self,
@@ -373,23 +912,17 @@ pub trait Block {
     #[zbus(property, name = "IdUUID")]
     fn id_uuid(&self) -> error::Result<String>;
 
-    /// A result of probing for signatures on the block device. Known values include:
-    ///
-    /// * `filesystem`: Used for mountable filesystems
-    /// * `crypto`: Used for e.g. LUKS devices
-    /// * `raid`: Used for e.g. RAID members and LVM PVs
-    /// * `other`: Something else was detected.
+    /// A result of probing for signatures on the block device, see [`IdUsage`] for known values.
     ///
-    /// If blank, no known signature was detected. This doesn't necessarily mean the device contains no
+    /// If [`IdUsage::Unknown`], no known signature was detected. This doesn't necessarily mean the device contains no
     /// structured data; it only means that no signature known to the probing code was detected.
     ///
     /// Applications should not rely on the value, or the value of [`Self::id_type`]
     /// - instead, applications should check for whether the object in question implements interfaces
     /// such as e.g. [`org.freedesktop.UDisks2.Filesystem`](crate::filesystem),
     /// [`org.freedesktop.UDisks2.Swapspace`](crate::swapspace) or [`org.freedesktop.UDisks2.Encrypted`](crate::encrypted).
-    //TODO: use enum
     #[zbus(property)]
-    fn id_usage(&self) -> error::Result<String>;
+    fn id_usage(&self) -> error::Result<IdUsage>;
 
     /// The version of the filesystem or other structured data on the block device.
     /// Do not make any assumptions about the format.
@@ -0,0 +1,146 @@
+//! Blocking wrapper around the [`Client`](crate::Client).
+//!
+//! Intended for command line tools and other synchronous code, which would otherwise have to
+//! block on each call of the async API. The methods block the calling thread until the
+//! call finished, so they must not be called from within an async runtime.
+//!
+//! Requires the `blocking` feature.
+//!
+//! # Examples
+//! ```no_run
+//! # fn main() -> udisks2::Result<()> {
+//! let client = udisks2::blocking::Client::new()?;
+//! let object = client.object("/org/freedesktop/UDisks2/block_devices/sda")?;
+//! let block = object.block()?;
+//! let drive = client.drive_for_block(&block)?;
+//! println!("Size: {}", client.size_for_display(drive.size()?, true, true));
+//! # Ok(())
+//! # }
+//! ```
+
+use zbus::zvariant::OwnedObjectPath;
+
+use crate::{block, drive, error, ObjectInfo};
+
+/// Blocking version of [`crate::Client`].
+///
+/// Returns the blocking variants of the proxies, e.g. [`block::BlockProxyBlocking`].
+/// The async client can be accessed using [`Self::inner`], e.g. to use methods that
+/// are not wrapped.
+#[derive(Debug, Clone)]
+pub struct Client {
+    inner: crate::Client,
+}
+
+/// Blocking version of [`crate::Object`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Object {
+    inner: crate::Object,
+}
+
+/// Converts an async proxy into its blocking variant.
+fn blocking<'a, P, B>(proxy: P) -> B
+where
+    P: zbus::proxy::ProxyImpl<'a>,
+    B: From<zbus::Proxy<'a>>,
+{
+    B::from(proxy.into_inner())
+}
+
+/// Converts a blocking proxy into its async variant.
+fn non_blocking<'a, B, P>(proxy: &B) -> P
+where
+    B: zbus::blocking::proxy::ProxyImpl<'a>,
+    P: From<zbus::Proxy<'a>>,
+{
+    P::from(proxy.inner().inner().clone())
+}
+
+impl Client {
+    /// Creates a new client, connected to the system bus.
+    ///
+    /// See [`crate::Client::new`].
+    pub fn new() -> error::Result<Self> {
+        async_io::block_on(crate::Client::new()).map(Self::from)
+    }
+
+    /// Returns the wrapped async client.
+    pub fn inner(&self) -> &crate::Client {
+        &self.inner
+    }
+
+    /// Looks up the [`Object`] for `object_path`.
+    ///
+    /// See [`crate::Client::object`].
+    pub fn object<P: TryInto<OwnedObjectPath>>(&self, object_path: P) -> Result<Object, P::Error> {
+        Ok(Object {
+            inner: self.inner.object(object_path)?,
+        })
+    }
+
+    /// Gets the block device with the given device number.
+    ///
+    /// See [`crate::Client::block_for_dev`].
+    pub fn block_for_dev(&self, block_device_number: u64) -> Option<block::BlockProxyBlocking<'_>> {
+        async_io::block_on(self.inner.block_for_dev(block_device_number)).map(blocking)
+    }
+
+    /// Returns the drive of the given block device.
+    ///
+    /// See [`crate::Client::drive_for_block`].
+    pub fn drive_for_block(
+        &self,
+        block: &block::BlockProxyBlocking<'_>,
+    ) -> error::Result<drive::DriveProxyBlocking<'static>> {
+        let block: block::BlockProxy<'_> = non_blocking(block);
+        async_io::block_on(self.inner.drive_for_block(&block)).map(blocking)
+    }
+
+    /// Returns information about the given object for presentation in a user interface.
+    ///
+    /// See [`crate::Client::object_info`].
+    pub fn object_info<'a>(&self, object: &'a Object) -> ObjectInfo<'a> {
+        async_io::block_on(self.inner.object_info(&object.inner))
+    }
+
+    /// Returns a human-readable string that represents the given size.
+    ///
+    /// See [`crate::Client::size_for_display`].
+    pub fn size_for_display(&self, size: u64, use_pow2: bool, long_str: bool) -> String {
+        self.inner.size_for_display(size, use_pow2, long_str)
+    }
+}
+
+impl From<crate::Client> for Client {
+    fn from(inner: crate::Client) -> Self {
+        Self { inner }
+    }
+}
+
+impl Object {
+    /// Returns the [`OwnedObjectPath`] used by the object.
+    pub fn object_path(&self) -> &OwnedObjectPath {
+        self.inner.object_path()
+    }
+
+    /// Returns the wrapped async object.
+    pub fn inner(&self) -> &crate::Object {
+        &self.inner
+    }
+
+    /// Returns the `org.freedesktop.UDisks2.Block` interface.
+    ///
+    /// # Errors
+    /// Returns [zbus::Error::InterfaceNotFound] if the interface could not be acquired.
+    pub fn block(&self) -> error::Result<block::BlockProxyBlocking<'static>> {
+        async_io::block_on(self.inner.block()).map(blocking)
+    }
+
+    /// Returns the `org.freedesktop.UDisks2.Drive` interface.
+    ///
+    /// # Errors
+    /// Returns [zbus::Error::InterfaceNotFound] if the interface could not be acquired.
+    pub fn drive(&self) -> error::Result<drive::DriveProxyBlocking<'static>> {
+        async_io::block_on(self.inner.drive()).map(blocking)
+    }
+}
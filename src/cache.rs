@@ -0,0 +1,156 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+use futures_util::{
+    future::{self, AbortHandle},
+    stream, StreamExt,
+};
+use zbus::{
+    fdo::{InterfacesAddedStream, InterfacesRemovedStream, ObjectManagerProxy},
+    zvariant::OwnedObjectPath,
+};
+
+use crate::{
+    error,
+    snapshot::{self, ObjectSnapshot},
+};
+
+/// Properties of the block interface referencing the object a block belongs to.
+const REFERENCES: [&str; 3] = ["Drive", "MDRaid", "MDRaidMember"];
+
+/// Cache of the block devices referencing each drive and RAID array.
+///
+/// The cache is filled on the first lookup and dropped whenever the daemon adds or removes a
+/// block device, which is tracked by a task on the executor of the connection, listening for the
+/// `InterfacesAdded` and `InterfacesRemoved` signals.
+#[derive(Debug)]
+pub(crate) struct BlockCache {
+    state: Arc<Mutex<State>>,
+    listener: AbortHandle,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    /// Maps drives and RAID arrays to the blocks referencing them.
+    blocks: Option<HashMap<OwnedObjectPath, Vec<OwnedObjectPath>>>,
+    /// Incremented on each invalidation, so outdated lookups are not stored.
+    generation: u64,
+}
+
+impl BlockCache {
+    /// Creates a new, empty cache, invalidated by the signals of `object_manager`.
+    pub(crate) async fn new(object_manager: &ObjectManagerProxy<'static>) -> error::Result<Self> {
+        let added = object_manager.receive_interfaces_added().await?;
+        let removed = object_manager.receive_interfaces_removed().await?;
+
+        let state = Arc::new(Mutex::new(State::default()));
+        let (listener, handle) =
+            future::abortable(invalidate_on_changes(Arc::clone(&state), added, removed));
+        // aborted on drop, so the task does not have to be kept
+        object_manager
+            .inner()
+            .connection()
+            .executor()
+            .spawn(listener, "udisks2-block-cache")
+            .detach();
+
+        Ok(Self {
+            state,
+            listener: handle,
+        })
+    }
+
+    /// Returns the blocks whose `Drive`, `MDRaid` or `MDRaidMember` property is set to `target`.
+    ///
+    /// If the cache is empty, it is filled from `snapshot`.
+    pub(crate) async fn blocks_referencing(
+        &self,
+        target: &OwnedObjectPath,
+        snapshot: impl Future<Output = ObjectSnapshot>,
+    ) -> Vec<OwnedObjectPath> {
+        if let Some(blocks) = &self.state.lock().unwrap().blocks {
+            return blocks.get(target).cloned().unwrap_or_default();
+        }
+        self.load(snapshot).await.remove(target).unwrap_or_default()
+    }
+
+    /// Fills the cache from `snapshot`, replacing the current content.
+    pub(crate) async fn refresh(&self, snapshot: impl Future<Output = ObjectSnapshot>) {
+        self.load(snapshot).await;
+    }
+
+    /// Empties the cache, so it is filled again on the next lookup.
+    pub(crate) fn invalidate(&self) {
+        invalidate(&self.state);
+    }
+
+    async fn load(
+        &self,
+        snapshot: impl Future<Output = ObjectSnapshot>,
+    ) -> HashMap<OwnedObjectPath, Vec<OwnedObjectPath>> {
+        let generation = self.state.lock().unwrap().generation;
+        let snapshot = snapshot.await;
+
+        let mut blocks: HashMap<OwnedObjectPath, Vec<OwnedObjectPath>> = HashMap::new();
+        for object_path in snapshot.object_paths() {
+            for property in REFERENCES {
+                if let Some(target) =
+                    snapshot.object_path_property(object_path, snapshot::BLOCK_INTERFACE, property)
+                {
+                    blocks.entry(target).or_default().push(object_path.clone());
+                }
+            }
+        }
+
+        let mut state = self.state.lock().unwrap();
+        // the snapshot may predate a change signalled in the meantime
+        if state.generation == generation {
+            state.blocks = Some(blocks.clone());
+        }
+        blocks
+    }
+}
+
+impl Drop for BlockCache {
+    fn drop(&mut self) {
+        self.listener.abort();
+    }
+}
+
+fn invalidate(state: &Mutex<State>) {
+    let mut state = state.lock().unwrap();
+    state.blocks = None;
+    state.generation += 1;
+}
+
+/// Invalidates the cache whenever a block device is added or removed.
+async fn invalidate_on_changes(
+    state: Arc<Mutex<State>>,
+    added: InterfacesAddedStream,
+    removed: InterfacesRemovedStream,
+) {
+    let added = added.map(|signal| {
+        signal.args().is_ok_and(|args| {
+            args.interfaces_and_properties()
+                .keys()
+                .any(|interface| interface.as_str() == snapshot::BLOCK_INTERFACE)
+        })
+    });
+    let removed = removed.map(|signal| {
+        signal.args().is_ok_and(|args| {
+            args.interfaces()
+                .iter()
+                .any(|interface| interface.as_str() == snapshot::BLOCK_INTERFACE)
+        })
+    });
+
+    let mut changes = stream::select(added, removed);
+    while let Some(block_changed) = changes.next().await {
+        if block_changed {
+            invalidate(&state);
+        }
+    }
+}
@@ -1,17 +1,37 @@
-use gettextrs::pgettext;
-use zbus::{fdo::ObjectManagerProxy, zvariant::OwnedObjectPath};
+use std::{
+    collections::HashMap,
+    ffi::CString,
+    fs::{File, OpenOptions},
+    future::Future,
+    os::{fd::AsFd, linux::fs::MetadataExt, unix::fs::FileTypeExt},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use enumflags2::BitFlags;
+use futures_util::{future, stream, FutureExt, Stream, StreamExt};
+use zbus::{
+    fdo::ObjectManagerProxy,
+    zvariant::{OwnedObjectPath, Value},
+};
 
 use crate::{
+    ata,
     block::{self, BlockProxy},
-    drive, error,
-    gettext::{dpgettext, pgettext_f},
+    cache::BlockCache,
+    drive, encrypted, error, filesystem,
+    gettext::{dpgettext, npgettext_f, pgettext, pgettext_f},
     id::ID_TYPES,
-    job, manager, mdraid,
+    job, manager, mdraid, nvme,
     object::Object,
-    object_info::ObjectInfo,
+    object_info::{ObjectInfo, ObjectInfoData},
     partition, partition_subtypes,
     partition_types::{self, PartitionTypeInfo, PARTITION_TYPES},
     partitiontable, r#loop,
+    safety::{SafetyConcern, SafetyReport},
+    snapshot::{self, ObjectSnapshot},
+    DeviceEvent, Error, MatchOptions, Size, SizeParseError, SmartSummary, StorageEntry,
 };
 
 const KILOBYTE_FACTOR: f64 = 1000.0;
@@ -22,7 +42,7 @@ const TERABYTE_FACTOR: f64 = 1000.0 * 1000.0 * 1000.0 * 1000.0;
 const KIBIBYTE_FACTOR: f64 = 1024.0;
 const MEBIBYTE_FACTOR: f64 = 1024.0 * 1024.0;
 const GIBIBYTE_FACTOR: f64 = 1024.0 * 1024.0 * 1024.0;
-const TEBIBYTE_FACTOR: f64 = 1024.0 * 1024.0 * 1024.0 * 10242.0;
+const TEBIBYTE_FACTOR: f64 = 1024.0 * 1024.0 * 1024.0 * 1024.0;
 
 /// Utility routines for accessing the UDisks service.
 ///
@@ -32,6 +52,7 @@ pub struct Client {
     connection: zbus::Connection,
     object_manager: zbus::fdo::ObjectManagerProxy<'static>,
     manager: manager::ManagerProxy<'static>,
+    cache: Option<Arc<BlockCache>>,
 }
 
 impl Client {
@@ -54,9 +75,55 @@ impl Client {
             connection,
             object_manager,
             manager,
+            cache: None,
         })
     }
 
+    /// Enables caching which block devices belong to which drive and RAID array.
+    ///
+    /// Without the cache, [`Self::block_for_drive`], [`Self::block_for_mdraid`] and related
+    /// lookups look at every object exported by the daemon, so looking up the blocks of all
+    /// drives takes quadratic time on systems with many devices. With the cache, only the blocks
+    /// belonging to the drive or array are looked at.
+    ///
+    /// The cache is filled on the first lookup and emptied whenever the daemon adds or
+    /// removes a block device. To track these changes, a task listening for the
+    /// `InterfacesAdded` and `InterfacesRemoved` signals is spawned on the executor of the
+    /// connection, which ends once the client and all its clones are dropped.
+    /// If the connection was built without its internal executor, the executor must be
+    /// ticked by the caller for the cache to be invalidated.
+    ///
+    /// The signals arrive asynchronously, so a lookup right after a device was plugged in
+    /// may miss the new device, until the signal is received. Changes of the `Drive`, `MDRaid`
+    /// and `MDRaidMember` properties of existing block devices are not tracked.
+    /// Use [`Self::invalidate_cache`] or [`Self::refresh_cache`] if a lookup must reflect the
+    /// current state of the daemon.
+    ///
+    /// # Errors
+    /// Returns an error if subscribing to the signals fails.
+    pub async fn with_cache(mut self) -> error::Result<Self> {
+        self.cache = Some(Arc::new(BlockCache::new(&self.object_manager).await?));
+        Ok(self)
+    }
+
+    /// Fills the cache enabled with [`Self::with_cache`] from the current state of the daemon.
+    ///
+    /// Does nothing if the cache is not enabled.
+    pub async fn refresh_cache(&self) {
+        if let Some(cache) = &self.cache {
+            cache.refresh(self.snapshot()).await;
+        }
+    }
+
+    /// Empties the cache enabled with [`Self::with_cache`], so it is filled again on the next lookup.
+    ///
+    /// Does nothing if the cache is not enabled.
+    pub fn invalidate_cache(&self) {
+        if let Some(cache) = &self.cache {
+            cache.invalidate();
+        }
+    }
+
     /// Returns the [`zbus::fdo::ObjectManagerProxy`] used by the [Client].
     pub fn object_manager(&self) -> &zbus::fdo::ObjectManagerProxy<'_> {
         &self.object_manager
@@ -67,6 +134,17 @@ impl Client {
         &self.manager
     }
 
+    /// Returns the version of the running UDisks daemon.
+    ///
+    /// # Errors
+    /// Returns an error if the version could not be read or parsed.
+    pub async fn version(&self) -> error::Result<manager::Version> {
+        let version = self.manager.version().await?;
+        version.parse().map_err(|_| {
+            zbus::Error::Failure(format!("Failed to parse daemon version `{}`", version)).into()
+        })
+    }
+
     /// Convenience function for looking up an [Object] for `object_path`.
     ///
     /// # Errors
@@ -80,6 +158,254 @@ impl Client {
         ))
     }
 
+    /// Returns all objects exported by the daemon.
+    ///
+    /// Useful for custom lookups that are not covered by the other methods of the client.
+    ///
+    /// # Errors
+    /// Returns an error if the managed objects could not be fetched.
+    pub async fn objects(&self) -> error::Result<Vec<Object>> {
+        Ok(self
+            .object_manager
+            .get_managed_objects()
+            .await?
+            .into_keys()
+            .map(|object_path| {
+                Object::new(
+                    object_path,
+                    self.object_manager.clone(),
+                    self.connection.clone(),
+                )
+            })
+            .collect())
+    }
+
+    /// Looks up the [`Object`] for `object_path` and verifies that it is exported by the daemon.
+    ///
+    /// Unlike [`Self::object`], which never checks whether the object exists, this queries
+    /// the managed objects of the daemon.
+    ///
+    /// # Errors
+    /// Returns [`zbus::fdo::Error::UnknownObject`] if the daemon does not export an object at
+    /// `object_path`, or an error if `object_path` is not a valid object path.
+    pub async fn get_object<P>(&self, object_path: P) -> error::Result<Object>
+    where
+        P: TryInto<OwnedObjectPath>,
+        P::Error: Into<Error>,
+    {
+        let object_path = object_path.try_into().map_err(Into::into)?;
+        let objects = self.object_manager.get_managed_objects().await?;
+        if !objects.contains_key(&object_path) {
+            return Err(
+                zbus::fdo::Error::UnknownObject(format!("No object at `{}`", object_path)).into(),
+            );
+        }
+        Ok(self.object(object_path)?)
+    }
+
+    /// Returns the block [`Object`]s matching the given `devspec`.
+    ///
+    /// This is useful to find the object of a device file, e.g. `/dev/sda1`, or of a
+    /// filesystem UUID.
+    ///
+    /// # Errors
+    /// Returns an error if the daemon fails to resolve the devices, e.g. because it does not
+    /// support [`manager::ManagerProxy::resolve_device`] (added in version 2.7.3).
+    pub async fn resolve_device(&self, devspec: manager::DevSpec) -> error::Result<Vec<Object>> {
+        let object_paths = self
+            .manager
+            .resolve_device(devspec.into(), HashMap::new())
+            .await?;
+        Ok(object_paths
+            .into_iter()
+            .map(|object_path| {
+                Object::new(
+                    object_path,
+                    self.object_manager.clone(),
+                    self.connection.clone(),
+                )
+            })
+            .collect())
+    }
+
+    /// Returns the [`Object`] for the block device with the given kernel `name`, e.g. `sda` or `nvme0n1p1`.
+    ///
+    /// Block devices are exported at `/org/freedesktop/UDisks2/block_devices/<name>`.
+    /// The object is not checked for existence.
+    ///
+    /// # Errors
+    /// Returns an error if `name` is not a valid object path element.
+    pub fn block_object(&self, name: &str) -> error::Result<Object> {
+        self.well_known_object("block_devices", name)
+    }
+
+    /// Returns the [`Object`] for the drive with the given `name`, e.g. `Samsung_SSD_970_EVO_S1234`.
+    ///
+    /// Drives are exported at `/org/freedesktop/UDisks2/drives/<name>`, where the name is
+    /// usually derived from the vendor, model and serial of the drive, with all characters
+    /// other than `A-Z`, `a-z`, `0-9` and `_` replaced by `_`.
+    /// The object is not checked for existence.
+    ///
+    /// # Errors
+    /// Returns an error if `name` is not a valid object path element.
+    pub fn drive_object(&self, name: &str) -> error::Result<Object> {
+        self.well_known_object("drives", name)
+    }
+
+    fn well_known_object(&self, kind: &str, name: &str) -> error::Result<Object> {
+        if name.is_empty() || name.contains('/') {
+            return Err(zbus::zvariant::Error::Message(format!(
+                "Invalid object path element `{}`",
+                name
+            ))
+            .into());
+        }
+        Ok(self.object(format!("/org/freedesktop/UDisks2/{}/{}", kind, name))?)
+    }
+
+    /// Returns a stream of all objects exported by the daemon.
+    ///
+    /// The managed objects are fetched once, when the stream is first polled,
+    /// and the [`Object`]s are then created lazily. This allows combinators such as
+    /// [`StreamExt::filter`] to short-circuit without looking at every object.
+    ///
+    /// If the objects could not be fetched, the stream is empty.
+    pub fn objects_stream(&self) -> impl Stream<Item = Object> + '_ {
+        stream::once(self.object_manager.get_managed_objects())
+            .flat_map(|objects| stream::iter(objects.into_iter().flatten()))
+            .filter_map(|(object_path, _)| future::ready(self.object(object_path).ok()))
+    }
+
+    /// Returns a stream of property changes of all objects exported by the daemon.
+    ///
+    /// Each item contains the object path and the name of the changed property,
+    /// for example to only update the affected row of a device list.
+    /// The interface of the property is not included, as property names are mostly unique
+    /// across the interfaces of an object.
+    ///
+    /// Note that the stream can be very busy: some properties, e.g. the progress of jobs
+    /// or SMART data, change frequently, and a single hotplug event changes many properties
+    /// of several objects at once. Consider debouncing the items before doing expensive work.
+    ///
+    /// # Errors
+    /// Returns an error if subscribing to the signals fails.
+    pub async fn watch_all(
+        &self,
+    ) -> error::Result<impl Stream<Item = (OwnedObjectPath, String)> + 'static> {
+        let rule = zbus::MatchRule::builder()
+            .msg_type(zbus::message::Type::Signal)
+            .sender("org.freedesktop.UDisks2")?
+            .interface("org.freedesktop.DBus.Properties")?
+            .member("PropertiesChanged")?
+            .path_namespace("/org/freedesktop/UDisks2")?
+            .build();
+        let messages = zbus::MessageStream::for_match_rule(rule, &self.connection, None).await?;
+
+        Ok(messages.flat_map(|message| {
+            let changes = message
+                .ok()
+                .and_then(zbus::fdo::PropertiesChanged::from_message)
+                .and_then(|signal| {
+                    let object_path: OwnedObjectPath =
+                        signal.message().header().path()?.to_owned().into();
+                    let args = signal.args().ok()?;
+                    let properties = args
+                        .changed_properties()
+                        .keys()
+                        .copied()
+                        .chain(args.invalidated_properties().iter().copied())
+                        .map(|property| (object_path.clone(), property.to_owned()))
+                        .collect::<Vec<_>>();
+                    Some(properties)
+                })
+                .unwrap_or_default();
+            stream::iter(changes)
+        }))
+    }
+
+    /// Returns a stream of objects being added to and removed from the daemon.
+    ///
+    /// Objects are added and removed when devices are plugged in or removed, but also for
+    /// example when a partition is created or an encrypted device is unlocked.
+    /// Interfaces added to or removed from an existing object are reported separately,
+    /// e.g. when a block device is formatted with a filesystem.
+    ///
+    /// The [`Object`]s of the events use the connection of the client.
+    ///
+    /// # Errors
+    /// Returns an error if subscribing to the signals or fetching the current objects fails.
+    pub async fn events(&self) -> error::Result<impl Stream<Item = DeviceEvent> + 'static> {
+        // subscribe first, so no change between fetching the objects and subscribing is missed
+        let signals = self.object_manager.inner().receive_all_signals().await?;
+        let objects: HashMap<OwnedObjectPath, Vec<String>> = self
+            .object_manager
+            .get_managed_objects()
+            .await?
+            .into_iter()
+            .map(|(object_path, interfaces)| {
+                let interfaces = interfaces
+                    .into_keys()
+                    .map(|name| name.to_string())
+                    .collect();
+                (object_path, interfaces)
+            })
+            .collect();
+
+        let client = self.clone();
+        Ok(signals
+            .scan(objects, move |objects, message| {
+                future::ready(Some(device_event(&client, objects, message)))
+            })
+            .filter_map(future::ready))
+    }
+
+    /// Returns the daemon modules that are available.
+    ///
+    /// The daemon does not report which modules are installed, only the interfaces of loaded
+    /// modules are exported on the manager object. A module is therefore reported as available
+    /// if the manager object implements the [`manager::Module`]'s manager interface.
+    ///
+    /// If `load_modules` is set, each module is loaded using [`manager::ManagerProxy::enable_module`]
+    /// before probing it, so installed modules that are not loaded yet are also found.
+    /// Note that this loads the modules as a side effect, which requires authorization and
+    /// makes the daemon export additional objects. If `load_modules` is not set,
+    /// the manager object is only inspected, so only modules that are already loaded are reported.
+    ///
+    /// Modules that can not be loaded or probed are not reported.
+    pub async fn available_modules(&self, load_modules: bool) -> Vec<manager::Module> {
+        let properties = async {
+            zbus::fdo::PropertiesProxy::builder(&self.connection)
+                .destination("org.freedesktop.UDisks2")?
+                .path(self.manager.inner().path().clone())?
+                .build()
+                .await
+        };
+        let Ok(properties) = properties.await else {
+            return Vec::new();
+        };
+
+        let mut modules = Vec::new();
+        for module in manager::Module::ALL {
+            if load_modules
+                && self
+                    .manager
+                    .enable_module(module.name(), true)
+                    .await
+                    .is_err()
+            {
+                continue;
+            }
+            let Ok(interface) = zbus::names::InterfaceName::try_from(module.manager_interface())
+            else {
+                continue;
+            };
+            if properties.get_all(interface).await.is_ok() {
+                modules.push(module);
+            }
+        }
+        modules
+    }
+
     /// Gets all  the [`job::JobProxy`] instances for the given object.
     ///
     /// If no instances are found, the returned vector is empty.
@@ -89,14 +415,7 @@ impl Client {
 
         let mut blocks = Vec::new();
 
-        for object in self
-            .object_manager
-            .get_managed_objects()
-            .await
-            .into_iter()
-            .flatten()
-            .filter_map(|(object_path, _)| self.object(object_path).ok())
-        {
+        for object in self.objects().await.into_iter().flatten() {
             let Ok(job) = object.job().await else {
                 continue;
             };
@@ -116,40 +435,22 @@ impl Client {
     ///
     /// For known job types, see the documentation for [`job::JobProxy::operation`].
     pub fn job_description_from_operation(&self, operation: &str) -> String {
-        match operation {
-            "ata-smart-selftest" => pgettext("job", "SMART self-test"),
-            "drive-eject" => pgettext("job", "Ejecting Medium"),
-            "encrypted-unlock" => pgettext("job", "Unlocking Device"),
-            "encrypted-lock" => pgettext("job", "Locking Device"),
-            "encrypted-modify" => pgettext("job", "Modifying Encrypted Device"),
-            "encrypted-resize" => pgettext("job", "Resizing Encrypted Device"),
-            "swapspace-start" => pgettext("job", "Starting Swap Device"),
-            "swapspace-stop" => pgettext("job", "Stopping Swap Device"),
-            "swapspace-modify" => pgettext("job", "Modifying Swap Device"),
-            "filesystem-check" => pgettext("job", "Checking Filesystem"),
-            "filesystem-mount" => pgettext("job", "Mounting Filesystem"),
-            "filesystem-unmount" => pgettext("job", "Unmounting Filesystem"),
-            "filesystem-modify" => pgettext("job", "Modifying Filesystem"),
-            "filesystem-repair" => pgettext("job", "Repairing Filesystem"),
-            "filesystem-resize" => pgettext("job", "Resizing Filesystem"),
-            "format-erase" => pgettext("job", "Erasing Device"),
-            "format-mkfs" => pgettext("job", "Creating Filesystem"),
-            "loop-setup" => pgettext("job", "Setting Up Loop Device"),
-            "partition-modify" => pgettext("job", "Modifying Partition"),
-            "partition-delete" => pgettext("job", "Deleting Partition"),
-            "partition-create" => pgettext("job", "Creating Partition"),
-            "cleanup" => pgettext("job", "Cleaning Up"),
-            "ata-secure-erase" => pgettext("job", "ATA Secure Erase"),
-            "ata-enhanced-secure-erase" => pgettext("job", "ATA Enhanced Secure Erase"),
-            "md-raid-stop" => pgettext("job", "Stopping RAID Array"),
-            "md-raid-start" => pgettext("job", "Starting RAID Array"),
-            "md-raid-fault-device" => pgettext("job", "Marking Device as Faulty"),
-            "md-raid-remove-device" => pgettext("job", "Removing Device from Array"),
-            "md-raid-add-device" => pgettext("job", "Adding Device to Array"),
-            "md-raid-set-bitmap" => pgettext("job", "Setting Write-Intent Bitmap"),
-            "md-raid-create" => pgettext("job", "Creating RAID Array"),
-            _ => pgettext_f("unknown-job", "Unknown ({})", [operation]),
-        }
+        job_operation_strings(operation)
+            .map(|(description, _)| description)
+            .unwrap_or_else(|| pgettext_f("unknown-job", "Unknown ({})", [operation]))
+    }
+
+    /// Gets a short, human-readable and localized label for the operation of a job.
+    ///
+    /// Unlike [`Self::job_description_from_operation`], which describes a running job
+    /// (e.g. "Creating Filesystem"), the label is an imperative verb (e.g. "Format"),
+    /// suitable for buttons of confirmation dialogs.
+    ///
+    /// For known job types, see the documentation for [`job::JobProxy::operation`].
+    pub fn operation_short_label(&self, operation: &str) -> String {
+        job_operation_strings(operation)
+            .map(|(_, label)| label)
+            .unwrap_or_else(|| pgettext_f("unknown-job", "Unknown ({})", [operation]))
     }
 
     /// Gets a human-readable and localized text string describing the operation of job.
@@ -162,15 +463,8 @@ impl Client {
     /// Gets the [`block::BlockProxy`] for the given `block_device_number`.
     ///
     /// If no block is found, [`None`] is returned,
-    pub async fn block_for_dev(&self, block_device_number: u64) -> Option<block::BlockProxy> {
-        for object in self
-            .object_manager
-            .get_managed_objects()
-            .await
-            .into_iter()
-            .flatten()
-            .filter_map(|(object_path, _)| self.object(object_path).ok())
-        {
+    pub async fn block_for_dev(&self, block_device_number: u64) -> Option<block::BlockProxy<'_>> {
+        for object in self.objects().await.into_iter().flatten() {
             let Ok(block) = object.block().await else {
                 continue;
             };
@@ -182,22 +476,177 @@ impl Client {
         None
     }
 
+    /// Gets the [`block::BlockProxy`] for the given `path`.
+    ///
+    /// If `path` is a block device (e.g. `/dev/sda1`), the block for the device itself is returned.
+    /// Otherwise the block of the device `path` resides on is returned.
+    /// Symbolic links, such as the ones in `/dev/disk/by-*`, are followed.
+    ///
+    /// `path` is accessed on a background thread, as it may reside on a slow filesystem,
+    /// e.g. a network share.
+    ///
+    /// If no block is found, [`None`] is returned.
+    ///
+    /// # Errors
+    /// Returns an error if `path` could not be accessed.
+    pub async fn block_for_path(
+        &self,
+        path: &Path,
+    ) -> error::Result<Option<block::BlockProxy<'_>>> {
+        let path = path.to_path_buf();
+        let metadata = ::blocking::unblock(move || std::fs::metadata(path))
+            .await
+            .map_err(|err| zbus::Error::InputOutput(Arc::new(err)))?;
+        let block_device_number = if metadata.file_type().is_block_device() {
+            metadata.st_rdev()
+        } else {
+            metadata.st_dev()
+        };
+        Ok(self.block_for_dev(block_device_number).await)
+    }
+
+    /// Returns the mount points created by the daemon whose block device no longer exists.
+    ///
+    /// This is the case if a device was removed without being unmounted first.
+    /// Mount points are considered if they are located below one of the directories the daemon
+    /// mounts filesystems in, `/run/media` or `/media` (if the daemon is configured to use
+    /// shared mount points), and belong to a block device that is not known to the daemon.
+    ///
+    /// The daemon normally cleans up such mount points by itself, using a `cleanup` job, and
+    /// does not provide a method to trigger the cleanup.
+    /// This is therefore only useful for reporting and diagnostics,
+    /// e.g. to inform the user that a device was unplugged while it was mounted.
+    ///
+    /// If the mount table could not be read, no mount points are returned.
+    pub async fn stale_mount_points(&self) -> Vec<PathBuf> {
+        const MOUNT_ROOTS: [&str; 2] = ["/run/media", "/media"];
+
+        let Ok(mountinfo) = std::fs::read_to_string("/proc/self/mountinfo") else {
+            return Vec::new();
+        };
+
+        let snapshot = self.snapshot().await;
+        let mut device_numbers = Vec::new();
+        for object_path in snapshot.object_paths() {
+            if let Some(block) = snapshot
+                .proxy::<BlockProxy>(object_path, snapshot::BLOCK_INTERFACE)
+                .await
+            {
+                device_numbers.extend(block.device_number().await.ok());
+            }
+        }
+
+        mountinfo
+            .lines()
+            .filter_map(|line| {
+                // see proc_pid_mountinfo(5) for the format
+                let mut fields = line.split(' ').skip(2);
+                let (major, minor) = fields.next()?.split_once(':')?;
+                let (major, minor) = (major.parse().ok()?, minor.parse().ok()?);
+                let mount_point = unescape_mount_path(fields.nth(1)?);
+                // major 0 is used for filesystems without a block device, e.g. FUSE
+                let stale = major != 0
+                    && !device_numbers.contains(&makedev(major, minor))
+                    && MOUNT_ROOTS.iter().any(|root| mount_point.starts_with(root));
+                stale.then_some(mount_point)
+            })
+            .collect()
+    }
+
+    /// Returns the SMART health summary of the drive the device at `path` belongs to.
+    ///
+    /// The lookup consists of the following steps:
+    ///
+    /// 1. The block device is resolved using [`Self::block_for_path`]. If `path` cannot be
+    ///    accessed, an error is returned; if no block device is found, [`None`] is returned.
+    /// 2. The drive of the block device is resolved using [`Self::drive_for_block`].
+    ///    Devices without a drive, such as loop devices, return [`None`].
+    /// 3. If the drive is an ATA drive, [`None`] is returned unless SMART is supported and enabled.
+    ///    If the drive is an NVMe controller, its health information is always used.
+    ///    Drives that are neither return [`None`].
+    ///
+    /// # Errors
+    /// Returns an error if `path` could not be accessed, or the drive or its SMART data
+    /// could not be read.
+    pub async fn smart_for_path(&self, path: &Path) -> error::Result<Option<SmartSummary>> {
+        let Some(block) = self.block_for_path(path).await? else {
+            return Ok(None);
+        };
+        let drive = block.drive().await?;
+        if drive.as_str() == "/" {
+            return Ok(None);
+        }
+        self.smart_status(&self.object(drive)?).await
+    }
+
+    /// Returns the SMART health summary of the given drive object.
+    ///
+    /// The health data is read from the [`ata::AtaProxy`] or [`nvme::controller::ControllerProxy`]
+    /// interface of the object, whichever is available.
+    /// ATA drives only report their health if SMART is supported and enabled.
+    /// For objects without either interface, such as block devices, [`None`] is returned.
+    ///
+    /// # Errors
+    /// Returns an error if the SMART data could not be read.
+    pub async fn smart_status(&self, object: &Object) -> error::Result<Option<SmartSummary>> {
+        if let Ok(ata) = object.drive_ata().await {
+            if !ata.smart_supported().await? || !ata.smart_enabled().await? {
+                return Ok(None);
+            }
+            return SmartSummary::from_ata(&ata).await.map(Some);
+        }
+        if let Ok(controller) = object.nvme_controller().await {
+            return SmartSummary::from_nvme(&controller).await.map(Some);
+        }
+        Ok(None)
+    }
+
+    /// Gets the [`block::BlockProxy`] whose filesystem is mounted at `mount_point`.
+    ///
+    /// `mount_point` is canonicalized on a background thread before comparing it to the mount
+    /// points of the filesystems, which are already canonical, so symbolic links and trailing
+    /// slashes are handled.
+    /// Paths are compared byte-wise, i.e. they do not need to be valid UTF-8.
+    /// Filesystems whose mount points could not be read are skipped.
+    ///
+    /// If nothing is mounted at `mount_point`, [`None`] is returned.
+    ///
+    /// # Errors
+    /// Returns an error if the block device of the matching filesystem could not be read.
+    pub async fn block_for_mount_point(
+        &self,
+        mount_point: &Path,
+    ) -> error::Result<Option<block::BlockProxy<'_>>> {
+        let mount_point = mount_point.to_path_buf();
+        let mount_point =
+            ::blocking::unblock(move || mount_point.canonicalize().unwrap_or(mount_point)).await;
+
+        for object in self.objects().await.into_iter().flatten() {
+            let Ok(filesystem) = object.filesystem().await else {
+                continue;
+            };
+            let Ok(mount_points) = filesystem.mount_points_paths().await else {
+                continue;
+            };
+
+            if mount_points.contains(&mount_point) {
+                return object.block().await.map(Some);
+            }
+        }
+        Ok(None)
+    }
+
     /// Gets all the [`block::BlockProxy`] instances with the given label.
     ///
+    /// The label has to match exactly, see [`Self::block_for_label_matching`] for a more lenient comparison.
+    ///
     /// If no instances are found, the returned vector is empty.
-    pub async fn block_for_label(&self, label: &str) -> Vec<block::BlockProxy> {
+    pub async fn block_for_label(&self, label: &str) -> Vec<block::BlockProxy<'_>> {
         //TODO refactor once it is possible to use iterators with async
 
         let mut blocks = Vec::new();
 
-        for object in self
-            .object_manager
-            .get_managed_objects()
-            .await
-            .into_iter()
-            .flatten()
-            .filter_map(|(object_path, _)| self.object(object_path).ok())
-        {
+        for object in self.objects().await.into_iter().flatten() {
             let Ok(block) = object.block().await else {
                 continue;
             };
@@ -209,19 +658,43 @@ impl Client {
         blocks
     }
 
+    /// Gets all the [`block::BlockProxy`] instances whose label matches `label`
+    /// using the given [`MatchOptions`].
+    ///
+    /// This is useful for labels entered by users, as labels may contain trailing whitespace
+    /// or differ in case. Note that some filesystems, such as FAT, store labels in upper case,
+    /// which is a common source of mismatches.
+    ///
+    /// If no instances are found, the returned vector is empty.
+    pub async fn block_for_label_matching(
+        &self,
+        label: &str,
+        options: MatchOptions,
+    ) -> Vec<block::BlockProxy<'_>> {
+        let mut blocks = Vec::new();
+
+        for object in self.objects().await.into_iter().flatten() {
+            let Ok(block) = object.block().await else {
+                continue;
+            };
+
+            if block
+                .id_label()
+                .await
+                .is_ok_and(|block_label| options.matches(&block_label, label))
+            {
+                blocks.push(block);
+            }
+        }
+        blocks
+    }
+
     /// Gets all the [`block::BlockProxy`]s for the given `uuid`.
     ///
     /// If no blocks are found, the returned vector is empty.
-    pub async fn block_for_uuid(&self, uuid: &str) -> Vec<block::BlockProxy> {
+    pub async fn block_for_uuid(&self, uuid: &str) -> Vec<block::BlockProxy<'_>> {
         let mut blocks = Vec::new();
-        for object in self
-            .object_manager
-            .get_managed_objects()
-            .await
-            .into_iter()
-            .flatten()
-            .filter_map(|(object_path, _)| self.object(object_path).ok())
-        {
+        for object in self.objects().await.into_iter().flatten() {
             let Ok(block) = object.block().await else {
                 continue;
             };
@@ -233,30 +706,101 @@ impl Client {
         blocks
     }
 
-    /// Returns all top-level [`Object`]s for the given drive.
+    /// Gets all the [`block::BlockProxy`]s for the given partition `uuid`.
+    ///
+    /// Not to be confused with [`Self::block_for_uuid`]:
+    /// - The filesystem UUID ([`block::BlockProxy::id_uuid`], `UUID=` in `/etc/fstab`) is stored
+    ///   inside the filesystem and changes when the partition is formatted.
+    /// - The partition UUID ([`partition::PartitionProxy::uuid`], `PARTUUID=` in `/etc/fstab`) is
+    ///   stored in the partition table and is kept when the partition is formatted.
+    /// - The partition label ([`partition::PartitionProxy::name`], `PARTLABEL=` in `/etc/fstab`) is
+    ///   also stored in the partition table, but is not unique.
+    ///
+    /// The lookup is done using [`manager::ManagerProxy::resolve_device`]. If this is not supported
+    /// by the daemon, all partitions are scanned instead.
+    ///
+    /// If no blocks are found, the returned vector is empty.
+    pub async fn block_for_partuuid(&self, uuid: &str) -> Vec<block::BlockProxy<'_>> {
+        let devspec = manager::DevSpec::new().partuuid(uuid);
+        if let Ok(objects) = self.resolve_device(devspec).await {
+            let mut blocks = Vec::new();
+            for object in objects {
+                if let Ok(block) = object.block().await {
+                    blocks.push(block);
+                }
+            }
+            return blocks;
+        }
+
+        let mut blocks = Vec::new();
+        for object in self.objects().await.into_iter().flatten() {
+            let Ok(partition) = object.partition().await else {
+                continue;
+            };
+
+            if partition
+                .uuid()
+                .await
+                .is_ok_and(|partuuid| partuuid.eq_ignore_ascii_case(uuid))
+            {
+                if let Ok(block) = object.block().await {
+                    blocks.push(block);
+                }
+            }
+        }
+        blocks
+    }
+
+    /// Returns all block [`Object`]s for the given drive, including partitions.
     ///
-    /// Top-level blocks are blocks that do not have a partition associated with it.
-    async fn top_level_blocks_for_drive(&self, drive_object_path: &OwnedObjectPath) -> Vec<Object> {
+    /// If `collapse_multipath` is set and the drive is accessed using multipath, only the
+    /// device-mapper device combining the paths and its partitions are returned, instead of
+    /// the block devices of each path (see [`Self::block_for_drive`]).
+    async fn all_blocks_for_drive(
+        &self,
+        drive_object_path: &OwnedObjectPath,
+        collapse_multipath: bool,
+    ) -> Vec<Object> {
         let mut blocks = Vec::new();
         for object in self
-            .object_manager
-            .get_managed_objects()
+            .block_candidates(drive_object_path)
             .await
             .into_iter()
-            .flatten()
-            .filter_map(|(object_path, _)| self.object(object_path).ok())
+            .filter_map(|object_path| self.object(object_path).ok())
         {
             let Ok(block) = object.block().await else {
                 continue;
             };
 
-            if block.drive().await.as_deref() == Ok(drive_object_path)
-                && object.partition().await.is_err()
-            {
-                blocks.push(object);
+            if block.drive().await.as_deref() == Ok(drive_object_path) {
+                let device_mapper = block
+                    .device()
+                    .await
+                    .is_ok_and(|device| device.starts_with(b"/dev/dm-"));
+                let table = match object.partition().await {
+                    Ok(partition) => partition.table().await.ok(),
+                    Err(_) => None,
+                };
+                blocks.push((object, device_mapper, table));
             }
         }
+
+        let multipath: Vec<OwnedObjectPath> = blocks
+            .iter()
+            .filter(|(_, device_mapper, table)| *device_mapper && table.is_none())
+            .map(|(object, _, _)| object.object_path().clone())
+            .collect();
+        if !collapse_multipath || multipath.is_empty() {
+            return blocks.into_iter().map(|(object, _, _)| object).collect();
+        }
+
         blocks
+            .into_iter()
+            .filter(|(object, _, table)| {
+                multipath.contains(table.as_ref().unwrap_or(object.object_path()))
+            })
+            .map(|(object, _, _)| object)
+            .collect()
     }
 
     /// Gets the [`block::BlockProxy`], if exists, for the given [`drive::DriveProxy`]
@@ -267,304 +811,2268 @@ impl Client {
     /// If `physical` is set to true, a block that is able to send low-level SCSI commands is
     /// returned. If `physical` is set to false, a block device that can read/write data is
     /// returned.
+    ///
+    /// This only makes a difference if multiple block devices belong to the drive,
+    /// e.g. for multipath setups, where each path (`/dev/sda`, `/dev/sdb`) and the
+    /// device-mapper device combining them (`/dev/dm-0`) belong to the same drive.
+    /// The block is selected by the following rules:
+    ///
+    /// - if `physical` is set, blocks that are not device-mapper devices are preferred
+    /// - otherwise, device-mapper devices are preferred, as the paths are in use by them
+    /// - cleartext devices of encrypted devices are only returned if there is no other block
+    ///
+    /// Low-level interfaces such as [`ata::AtaProxy`] are exported on the drive itself,
+    /// so the physical block is chosen by its device type, not by the interfaces it implements.
     pub async fn block_for_drive(
         &self,
         drive: &drive::DriveProxy<'_>,
-        _physical: bool,
-    ) -> Option<block::BlockProxy> {
-        let object = self.object(drive.inner().path().clone()).ok()?;
-
-        for object in self
-            .top_level_blocks_for_drive(object.object_path())
+        physical: bool,
+    ) -> Option<block::BlockProxy<'_>> {
+        self.block_for_drive_cancellable(drive, physical, future::pending())
             .await
-            .iter()
-        {
-            if let Ok(block) = object.block().await {
-                return Some(block);
-            };
-        }
-        None
     }
 
-    /// Gets the [`drive::DriveProxy`] for the given [`block::BlockProxy`], if any.
+    /// Like [`Self::block_for_drive`], but stops scanning the objects once `cancel` completes.
     ///
-    /// # Errors
-    /// If no drive is found, [`zbus::Error::InterfaceNotFound`] is returned.
-    pub async fn drive_for_block(
+    /// `cancel` is checked before each object is looked at. If it completes, [`None`] is returned,
+    /// even if a block was already found, as it may not be the one preferred by
+    /// [`Self::block_for_drive`].
+    pub async fn block_for_drive_cancellable(
         &self,
-        block: &block::BlockProxy<'_>,
-    ) -> error::Result<drive::DriveProxy<'static>> {
-        let drive = block.drive().await?;
-        self.object(drive)?.drive().await
+        drive: &drive::DriveProxy<'_>,
+        physical: bool,
+        cancel: impl Future<Output = ()>,
+    ) -> Option<block::BlockProxy<'_>> {
+        let mut cancel = std::pin::pin!(cancel);
+        let drive_object_path = OwnedObjectPath::from(drive.inner().path().clone());
+
+        let mut best: Option<(u8, block::BlockProxy<'_>)> = None;
+        for object in self
+            .block_candidates(&drive_object_path)
+            .await
+            .into_iter()
+            .filter_map(|object_path| self.object(object_path).ok())
+        {
+            if is_cancelled(&mut cancel) {
+                return None;
+            }
+
+            let Ok(block) = object.block().await else {
+                continue;
+            };
+            if block.drive().await.as_ref() != Ok(&drive_object_path)
+                || object.partition().await.is_ok()
+            {
+                continue;
+            }
+
+            let device_mapper = block
+                .device()
+                .await
+                .is_ok_and(|device| device.starts_with(b"/dev/dm-"));
+            let cleartext = block
+                .crypto_backing_device()
+                .await
+                .is_ok_and(|backing_device| backing_device.as_str() != "/");
+            let rank = match (cleartext, device_mapper == physical) {
+                (true, _) => 2,
+                (false, true) => 1,
+                (false, false) => 0,
+            };
+            if best.as_ref().is_none_or(|(best_rank, _)| rank < *best_rank) {
+                best = Some((rank, block));
+            }
+        }
+        best.map(|(_, block)| block)
     }
 
-    /// If the given [`block::BlockProxy`] is an encrypted device, returns the cleartext device.
+    /// Returns all drives.
+    pub async fn drives(&self) -> Vec<drive::DriveProxy<'static>> {
+        let drives = self.drive_paths_with_property("SortKey").await;
+        self.drive_proxies(drives).await
+    }
+
+    /// Returns all drives, ordered by their [`drive::DriveProxy::sort_key`].
     ///
-    /// If no block is found, [`None`] is returned.
-    pub async fn cleartext_block(
+    /// This is the order in which UDisks intends drives to be displayed.
+    /// The sort keys are read from a single snapshot of all objects, instead of
+    /// reading the property of each drive separately.
+    ///
+    /// Sort keys are opaque and only meaningful for comparison with other sort keys.
+    pub async fn sorted_drives(&self) -> Vec<drive::DriveProxy<'static>> {
+        let mut drives = self.drive_paths_with_property("SortKey").await;
+        drives.sort_by(|(_, a), (_, b)| a.cmp(b));
+
+        self.drive_proxies(drives).await
+    }
+
+    /// Returns all drives, ordered as in [`Self::sorted_drives`], with duplicate drives collapsed.
+    ///
+    /// Drives with the same non-empty [`drive::DriveProxy::wwn`] are the same disk, exposed
+    /// multiple times, e.g. through several paths to an enterprise storage array.
+    /// Of each group of duplicates, only the first drive is returned,
+    /// so that e.g. the capacity of a disk is not counted multiple times.
+    ///
+    /// Drives with the same [`drive::DriveProxy::sibling_id`] are not collapsed, as they are
+    /// separate drives of one physical device, e.g. the slots of a card reader,
+    /// see [`Self::drive_siblings`].
+    ///
+    /// Collapsing is opt-in, [`Self::drives`] and [`Self::sorted_drives`] return all drives.
+    pub async fn unique_drives(&self) -> Vec<drive::DriveProxy<'static>> {
+        let mut wwns = Vec::new();
+        let mut drives = Vec::new();
+        for drive in self.sorted_drives().await {
+            let wwn = drive.wwn().await.unwrap_or_default();
+            if !wwn.is_empty() {
+                if wwns.contains(&wwn) {
+                    continue;
+                }
+                wwns.push(wwn);
+            }
+            drives.push(drive);
+        }
+        drives
+    }
+
+    /// Returns all drives, grouped as in the sidebar of a disk manager.
+    ///
+    /// The groups are ordered as [`drive::DriveGroup`], empty groups are omitted.
+    /// Within a group, drives are ordered as in [`Self::sorted_drives`].
+    ///
+    /// The classification is a best-effort heuristic, checked in the following order:
+    ///
+    /// 1. drives compatible with optical media are [`drive::DriveGroup::Optical`]
+    /// 2. drives with removable flash media, or connected using SDIO, are
+    ///    [`drive::DriveGroup::CardReader`]
+    /// 3. drives connected using USB are [`drive::DriveGroup::Usb`]
+    /// 4. non-removable drives on an internal bus are [`drive::DriveGroup::Internal`]
+    /// 5. all other drives, e.g. FireWire drives, are [`drive::DriveGroup::Other`]
+    pub async fn drives_grouped(
         &self,
-        block: &block::BlockProxy<'_>,
-    ) -> Option<block::BlockProxy<'_>> {
-        let object_path = block.inner().path().to_owned().into();
-        for object in self
-            .object_manager
+    ) -> Vec<(drive::DriveGroup, Vec<drive::DriveProxy<'static>>)> {
+        let mut groups: Vec<(drive::DriveGroup, Vec<drive::DriveProxy<'static>>)> = Vec::new();
+        for drive in self.sorted_drives().await {
+            let group = Self::drive_group(&drive).await;
+            match groups.iter_mut().find(|(g, _)| *g == group) {
+                Some((_, drives)) => drives.push(drive),
+                None => groups.push((group, vec![drive])),
+            }
+        }
+        groups.sort_by_key(|(group, _)| *group);
+        groups
+    }
+
+    async fn drive_group(drive: &drive::DriveProxy<'_>) -> drive::DriveGroup {
+        let media_compat = drive.media_compatibility().await.unwrap_or_default();
+        let has_category = |category| {
+            media_compat
+                .iter()
+                .any(|media| media.category() == category)
+        };
+        if has_category(drive::MediaCategory::Optical) {
+            return drive::DriveGroup::Optical;
+        }
+
+        let bus = drive
+            .connection_bus_typed()
+            .await
+            .unwrap_or(drive::ConnectionBus::Unknown);
+        let media_removable = drive.media_removable().await.unwrap_or_default();
+        if bus == drive::ConnectionBus::Sdio
+            || (media_removable && has_category(drive::MediaCategory::Flash))
+        {
+            return drive::DriveGroup::CardReader;
+        }
+        if bus == drive::ConnectionBus::Usb {
+            return drive::DriveGroup::Usb;
+        }
+        if bus == drive::ConnectionBus::Unknown && !drive.removable().await.unwrap_or_default() {
+            return drive::DriveGroup::Internal;
+        }
+        drive::DriveGroup::Other
+    }
+
+    /// Returns all drives attached to the given `seat`, e.g. `seat0`.
+    ///
+    /// On multi-seat systems, [logind](https://www.freedesktop.org/wiki/Software/systemd/multiseat/)
+    /// assigns devices to seats. Drives with a blank [`drive::DriveProxy::seat`] are not assigned
+    /// to any seat and are therefore returned for every seat.
+    ///
+    /// See [`Self::current_seat`] for getting the seat of the current session.
+    pub async fn drives_for_seat(&self, seat: &str) -> Vec<drive::DriveProxy<'static>> {
+        let drives = self
+            .drive_paths_with_property("Seat")
+            .await
+            .into_iter()
+            .filter(|(_, drive_seat)| drive_seat.is_empty() || drive_seat == seat)
+            .collect();
+        self.drive_proxies(drives).await
+    }
+
+    /// Returns the seat of the current session, as set by logind in `$XDG_SEAT`.
+    ///
+    /// If the variable is unset or empty, e.g. for remote sessions, [`None`] is returned.
+    pub fn current_seat(&self) -> Option<String> {
+        std::env::var("XDG_SEAT")
+            .ok()
+            .filter(|seat| !seat.is_empty())
+    }
+
+    /// Returns the object paths of all drives, together with the given string `property`.
+    async fn drive_paths_with_property(&self, property: &str) -> Vec<(OwnedObjectPath, String)> {
+        self.object_manager
             .get_managed_objects()
             .await
             .into_iter()
             .flatten()
-            .filter_map(|(object_path, _)| self.object(object_path).ok())
-        {
-            let Ok(block) = object.block().await else {
+            .filter_map(|(object_path, interfaces)| {
+                let properties = interfaces.get("org.freedesktop.UDisks2.Drive")?;
+                let value = properties
+                    .get(property)
+                    .and_then(|value| <&str>::try_from(value).ok())
+                    .unwrap_or_default()
+                    .to_owned();
+                Some((object_path, value))
+            })
+            .collect()
+    }
+
+    /// Creates the [`drive::DriveProxy`]s for the given object paths, skipping failed ones.
+    async fn drive_proxies(
+        &self,
+        drives: Vec<(OwnedObjectPath, String)>,
+    ) -> Vec<drive::DriveProxy<'static>> {
+        let mut proxies = Vec::with_capacity(drives.len());
+        for (object_path, _) in drives {
+            let Ok(builder) = drive::DriveProxy::builder(&self.connection).path(object_path) else {
                 continue;
             };
-            if block.crypto_backing_device().await.as_ref() == Ok(&object_path) {
-                return Some(block);
+            if let Ok(drive) = builder.build().await {
+                proxies.push(drive);
             }
         }
-        None
+        proxies
     }
 
-    /// Returns the [`partitiontable::PartitionTableProxy`] for the given partition.
+    /// Returns the unallocated regions of the given partition table, sorted by offset.
+    ///
+    /// The first MiB of the device and, for `gpt` partition tables, the backup header
+    /// at the end are not considered free. The size of the backup header depends on the
+    /// logical block size of the device, which is read from sysfs, as it is not exported
+    /// by the daemon. If it can not be read, 512 bytes are assumed.
+    /// For `dos` partition tables, the free space within an extended partition is returned as
+    /// [`partitiontable::FreeRegion::contained`] regions.
     ///
     /// # Errors
-    /// Returns an error if it is unable to get the table or the [`Object`] for the table.
-    pub async fn partition_table(
+    /// Returns an error if the size of the table or its partitions could not be read.
+    pub async fn free_regions(
         &self,
-        partition: &partition::PartitionProxy<'_>,
-    ) -> error::Result<partitiontable::PartitionTableProxy<'_>> {
-        self.object(partition.table().await?)?
-            .partition_table()
+        table: &partitiontable::PartitionTableProxy<'_>,
+    ) -> error::Result<Vec<partitiontable::FreeRegion>> {
+        let block = self.object(table.inner().path().clone())?.block().await?;
+        let size = block.size().await?;
+        let logical_block_size = logical_block_size(&block.device_path().await?)
+            .unwrap_or(partitiontable::DEFAULT_LOGICAL_BLOCK_SIZE);
+        let (start, reserved_end) = table.table_type().await?.reserved_space(logical_block_size);
+        let end = size.saturating_sub(reserved_end);
+
+        let mut occupied = Vec::new();
+        let mut contained = Vec::new();
+        let mut container = None;
+        for partition in self.partitions(table).await {
+            let region = (partition.offset().await?, partition.size().await?);
+            if partition.is_contained().await? {
+                contained.push(region);
+            } else {
+                if partition.is_container().await? {
+                    container = Some(region);
+                }
+                occupied.push(region);
+            }
+        }
+
+        let mut regions = partitiontable::free_regions(start, end, occupied, false);
+        if let Some((offset, size)) = container {
+            regions.extend(partitiontable::free_regions(
+                offset,
+                offset.saturating_add(size),
+                contained,
+                true,
+            ));
+            regions.sort_unstable_by_key(|region| region.offset);
+        }
+        Ok(regions)
+    }
+
+    /// Creates a new partition on the given partition table and waits until it is available.
+    ///
+    /// The interfaces of a newly created partition appear asynchronously, so the object path
+    /// returned by [`partitiontable::PartitionTableProxy::create_partition`] may not be usable
+    /// immediately. This waits for the partition interface of the new object to be exported,
+    /// so the returned [`partition::PartitionProxy`] is ready to use.
+    ///
+    /// Before calling the daemon, the requested region is checked against the
+    /// [`Self::free_regions`] of the table. `logical` partitions must fit into the free space
+    /// of the extended partition, all others into the free space of the table itself.
+    ///
+    /// The check uses the offset and size as given. The daemon aligns the partition afterwards
+    /// (usually to 1 MiB), which may move its start forward and round its size up. A request
+    /// that only just fits into a free region may therefore still be rejected by the daemon,
+    /// so prefer aligned offsets and sizes, or a size of 0 to fill the free region.
+    ///
+    /// # Errors
+    /// Returns [`Error::PartitionOverlap`] if the requested region does not fit into a free region,
+    /// or [`Error::TimedOut`] if the partition did not appear within
+    /// [`partitiontable::PartitionSpec::timeout`].
+    pub async fn create_partition(
+        &self,
+        table: &partitiontable::PartitionTableProxy<'_>,
+        spec: partitiontable::PartitionSpec,
+    ) -> error::Result<partition::PartitionProxy<'static>> {
+        let fits = self.free_regions(table).await?.iter().any(|region| {
+            region.contained == spec.is_logical() && region.contains(spec.offset(), spec.size())
+        });
+        if !fits {
+            return Err(Error::PartitionOverlap {
+                offset: spec.offset(),
+                size: spec.size(),
+            });
+        }
+
+        // subscribe before creating the partition to not miss the signal
+        let mut interfaces_added = self.object_manager.receive_interfaces_added().await?;
+        let object_path = table
+            .create_partition(
+                spec.offset(),
+                spec.size(),
+                spec.partition_type_str(),
+                spec.name_str(),
+                spec.options(),
+            )
+            .await?;
+        let object = self.object(object_path)?;
+
+        let wait = std::pin::pin!(async {
+            loop {
+                if let Ok(partition) = object.partition().await {
+                    return Ok(partition);
+                }
+                if interfaces_added.next().await.is_none() {
+                    return Err(zbus::Error::InterfaceNotFound.into());
+                }
+            }
+        });
+
+        match future::select(wait, async_io::Timer::after(spec.wait_timeout())).await {
+            future::Either::Left((partition, _)) => partition,
+            future::Either::Right(_) => Err(Error::TimedOut),
+        }
+    }
+
+    /// Waits until the value of `property` of `interface` on the given object satisfies `predicate`.
+    ///
+    /// The current value is checked first, so this returns immediately if it already satisfies
+    /// the predicate. Otherwise the `PropertiesChanged` signals of the object are watched
+    /// until the predicate holds, which can be used to e.g. wait until a drive reports available media:
+    ///
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # async fn run(client: &udisks2::Client, drive: &udisks2::Object) -> udisks2::Result<()> {
+    /// client
+    ///     .wait_for_property(
+    ///         drive,
+    ///         "org.freedesktop.UDisks2.Drive",
+    ///         "MediaAvailable",
+    ///         |available: &bool| *available,
+    ///         Duration::from_secs(30),
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// The property is decoded using the [`TryFrom<OwnedValue>`](struct@zbus::zvariant::OwnedValue)
+    /// implementation of `T`, so `T` must match the D-Bus type of the property.
+    /// Returns the value satisfying the predicate.
+    ///
+    /// # Errors
+    /// Returns [`Error::TimedOut`] if the predicate does not hold within `timeout`, or an error
+    /// if the property could not be read or decoded.
+    pub async fn wait_for_property<T>(
+        &self,
+        object: &Object,
+        interface: &str,
+        property: &str,
+        predicate: impl Fn(&T) -> bool,
+        timeout: Duration,
+    ) -> error::Result<T>
+    where
+        T: TryFrom<zbus::zvariant::OwnedValue, Error = zbus::zvariant::Error>,
+    {
+        let interface =
+            zbus::names::InterfaceName::try_from(interface).map_err(zbus::Error::from)?;
+        let properties = zbus::fdo::PropertiesProxy::builder(&self.connection)
+            .destination("org.freedesktop.UDisks2")?
+            .path(object.object_path().clone())?
+            .build()
+            .await?;
+        // subscribe before reading the current value to not miss a change
+        let mut changes = properties.receive_properties_changed().await?;
+
+        let wait = std::pin::pin!(async {
+            let mut value = T::try_from(properties.get(interface.as_ref(), property).await?)?;
+            loop {
+                if predicate(&value) {
+                    return Ok(value);
+                }
+                loop {
+                    let Some(signal) = changes.next().await else {
+                        return Err(zbus::Error::InterfaceNotFound.into());
+                    };
+                    let args = signal.args()?;
+                    if args.interface_name() != &interface {
+                        continue;
+                    }
+                    if let Some(changed) = args.changed_properties().get(property) {
+                        value = T::try_from(changed.try_to_owned()?)?;
+                        break;
+                    }
+                    if args.invalidated_properties().contains(&property) {
+                        value = T::try_from(properties.get(interface.as_ref(), property).await?)?;
+                        break;
+                    }
+                }
+            }
+        });
+
+        match future::select(wait, async_io::Timer::after(timeout)).await {
+            future::Either::Left((value, _)) => value,
+            future::Either::Right(_) => Err(Error::TimedOut),
+        }
+    }
+
+    /// Enables or disables the write cache of the given drive.
+    ///
+    /// The setting is stored in the drive configuration (see [`drive::DriveProxy::configuration`]),
+    /// which is persisted in `/etc/udisks2/` and applied again whenever the drive is connected
+    /// (i.e. start-up, hotplug or resume). Other configuration directives are preserved.
+    ///
+    /// # Errors
+    /// Returns [`Error::NotSupported`] if the drive is not an ATA drive or does not support a write cache.
+    pub async fn set_write_cache(
+        &self,
+        drive: &drive::DriveProxy<'_>,
+        enabled: bool,
+    ) -> error::Result<()> {
+        let ata = self.ata_for_drive(drive).await?;
+        if !ata.write_cache_supported().await? {
+            return Err(Error::NotSupported);
+        }
+        self.set_drive_configuration(drive, "ata-write-cache-enabled", enabled.into())
             .await
     }
 
-    /// Returns the [`loop::LoopProxy`] for the given [`block::BlockProxy`].
+    /// Enables or disables read look-ahead of the given drive.
     ///
-    /// This only works if the block is a loop device, or a partition of a loop device.
+    /// The setting is stored in the drive configuration (see [`drive::DriveProxy::configuration`]),
+    /// which is persisted in `/etc/udisks2/` and applied again whenever the drive is connected
+    /// (i.e. start-up, hotplug or resume). Other configuration directives are preserved.
     ///
     /// # Errors
-    /// Returns an error if it is unable to get the loop interface.
-    pub async fn loop_for_block(
+    /// Returns [`Error::NotSupported`] if the drive is not an ATA drive or does not support read look-ahead.
+    pub async fn set_read_lookahead(
         &self,
-        block: &block::BlockProxy<'_>,
-    ) -> error::Result<r#loop::LoopProxy> {
-        let object = self.object(block.inner().path().clone())?;
+        drive: &drive::DriveProxy<'_>,
+        enabled: bool,
+    ) -> error::Result<()> {
+        let ata = self.ata_for_drive(drive).await?;
+        if !ata.read_lookahead_supported().await? {
+            return Err(Error::NotSupported);
+        }
+        self.set_drive_configuration(drive, "ata-read-lookahead-enabled", enabled.into())
+            .await
+    }
 
-        if let Ok(loop_proxy) = object.r#loop().await {
-            return Ok(loop_proxy);
+    /// Returns how the given drive can be removed by the user, i.e. whether to show an eject
+    /// or power off button.
+    ///
+    /// The decision is based on the following drive properties:
+    ///
+    /// | [`removable`] | [`media_removable`] | [`ejectable`] | [`can_power_off`] | Result |
+    /// |---|---|---|---|---|
+    /// | no  | *   | *   | *   | [`Fixed`] |
+    /// | yes | yes | yes | *   | [`Eject`] |
+    /// | yes | *   | *   | yes | [`PowerOff`] |
+    /// | yes | *   | *   | no  | [`UnmountOnly`] |
+    ///
+    /// The rows are checked from top to bottom. Drives with removable media are thus ejected,
+    /// while hotpluggable drives with fixed media are powered off.
+    ///
+    /// [`removable`]: drive::DriveProxy::removable
+    /// [`media_removable`]: drive::DriveProxy::media_removable
+    /// [`ejectable`]: drive::DriveProxy::ejectable
+    /// [`can_power_off`]: drive::DriveProxy::can_power_off
+    /// [`Fixed`]: drive::Removability::Fixed
+    /// [`Eject`]: drive::Removability::Eject
+    /// [`PowerOff`]: drive::Removability::PowerOff
+    /// [`UnmountOnly`]: drive::Removability::UnmountOnly
+    ///
+    /// # Errors
+    /// Returns an error if the properties of the drive could not be read.
+    pub async fn removability(
+        &self,
+        drive: &drive::DriveProxy<'_>,
+    ) -> error::Result<drive::Removability> {
+        if !drive.removable().await? {
+            return Ok(drive::Removability::Fixed);
+        }
+        let can_power_off = drive.can_power_off().await?;
+        if drive.media_removable().await? && drive.ejectable().await? {
+            return Ok(drive::Removability::Eject { can_power_off });
         }
+        Ok(if can_power_off {
+            drive::Removability::PowerOff
+        } else {
+            drive::Removability::UnmountOnly
+        })
+    }
 
-        // possibly partition of a loop device
-        let partition = object.partition().await?;
-        let partitiontable = self.partition_table(&partition).await?;
-        let partitiontable_object = self.object(partitiontable.inner().path().clone())?;
-        partitiontable_object.r#loop().await
+    /// Returns a summary of the optical disc in the given drive.
+    ///
+    /// If the drive is not an optical drive or contains no disc, [`None`] is returned.
+    ///
+    /// # Errors
+    /// Returns an error if the properties of the drive could not be read.
+    pub async fn optical_disc_info(
+        &self,
+        drive: &drive::DriveProxy<'_>,
+    ) -> error::Result<Option<drive::OpticalDiscInfo>> {
+        if !drive.optical().await? || !drive.media_available().await? {
+            return Ok(None);
+        }
+
+        let media = drive.media().await?;
+        Ok(Some(drive::OpticalDiscInfo {
+            blank: drive.optical_blank().await?,
+            num_sessions: drive.optical_num_sessions().await?,
+            num_tracks: drive.optical_num_tracks().await?,
+            num_audio_tracks: drive.optical_num_audio_tracks().await?,
+            num_data_tracks: drive.optical_num_data_tracks().await?,
+            media_type: crate::media::MEDIA_DATA
+                .iter()
+                .find(|media_data| media_data.id == media)
+                .map(|media_data| dpgettext("media-type", media_data.media_name)),
+            capacity: drive.size().await?,
+        }))
     }
 
-    /// Returns all [`partition::PartitionProxy`] of the given [`partitiontable::PartitionTableProxy`].
-    pub async fn partitions(
+    /// Returns the power management capabilities and state of the given drive.
+    ///
+    /// If `query_state` is set, the current power mode is queried using
+    /// [`ata::AtaProxy::pm_get_state`]. This does not wake up a drive in standby, but still sends
+    /// a command to it, so it can be skipped e.g. when polling periodically.
+    ///
+    /// Drives that are not ATA drives return a status with only
+    /// [`ata::PowerStatus::can_power_off`] set.
+    ///
+    /// # Errors
+    /// Returns an error if the properties of the drive could not be read, or the power mode
+    /// could not be queried.
+    pub async fn power_management_status(
         &self,
-        table: &partitiontable::PartitionTableProxy<'_>,
-    ) -> Vec<partition::PartitionProxy<'_>> {
-        let mut partitions = Vec::new();
-        // safe to unwrap as the table's object path does not need to be converted
-        let table_object = self.object(table.inner().path().clone()).unwrap();
-        let table_object_path = table_object.object_path();
+        drive: &drive::DriveProxy<'_>,
+        query_state: bool,
+    ) -> error::Result<ata::PowerStatus> {
+        let mut status = ata::PowerStatus {
+            can_power_off: drive.can_power_off().await?,
+            ..Default::default()
+        };
+        let Ok(ata) = self.ata_for_drive(drive).await else {
+            return Ok(status);
+        };
 
-        for object in self
-            .object_manager
-            .get_managed_objects()
+        status.is_ata = true;
+        status.pm_supported = ata.pm_supported().await?;
+        status.pm_enabled = ata.pm_enabled().await?;
+        status.apm_supported = ata.apm_supported().await?;
+        status.apm_enabled = ata.apm_enabled().await?;
+        status.aam_supported = ata.aam_supported().await?;
+        status.aam_enabled = ata.aam_enabled().await?;
+        if query_state {
+            status.state = Some(ata.pm_get_state(HashMap::new()).await?.into());
+        }
+        Ok(status)
+    }
+
+    /// Returns the estimated duration of an ATA secure erase of the given drive.
+    ///
+    /// If `enhanced` is set, the estimate for an enhanced secure erase is returned.
+    /// Returns `None` if the drive is not an ATA drive or does not report an estimate.
+    ///
+    /// The drive reports the estimate in steps of two minutes, with the value `510` meaning
+    /// that the erase takes at least 508 minutes. In that case
+    /// [`ata::EraseEstimate::Unbounded`] with 508 minutes is returned, as the actual
+    /// duration may be arbitrarily longer.
+    ///
+    /// # Errors
+    /// Returns an error if the estimate could not be read.
+    pub async fn secure_erase_estimate(
+        &self,
+        drive: &drive::DriveProxy<'_>,
+        enhanced: bool,
+    ) -> error::Result<Option<ata::EraseEstimate>> {
+        /// Value reported by drives that take at least 508 minutes.
+        const AT_LEAST_508_MINUTES: i32 = 510;
+
+        let Ok(ata) = self.ata_for_drive(drive).await else {
+            return Ok(None);
+        };
+        let minutes = if enhanced {
+            ata.security_enhanced_erase_unit_minutes().await?
+        } else {
+            ata.security_erase_unit_minutes().await?
+        };
+        Ok(match minutes {
+            ..=0 => None,
+            AT_LEAST_508_MINUTES => {
+                Some(ata::EraseEstimate::Unbounded(Duration::from_secs(508 * 60)))
+            }
+            minutes => Some(ata::EraseEstimate::Bounded(Duration::from_secs(
+                minutes as u64 * 60,
+            ))),
+        })
+    }
+
+    /// Returns the [`ata::AtaProxy`] of the given drive, or [`Error::NotSupported`] if it is not an ATA drive.
+    async fn ata_for_drive(
+        &self,
+        drive: &drive::DriveProxy<'_>,
+    ) -> error::Result<ata::AtaProxy<'static>> {
+        self.object(drive.inner().path().clone())?
+            .drive_ata()
             .await
-            .into_iter()
-            .flatten()
-            .filter_map(|(object_path, _)| self.object(object_path).ok())
-        {
+            .map_err(|_| Error::NotSupported)
+    }
+
+    /// Sets the configuration directive `key` of the given drive to `value`,
+    /// keeping all other directives.
+    async fn set_drive_configuration(
+        &self,
+        drive: &drive::DriveProxy<'_>,
+        key: &str,
+        value: Value<'_>,
+    ) -> error::Result<()> {
+        let configuration = drive.configuration().await?;
+        let mut new_configuration = configuration
+            .iter()
+            .map(|(key, value)| Ok((key.as_str(), Value::from(value.try_clone()?))))
+            .collect::<zbus::zvariant::Result<HashMap<&str, Value<'_>>>>()?;
+        new_configuration.insert(key, value);
+        drive
+            .set_configuration(new_configuration, HashMap::new())
+            .await
+    }
+
+    /// Returns all EFI System Partitions (ESPs).
+    ///
+    /// Partitions on `gpt` partition tables are matched by their type GUID
+    /// ([`partition_types::ESP_GUID`]), partitions on `dos` partition tables by the type `0xef`
+    /// ([`partition_types::ESP_DOS_TYPE`]).
+    pub async fn efi_system_partitions(&self) -> Vec<partition::PartitionProxy<'static>> {
+        let mut partitions = Vec::new();
+        for object in self.objects().await.into_iter().flatten() {
             let Ok(partition) = object.partition().await else {
                 continue;
             };
 
-            if partition.table().await.as_ref() == Ok(table_object_path) {
+            if partition
+                .type_()
+                .await
+                .is_ok_and(|ty| partition_types::is_esp_type(&ty))
+            {
                 partitions.push(partition);
             }
         }
         partitions
     }
 
-    /// Returns all [`partition::PartitionProxy`] of the given [`partitiontable::PartitionTableProxy`].
-    pub async fn drive_siblings(
+    /// Returns the EFI System Partition (ESP) on the given drive, if any.
+    ///
+    /// If the drive contains multiple ESPs, the one with the lowest partition number is returned.
+    /// See [`Self::efi_system_partitions`] for how ESPs are detected.
+    pub async fn esp_for_drive(
         &self,
         drive: &drive::DriveProxy<'_>,
-    ) -> Vec<drive::DriveProxy<'_>> {
-        let mut drive_siblings = Vec::new();
-        let sibling_id = drive.sibling_id().await;
+    ) -> Option<partition::PartitionProxy<'static>> {
+        let drive_object_path = drive.inner().path().to_owned().into();
+        let mut esp: Option<(u32, partition::PartitionProxy<'static>)> = None;
+        for object in self.all_blocks_for_drive(&drive_object_path, true).await {
+            let Ok(partition) = object.partition().await else {
+                continue;
+            };
+            if !partition
+                .type_()
+                .await
+                .is_ok_and(|ty| partition_types::is_esp_type(&ty))
+            {
+                continue;
+            }
 
-        if sibling_id.is_err() || sibling_id.as_ref().unwrap().is_empty() {
-            return drive_siblings;
+            let number = partition.number().await.unwrap_or(u32::MAX);
+            if esp
+                .as_ref()
+                .is_none_or(|(esp_number, _)| number < *esp_number)
+            {
+                esp = Some((number, partition));
+            }
         }
+        esp.map(|(_, partition)| partition)
+    }
 
-        for object in self
-            .object_manager
-            .get_managed_objects()
-            .await
+    /// Gets the [`drive::DriveProxy`] for the given [`block::BlockProxy`], if any.
+    ///
+    /// # Errors
+    /// If no drive is found, [`zbus::Error::InterfaceNotFound`] is returned.
+    pub async fn drive_for_block(
+        &self,
+        block: &block::BlockProxy<'_>,
+    ) -> error::Result<drive::DriveProxy<'static>> {
+        let drive = block.drive().await?;
+        self.object(drive)?.drive().await
+    }
+
+    /// Classifies the contents of the given block device.
+    ///
+    /// The role is determined using the interfaces of the block device and its
+    /// [`block::BlockProxy::id_usage`] and [`block::BlockProxy::id_type`].
+    /// If multiple roles apply, e.g. for hybrid ISO images, which contain both a partition table
+    /// and a filesystem, the first matching role is returned:
+    ///
+    /// 1. [`block::DeviceRole::PartitionTable`], if the device has a partition table
+    /// 2. [`block::DeviceRole::RaidMember`], if the device is a member of a MD-RAID array,
+    ///    or its type is `linux_raid_member`
+    /// 3. [`block::DeviceRole::Encrypted`], if the device is encrypted, or its usage is `crypto`
+    /// 4. [`block::DeviceRole::Swap`], if the device contains swap space
+    /// 5. [`block::DeviceRole::Filesystem`], if the device contains a filesystem
+    /// 6. [`block::DeviceRole::Other`], if the usage is set to any other value
+    /// 7. [`block::DeviceRole::Empty`] otherwise
+    ///
+    /// # Errors
+    /// Returns an error if the properties of the block device could not be read.
+    pub async fn device_role(
+        &self,
+        block: &block::BlockProxy<'_>,
+    ) -> error::Result<block::DeviceRole> {
+        let snapshot = self.snapshot().await;
+        let path = OwnedObjectPath::from(block.inner().path().clone());
+        let has_interface = |interface| {
+            snapshot.has_interface(&path, &format!("org.freedesktop.UDisks2.{}", interface))
+        };
+        let usage = block.id_usage().await?;
+        let ty = block.id_type().await?;
+
+        let role = if has_interface("PartitionTable") {
+            block::DeviceRole::PartitionTable
+        } else if block.mdraid_member().await?.as_str() != "/" || ty == "linux_raid_member" {
+            block::DeviceRole::RaidMember
+        } else if has_interface("Encrypted") || usage == block::IdUsage::Crypto {
+            block::DeviceRole::Encrypted
+        } else if has_interface("Swapspace") || (usage == block::IdUsage::Other && ty == "swap") {
+            block::DeviceRole::Swap
+        } else if has_interface("Filesystem") || usage == block::IdUsage::Filesystem {
+            block::DeviceRole::Filesystem
+        } else if usage != block::IdUsage::Unknown {
+            block::DeviceRole::Other { usage, ty }
+        } else {
+            block::DeviceRole::Empty
+        };
+        Ok(role)
+    }
+
+    /// Returns the NVMe controller of the given namespace.
+    ///
+    /// Namespaces are exported on the block device of the namespace, e.g. `/dev/nvme0n1`,
+    /// while the controller is exported on the drive object, which also holds the
+    /// subsystem NQN ([`nvme::controller::ControllerProxy::subsystem_nqn`]).
+    /// The association is resolved using the [`block::BlockProxy::drive`] of the namespace,
+    /// rather than by matching subsystem NQNs: the namespace interface does not export the
+    /// NQN of its subsystem, and the daemon already links each namespace to the drive of the
+    /// controller it was probed from, which also picks a single controller for subsystems
+    /// that are reachable through multiple controllers.
+    ///
+    /// Returns `None` if the namespace has no drive, or the drive does not export a controller,
+    /// e.g. because the NVMe module of the daemon is not loaded.
+    ///
+    /// # Errors
+    /// Returns an error if the block device of the namespace could not be read.
+    pub async fn controller_for_namespace(
+        &self,
+        namespace: &nvme::namespace::NamespaceProxy<'_>,
+    ) -> error::Result<Option<nvme::controller::ControllerProxy<'static>>> {
+        let block = self
+            .object(namespace.inner().path().clone())?
+            .block()
+            .await?;
+        let drive = block.drive().await?;
+        if drive.as_str() == "/" {
+            return Ok(None);
+        }
+        Ok(self.object(drive)?.nvme_controller().await.ok())
+    }
+
+    /// Returns the block devices of the given namespace.
+    ///
+    /// This is the block device of the namespace itself, followed by the block devices of
+    /// its partitions ordered by their partition number, if it contains a partition table.
+    pub async fn blocks_for_namespace(
+        &self,
+        namespace: &nvme::namespace::NamespaceProxy<'_>,
+    ) -> Vec<block::BlockProxy<'static>> {
+        let snapshot = self.snapshot().await;
+        let path = OwnedObjectPath::from(namespace.inner().path().clone());
+
+        let mut partitions: Vec<&OwnedObjectPath> = snapshot
+            .objects_with(snapshot::PARTITION_INTERFACE, "Table", &path)
+            .collect();
+        partitions.sort_unstable_by_key(|path| snapshot.partition_number(path));
+
+        let mut blocks = Vec::new();
+        for object_path in std::iter::once(&path).chain(partitions) {
+            if let Some(block) = snapshot.proxy(object_path, snapshot::BLOCK_INTERFACE).await {
+                blocks.push(block);
+            }
+        }
+        blocks
+    }
+
+    /// Returns the best available persistent identifier for the given [`Object`].
+    ///
+    /// The identifier can be used to recognize the same device across reboots, e.g. to store
+    /// application state for it. The following precedence is used:
+    ///
+    /// 1. [`drive::DriveProxy::wwn`] of the drive
+    /// 2. [`drive::DriveProxy::id`] of the drive
+    /// 3. [`block::BlockProxy::id`] of the block device
+    ///
+    /// The drive identifiers are only used for drives and the whole-disk block devices
+    /// of drives, as they are the same for all partitions of a drive.
+    ///
+    /// If no persistent identifier is available, [`None`] is returned.
+    ///
+    /// # Errors
+    /// Returns an error if it fails to read the identifiers.
+    pub async fn stable_id_for_object(&self, object: &Object) -> error::Result<Option<String>> {
+        let block = object.block().await.ok();
+        let drive = match (object.drive().await, &block) {
+            (Ok(drive), _) => Some(drive),
+            (Err(_), Some(block)) if object.partition().await.is_err() => {
+                self.drive_for_block(block).await.ok()
+            }
+            _ => None,
+        };
+
+        if let Some(drive) = drive {
+            let wwn = drive.wwn().await?;
+            if !wwn.is_empty() {
+                return Ok(Some(wwn));
+            }
+            let id = drive.id().await?;
+            if !id.is_empty() {
+                return Ok(Some(id));
+            }
+        }
+
+        if let Some(block) = block {
+            let id = block.id().await?;
+            if !id.is_empty() {
+                return Ok(Some(id));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// If the given [`block::BlockProxy`] is an encrypted device, returns the cleartext device.
+    ///
+    /// If no block is found, [`None`] is returned.
+    pub async fn cleartext_block(
+        &self,
+        block: &block::BlockProxy<'_>,
+    ) -> Option<block::BlockProxy<'_>> {
+        let object_path = block.inner().path().to_owned().into();
+        for object in self.objects().await.into_iter().flatten() {
+            let Ok(block) = object.block().await else {
+                continue;
+            };
+            if block.crypto_backing_device().await.as_ref() == Ok(&object_path) {
+                return Some(block);
+            }
+        }
+        None
+    }
+
+    /// Unlocks the encrypted device and returns its cleartext block device.
+    ///
+    /// The passphrase is ignored, if [`encrypted::UnlockOptions::keyfile_contents`] is set.
+    ///
+    /// # Errors
+    /// Returns an error if unlocking fails, e.g. because of a wrong passphrase, or the cleartext
+    /// device does not have a block interface.
+    pub async fn unlock(
+        &self,
+        encrypted: &encrypted::EncryptedProxy<'_>,
+        passphrase: &str,
+        options: encrypted::UnlockOptions,
+    ) -> error::Result<block::BlockProxy<'static>> {
+        let cleartext_device = encrypted.unlock(passphrase, options.into()).await?;
+        self.object(cleartext_device)?.block().await
+    }
+
+    /// Locks all unlocked encrypted devices of the given drive.
+    ///
+    /// This includes encrypted partitions. Devices that are not encrypted or already locked
+    /// are skipped, so calling this multiple times is fine.
+    /// Filesystems on the cleartext devices must be unmounted before locking, otherwise locking
+    /// fails with [`error::Error::DeviceBusy`].
+    ///
+    /// Together with unmounting and [`drive::DriveProxy::power_off`] this can be used to safely
+    /// remove a drive.
+    ///
+    /// # Errors
+    /// All devices are attempted to be locked, even if locking one fails.
+    /// If any device fails to lock, the first error is returned.
+    pub async fn lock_all_encrypted_for_drive(
+        &self,
+        drive: &drive::DriveProxy<'_>,
+        options: &HashMap<&str, Value<'_>>,
+    ) -> error::Result<()> {
+        let drive_object_path = drive.inner().path().to_owned().into();
+        let mut result = Ok(());
+
+        for object in self.all_blocks_for_drive(&drive_object_path, false).await {
+            let Ok(encrypted) = object.encrypted().await else {
+                continue;
+            };
+
+            let unlocked = encrypted
+                .cleartext_device()
+                .await
+                .is_ok_and(|cleartext_device| cleartext_device.as_str() != "/");
+            if !unlocked {
+                continue;
+            }
+
+            let lock_result = match clone_options(options) {
+                Ok(options) => encrypted.lock(options).await,
+                Err(err) => Err(err.into()),
+            };
+            if let Err(err) = lock_result {
+                if result.is_ok() {
+                    result = Err(err);
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Returns the [`partitiontable::PartitionTableProxy`] for the given partition.
+    ///
+    /// # Errors
+    /// Returns an error if it is unable to get the table or the [`Object`] for the table.
+    pub async fn partition_table(
+        &self,
+        partition: &partition::PartitionProxy<'_>,
+    ) -> error::Result<partitiontable::PartitionTableProxy<'_>> {
+        self.object(partition.table().await?)?
+            .partition_table()
+            .await
+    }
+
+    /// Enables or disables mounting the filesystem on the given block device at boot.
+    ///
+    /// If `enabled` is set and the block device has no `/etc/fstab` entry, the following entry is
+    /// added, with `track-parents` set so parent devices (e.g. an encrypted container)
+    /// are configured as well:
+    ///
+    /// ```text
+    /// UUID=<uuid>  /mnt/<uuid>  auto  nosuid,nodev,nofail,x-gvfs-show  0 0
+    /// ```
+    ///
+    /// The directory and mount options can be changed using `options`. If the filesystem has no
+    /// UUID, the device file (e.g. `/dev/sdb1`) and its name are used instead.
+    /// If an entry already exists, it is kept, but the `noauto` option is removed from it.
+    ///
+    /// If `enabled` is not set, all `/etc/fstab` entries of the block device are removed.
+    ///
+    /// # Errors
+    /// Returns an error if the configuration could not be read or changed.
+    pub async fn set_mount_at_boot(
+        &self,
+        block: &block::BlockProxy<'_>,
+        enabled: bool,
+        options: block::MountAtBootOptions,
+    ) -> error::Result<()> {
+        let entries: Vec<block::FstabEntry> = block
+            .configuration_items()
+            .await?
+            .into_iter()
+            .filter_map(|item| match item {
+                block::ConfigurationItem::Fstab(entry) => Some(entry),
+                _ => None,
+            })
+            .collect();
+
+        if !enabled {
+            for entry in entries {
+                let item = block::ConfigurationItem::Fstab(entry).into_raw();
+                block
+                    .remove_configuration_item(&item, options.clone().into())
+                    .await?;
+            }
+            return Ok(());
+        }
+
+        if let Some(entry) = entries.into_iter().next() {
+            if !entry.opts.split(',').any(|opt| opt == "noauto") {
+                return Ok(());
+            }
+            let new_entry = block::FstabEntry {
+                opts: entry
+                    .opts
+                    .split(',')
+                    .filter(|opt| *opt != "noauto")
+                    .collect::<Vec<_>>()
+                    .join(","),
+                ..entry.clone()
+            };
+            return block
+                .update_configuration_item(
+                    &block::ConfigurationItem::Fstab(entry).into_raw(),
+                    &block::ConfigurationItem::Fstab(new_entry).into_raw(),
+                    options.into(),
+                )
+                .await;
+        }
+
+        let uuid = block.id_uuid().await?;
+        let (fsname, name) = if uuid.is_empty() {
+            let device = block.preferred_device_path().await?;
+            let name = device
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            (device.to_string_lossy().into_owned(), name)
+        } else {
+            (format!("UUID={}", uuid), uuid)
+        };
+        let entry = block::FstabEntry {
+            fsname,
+            dir: options.dir_or(|| format!("/mnt/{}", name)),
+            type_: "auto".to_owned(),
+            opts: options.mount_options_or_default(),
+            freq: 0,
+            passno: 0,
+        };
+        block
+            .add_configuration_item(
+                &block::ConfigurationItem::Fstab(entry).into_raw(),
+                options.into(),
+            )
+            .await
+    }
+
+    /// Enables or disables unlocking the encrypted block device at boot.
+    ///
+    /// If `enabled` is set and the block device has no `/etc/crypttab` entry, the following entry
+    /// is added, with `track-parents` set:
+    ///
+    /// ```text
+    /// luks-<uuid>  UUID=<uuid>  <passphrase-path>  nofail
+    /// ```
+    ///
+    /// If `keyfile` is given, its contents are read and handed to the daemon, which stores them
+    /// in the new file `/etc/luks-keys/luks-<uuid>` used as passphrase path.
+    /// The keyfile is read with blocking IO, as it is expected to be small.
+    /// For security reasons the daemon only accepts passphrase paths of not yet existing files in
+    /// `/etc/luks-keys`, so this fails if that file already exists. Without a `keyfile`,
+    /// the passphrase path is left empty and the passphrase is asked for at boot.
+    /// If an entry already exists, it is kept as is.
+    ///
+    /// If `enabled` is not set, all `/etc/crypttab` entries of the block device are removed.
+    /// Stored keyfiles are removed by the daemon as well.
+    ///
+    /// Changing `/etc/crypttab` requires administrator privileges, so the user is usually asked
+    /// to authenticate.
+    ///
+    /// # Errors
+    /// Returns [`Error::NotSupported`] if `enabled` is set and the block device is not encrypted
+    /// or has no UUID.
+    /// Returns an error if the keyfile could not be read or the configuration could not be
+    /// read or changed.
+    pub async fn set_unlock_at_boot(
+        &self,
+        block: &block::BlockProxy<'_>,
+        enabled: bool,
+        keyfile: Option<&Path>,
+    ) -> error::Result<()> {
+        let options = || {
+            let mut options = crate::standard_options(false);
+            options.insert("track-parents", true.into());
+            options
+        };
+        let entries: Vec<block::CrypttabEntry> = block
+            .configuration_items()
+            .await?
+            .into_iter()
+            .filter_map(|item| match item {
+                block::ConfigurationItem::Crypttab(entry) => Some(entry),
+                _ => None,
+            })
+            .collect();
+
+        if !enabled {
+            for entry in entries {
+                let item = block::ConfigurationItem::Crypttab(entry).into_raw();
+                block.remove_configuration_item(&item, options()).await?;
+            }
+            return Ok(());
+        }
+
+        if !entries.is_empty() {
+            return Ok(());
+        }
+
+        let uuid = block.id_uuid().await?;
+        if block.id_usage().await? != block::IdUsage::Crypto || uuid.is_empty() {
+            return Err(Error::NotSupported);
+        }
+        let device = format!("UUID={}", uuid);
+        let name = format!("luks-{}", uuid);
+        let contents = keyfile
+            .map(std::fs::read)
+            .transpose()
+            .map_err(|err| zbus::Error::InputOutput(Arc::new(err)))?;
+        let entry = block::CrypttabEntry {
+            passphrase_path: if contents.is_some() {
+                format!("/etc/luks-keys/{}", name)
+            } else {
+                String::new()
+            },
+            name,
+            device,
+            options: "nofail".to_owned(),
+        };
+        let (ty, mut details) = block::ConfigurationItem::Crypttab(entry).into_raw();
+        if let Some(contents) = contents {
+            details.insert("passphrase-contents", contents.into());
+        }
+        block
+            .add_configuration_item(&(ty, details), options())
+            .await
+    }
+
+    /// Sets the flags of the given partition, after checking that they apply to its partition table.
+    ///
+    /// Only [`partition::PartitionFlags::Bootable`] applies to `dos` partitions, while all other
+    /// flags only apply to `gpt` partitions (see [`partitiontable::PartitionTableType::supported_flags`]).
+    /// Flags of other partition table types are not checked.
+    ///
+    /// Use [`partition::PartitionProxy::set_flags`] to set the flags without checking them.
+    ///
+    /// # Errors
+    /// Returns [`Error::UnsupportedPartitionFlags`] containing the offending flags if any of the
+    /// flags do not apply to the partition table, or an error if the partition table could not
+    /// be read or the flags could not be set.
+    pub async fn set_partition_flags(
+        &self,
+        partition: &partition::PartitionProxy<'_>,
+        flags: BitFlags<partition::PartitionFlags>,
+    ) -> error::Result<()> {
+        let table_type = self.partition_table(partition).await?.table_type().await?;
+        if let Some(supported) = table_type.supported_flags() {
+            let unsupported = flags & !supported;
+            if !unsupported.is_empty() {
+                return Err(Error::UnsupportedPartitionFlags {
+                    table_type,
+                    flags: unsupported,
+                });
+            }
+        }
+        partition.set_flags(flags, HashMap::new()).await
+    }
+
+    /// Sets up a loop device backed by the given `file`.
+    ///
+    /// The file must have been opened with a mode matching [`loop::LoopSetupOptions::read_only`],
+    /// i.e. read-write unless the loop device is read-only.
+    ///
+    /// Returns the [`Object`] of the created loop device.
+    ///
+    /// # Errors
+    /// Returns an error if the loop device could not be set up.
+    pub async fn loop_setup(
+        &self,
+        file: &File,
+        options: r#loop::LoopSetupOptions,
+    ) -> error::Result<Object> {
+        let path = self
+            .manager
+            .loop_setup(file.as_fd().into(), options.into())
+            .await?;
+        Ok(self.object(path)?)
+    }
+
+    /// Opens the file at `path` and sets up a loop device backed by it.
+    ///
+    /// The file is opened read-write, unless [`loop::LoopSetupOptions::read_only`] is set.
+    /// It is opened on a background thread, as it may reside on a slow filesystem.
+    /// This can for example be used to access the content of an ISO image, by mounting the
+    /// filesystem of the returned [`Object`].
+    ///
+    /// # Errors
+    /// Returns an error if the file could not be opened or the loop device could not be set up.
+    pub async fn create_loop_device(
+        &self,
+        path: &Path,
+        options: r#loop::LoopSetupOptions,
+    ) -> error::Result<Object> {
+        let path = path.to_path_buf();
+        let write = !options.is_read_only();
+        let file =
+            ::blocking::unblock(move || OpenOptions::new().read(true).write(write).open(path))
+                .await
+                .map_err(|err| zbus::Error::InputOutput(Arc::new(err)))?;
+        self.loop_setup(&file, options).await
+    }
+
+    /// Deletes the loop device of the given `object`, detaching it from its backing file.
+    ///
+    /// This is the counterpart to [`Self::create_loop_device`].
+    ///
+    /// # Errors
+    /// Returns an error if the object is not a loop device or it could not be deleted.
+    pub async fn delete_loop_device(
+        &self,
+        object: &Object,
+        options: HashMap<&str, Value<'_>>,
+    ) -> error::Result<()> {
+        object.r#loop().await?.delete(options).await
+    }
+
+    /// Sets whether the loop device of the given `object` is cleared automatically
+    /// once it is no longer in use, see [`loop::LoopProxy::autoclear`].
+    ///
+    /// # Errors
+    /// Returns an error if the object is not a loop device or the value could not be set.
+    pub async fn set_loop_autoclear(&self, object: &Object, autoclear: bool) -> error::Result<()> {
+        object
+            .r#loop()
+            .await?
+            .set_autoclear(autoclear, HashMap::new())
+            .await
+    }
+
+    /// Returns the [`loop::LoopProxy`] for the given [`block::BlockProxy`].
+    ///
+    /// This only works if the block is a loop device, or a partition of a loop device.
+    ///
+    /// # Errors
+    /// Returns an error if it is unable to get the loop interface.
+    pub async fn loop_for_block(
+        &self,
+        block: &block::BlockProxy<'_>,
+    ) -> error::Result<r#loop::LoopProxy<'_>> {
+        let object = self.object(block.inner().path().clone())?;
+
+        if let Ok(loop_proxy) = object.r#loop().await {
+            return Ok(loop_proxy);
+        }
+
+        // possibly partition of a loop device
+        let partition = object.partition().await?;
+        let partitiontable = self.partition_table(&partition).await?;
+        let partitiontable_object = self.object(partitiontable.inner().path().clone())?;
+        partitiontable_object.r#loop().await
+    }
+
+    /// Returns all [`partition::PartitionProxy`] of the given [`partitiontable::PartitionTableProxy`].
+    pub async fn partitions(
+        &self,
+        table: &partitiontable::PartitionTableProxy<'_>,
+    ) -> Vec<partition::PartitionProxy<'_>> {
+        let mut partitions = Vec::new();
+        // safe to unwrap as the table's object path does not need to be converted
+        let table_object = self.object(table.inner().path().clone()).unwrap();
+        let table_object_path = table_object.object_path();
+
+        for object in self.objects().await.into_iter().flatten() {
+            let Ok(partition) = object.partition().await else {
+                continue;
+            };
+
+            if partition.table().await.as_ref() == Ok(table_object_path) {
+                partitions.push(partition);
+            }
+        }
+        partitions
+    }
+
+    /// Returns the other drives with the same [`drive::DriveProxy::sibling_id`] as `drive`.
+    ///
+    /// Siblings are separate drives that are part of the same physical device, e.g. the LUNs
+    /// of a multi-slot card reader. Each sibling has its own media, so siblings are not
+    /// duplicates of each other. The given drive itself is not returned.
+    pub async fn drive_siblings(
+        &self,
+        drive: &drive::DriveProxy<'_>,
+    ) -> Vec<drive::DriveProxy<'_>> {
+        let mut drive_siblings = Vec::new();
+        let sibling_id = drive.sibling_id().await;
+
+        if sibling_id.is_err() || sibling_id.as_ref().unwrap().is_empty() {
+            return drive_siblings;
+        }
+
+        for object in self.objects().await.into_iter().flatten() {
+            if object.path_eq(drive) {
+                continue;
+            }
+            let Ok(iter_drive) = object.drive().await else {
+                continue;
+            };
+
+            if iter_drive.sibling_id().await.as_ref() == sibling_id.as_ref() {
+                drive_siblings.push(iter_drive);
+            }
+        }
+        drive_siblings
+    }
+
+    async fn block_or_blocks_for_mdraid(
+        &self,
+        mdraid: &mdraid::MDRaidProxy<'_>,
+        //TODO: pass in a function
+        // member_get: impl Fn(&block::BlockProxy<'a>) -> Future<Output = error::Result<OwnedObjectPath>> + 'a,
+        members: bool,
+        only_first_one: bool,
+        skip_partitions: bool,
+    ) -> Vec<block::BlockProxy<'_>> {
+        let mut blocks = Vec::new();
+        // safe to unwrap as the table's object path does not need to be converted
+        let raid_object = self.object(mdraid.inner().path().clone()).unwrap();
+
+        let raid_objpath = raid_object.object_path();
+
+        for object in self
+            .block_candidates(raid_objpath)
+            .await
+            .into_iter()
+            .filter_map(|object_path| self.object(object_path).ok())
+        {
+            let Ok(block) = object.block().await else {
+                continue;
+            };
+
+            // skip partitions
+            if skip_partitions && object.partition().await.is_ok() {
+                continue;
+            }
+
+            // if member_get(&block).await.as_ref() == Ok(raid_objpath) {
+            let block_objpath = if members {
+                block.mdraid().await
+            } else {
+                block.mdraid_member().await
+            };
+
+            if block_objpath.as_ref() == Ok(raid_objpath) {
+                blocks.push(block);
+                if only_first_one {
+                    break;
+                }
+            }
+        }
+
+        blocks
+    }
+
+    /// Returns the RAID device (e.g. `/dev/md0`) for the given mdraid.
+    ///
+    /// In the case of a [split-brain syndrome](https://en.wikipedia.org/wiki/Split-brain_(computing)),
+    /// it is undefined which RAID device is returned. For example this can happen if `/dev/sda` and `/dev/sdb`
+    /// are components of a two-disk RAID-1 and `/dev/md0` and `/dev/md1` are two degraded arrays,
+    /// each one using exactly one of the two devices. Use [`Client::all_blocks_for_mdraid`] to get all RAID devices.
+    ///
+    /// If no RAID device is running, [`Option::None`] is returned.
+    pub async fn block_for_mdraid(
+        &self,
+        mdraid: &mdraid::MDRaidProxy<'_>,
+    ) -> Option<BlockProxy<'_>> {
+        self.block_or_blocks_for_mdraid(mdraid, false, true, true)
+            .await
+            .first()
+            .cloned()
+    }
+
+    /// Returns all RAID devices (e.g. `/dev/md0` and `/dev/md1`) for the given mdraid.
+    ///
+    /// This is usually only useful [split-brain syndrome](https://en.wikipedia.org/wiki/Split-brain_(computing)),
+    /// and is normally used only to convey the problem in an user interface. See [`Client::block_for_mdraid`] for an example.
+    pub async fn all_blocks_for_mdraid(
+        &self,
+        mdraid: &mdraid::MDRaidProxy<'_>,
+    ) -> Vec<block::BlockProxy<'_>> {
+        self.block_or_blocks_for_mdraid(mdraid, false, false, true)
+            .await
+    }
+
+    /// returns the physical block devices that are part of the given raid.
+    pub async fn members_for_mdraid(
+        &self,
+        mdraid: &mdraid::MDRaidProxy<'_>,
+    ) -> Vec<block::BlockProxy<'_>> {
+        self.block_or_blocks_for_mdraid(mdraid, true, false, false)
+            .await
+    }
+
+    /// Returns the [`mdraid::MDRaidProxy`] that the given block is the block device for.
+    ///
+    /// # Errors
+    /// Returns an error if no [`mdraid::MDRaidProxy`] for the block is found, or the block is not
+    /// a MD-RAID block device.
+    pub async fn mdraid_for_block(
+        &self,
+        block: &block::BlockProxy<'_>,
+    ) -> error::Result<mdraid::MDRaidProxy<'_>> {
+        let object = self.object(block.mdraid().await?)?;
+        object.mdraid().await
+    }
+
+    /// Checks whether it is safe to perform destructive operations, such as
+    /// [`block::BlockProxy::format`], on the given block device.
+    ///
+    /// The returned [`SafetyReport`] lists all found concerns, e.g. if the device is a system
+    /// device, read-only, mounted (including mounted partitions), an active swap device,
+    /// a member of a running RAID array or an unlocked encrypted device.
+    ///
+    /// As noted in [`block::BlockProxy::format`], it is the caller's responsibility to ensure the
+    /// device is ready for destructive operations. This can be used to show the user a
+    /// confirmation with the specific reasons.
+    ///
+    /// # Errors
+    /// Returns an error if it fails to read the properties of the block device.
+    pub async fn is_destructive_safe(
+        &self,
+        block: &block::BlockProxy<'_>,
+    ) -> error::Result<SafetyReport> {
+        let mut report = SafetyReport::default();
+
+        if block.hint_system().await? {
+            report.push(SafetyConcern::System);
+        }
+        if block.read_only().await? {
+            report.push(SafetyConcern::ReadOnly);
+        }
+
+        let object = self.object(block.inner().path().clone())?;
+        let mut objects = vec![object.clone()];
+        if let Ok(table) = object.partition_table().await {
+            for partition in table.partitions().await.unwrap_or_default() {
+                objects.push(self.object(partition)?);
+            }
+        }
+
+        for object in &objects {
+            if let Ok(filesystem) = object.filesystem().await {
+                if !filesystem.mount_points().await?.is_empty() {
+                    report.push(SafetyConcern::Mounted);
+                }
+            }
+            if let Ok(swapspace) = object.swapspace().await {
+                if swapspace.active().await? {
+                    report.push(SafetyConcern::SwapActive);
+                }
+            }
+            if let Ok(encrypted) = object.encrypted().await {
+                if encrypted.cleartext_device().await?.as_str() != "/" {
+                    report.push(SafetyConcern::Unlocked);
+                }
+            }
+        }
+
+        let mdraid = block.mdraid_member().await?;
+        if mdraid.as_str() != "/" {
+            let running = match self.object(mdraid)?.mdraid().await {
+                Ok(mdraid) => mdraid.running().await.unwrap_or_default(),
+                Err(_) => false,
+            };
+            if running {
+                report.push(SafetyConcern::RaidMember);
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Returns all NVMe controllers, together with their [`Object`].
+    ///
+    /// NVMe controllers are exported on the [`drive::DriveProxy`] object of the NVMe drive.
+    pub async fn nvme_controllers(
+        &self,
+    ) -> Vec<(Object, nvme::controller::ControllerProxy<'static>)> {
+        let mut controllers = Vec::new();
+        for object in self.objects().await.into_iter().flatten() {
+            if let Ok(controller) = object.nvme_controller().await {
+                controllers.push((object, controller));
+            }
+        }
+        controllers
+    }
+
+    /// Returns all NVMe namespaces, together with their [`Object`].
+    ///
+    /// NVMe namespaces are exported on the [`block::BlockProxy`] object of the namespace.
+    pub async fn nvme_namespaces(&self) -> Vec<(Object, nvme::namespace::NamespaceProxy<'static>)> {
+        let mut namespaces = Vec::new();
+        for object in self.objects().await.into_iter().flatten() {
+            if let Ok(namespace) = object.nvme_namespace().await {
+                namespaces.push((object, namespace));
+            }
+        }
+        namespaces
+    }
+
+    /// Returns all NVMe namespaces that are attached to the given controller.
+    ///
+    /// The namespace's block device references the drive object it belongs to (see
+    /// [`block::BlockProxy::drive`]), which exports the controller of the namespace.
+    /// A namespace is considered to be attached to `controller`, if its controller
+    /// has the same [`nvme::controller::ControllerProxy::subsystem_nqn`] as `controller`.
+    /// This also includes namespaces that are reachable through other controllers of the same
+    /// NVMe subsystem.
+    pub async fn namespaces_for_controller(
+        &self,
+        controller: &nvme::controller::ControllerProxy<'_>,
+    ) -> Vec<(Object, nvme::namespace::NamespaceProxy<'static>)> {
+        let Ok(subsystem_nqn) = controller.subsystem_nqn().await else {
+            return Vec::new();
+        };
+
+        let mut namespaces = Vec::new();
+        for (object, namespace) in self.nvme_namespaces().await {
+            let Ok(block) = object.block().await else {
+                continue;
+            };
+            let Ok(drive) = block.drive().await else {
+                continue;
+            };
+            //safe to unwrap, as the drive path is already an OwnedObjectPath
+            let drive_object = self.object(drive).unwrap();
+            let Ok(namespace_controller) = drive_object.nvme_controller().await else {
+                continue;
+            };
+
+            if namespace_controller.subsystem_nqn().await.as_ref() == Ok(&subsystem_nqn) {
+                namespaces.push((object, namespace));
+            }
+        }
+        namespaces
+    }
+
+    /// Mounts the given `filesystem` with the given `options`, returning the path of the mount point.
+    ///
+    /// If [`filesystem::MountOptions::auto_readonly`] is enabled (the default) and the block device
+    /// of the filesystem is read-only (e.g. write-protected SD cards or optical discs),
+    /// the `ro` mount option is added automatically, unless `ro` or `rw` have been requested explicitly.
+    ///
+    /// # Errors
+    /// Returns an error if the filesystem could not be mounted, e.g. because it is already mounted.
+    pub async fn mount(
+        &self,
+        filesystem: &filesystem::FilesystemProxy<'_>,
+        mut options: filesystem::MountOptions,
+    ) -> error::Result<PathBuf> {
+        if options.is_auto_readonly() && !options.has_access_mode() {
+            let object = self.object(filesystem.inner().path().clone())?;
+            if object.block().await?.read_only().await? {
+                options = options.add_readonly();
+            }
+        }
+        filesystem.mount(options.into()).await.map(PathBuf::from)
+    }
+
+    /// Checks the given `filesystem` for consistency.
+    ///
+    /// In addition to the result of [`filesystem::FilesystemProxy::check`], this returns the
+    /// [`job::JobProxy`] the daemon created for the check, e.g. to display its start time.
+    /// See [`filesystem::CheckResult::job`].
+    ///
+    /// The filesystem must not be mounted, otherwise an error is returned by the daemon.
+    /// Not all filesystem types support checking.
+    ///
+    /// # Errors
+    /// Returns an error if the filesystem is mounted, checking is not supported
+    /// or the check failed.
+    pub async fn check_filesystem(
+        &self,
+        filesystem: &filesystem::FilesystemProxy<'_>,
+        options: HashMap<&str, Value<'_>>,
+    ) -> error::Result<filesystem::CheckResult> {
+        let (clean, job) = self
+            .call_with_job(filesystem.inner().path(), filesystem.check(options))
+            .await?;
+        Ok(filesystem::CheckResult { clean, job })
+    }
+
+    /// Repairs the given `filesystem`.
+    ///
+    /// In addition to the result of [`filesystem::FilesystemProxy::repair`], this returns the
+    /// [`job::JobProxy`] the daemon created for the repair, like [`Self::check_filesystem`].
+    ///
+    /// The filesystem must not be mounted, otherwise an error is returned by the daemon.
+    /// Not all filesystem types support repairing.
+    ///
+    /// # Errors
+    /// Returns an error if the filesystem is mounted, repairing is not supported
+    /// or the repair failed.
+    pub async fn repair_filesystem(
+        &self,
+        filesystem: &filesystem::FilesystemProxy<'_>,
+        options: HashMap<&str, Value<'_>>,
+    ) -> error::Result<filesystem::RepairResult> {
+        let (repaired, job) = self
+            .call_with_job(filesystem.inner().path(), filesystem.repair(options))
+            .await?;
+        Ok(filesystem::RepairResult { repaired, job })
+    }
+
+    /// Resizes the given `filesystem`, unmounting it first if necessary.
+    ///
+    /// The resize mode is selected using the [`manager::ResizeFlags`] of the filesystem type
+    /// (see [`manager::ManagerProxy::can_resize`]):
+    ///
+    /// - if the filesystem is not mounted, it is resized offline
+    /// - if it is mounted and can be grown (or shrunk) while mounted, it is resized in place
+    /// - otherwise it is unmounted, resized offline and mounted again
+    ///
+    /// Whether the filesystem grows or shrinks is determined by comparing `target` with
+    /// [`filesystem::FilesystemProxy::size`]. If the current size is unknown, an online resize
+    /// is only used if both growing and shrinking are supported while mounted.
+    ///
+    /// The filesystem is mounted again using [`filesystem::FilesystemProxy::mount`].
+    /// The daemon chooses the mount point, which is the original one for filesystems
+    /// with an `/etc/fstab` entry, and usually the same one for all others.
+    /// If resizing fails after unmounting, the filesystem is still mounted again
+    /// before the error is returned.
+    ///
+    /// `options` are only passed to [`filesystem::FilesystemProxy::resize`]. Unmounting and
+    /// mounting only use the [`crate::standard_options`], with `auth.no_user_interaction`
+    /// taken from `options`.
+    ///
+    /// # Errors
+    /// Returns [`Error::NotSupported`] if the filesystem type can not be resized in the required mode,
+    /// [`Error::MountPointChanged`] if the filesystem was mounted again at a different mount point,
+    /// or an error if unmounting, resizing or mounting failed.
+    pub async fn resize_filesystem(
+        &self,
+        filesystem: &filesystem::FilesystemProxy<'_>,
+        target: filesystem::ResizeTarget,
+        options: HashMap<&str, Value<'_>>,
+    ) -> error::Result<()> {
+        use manager::ResizeFlags;
+
+        let object = self.object(filesystem.inner().path().clone())?;
+        let id_type = object.block().await?.id_type().await?;
+        let (available, flags, _) = self.manager.can_resize(&id_type).await?;
+        if !available {
+            return Err(Error::NotSupported);
+        }
+        let flags = BitFlags::<ResizeFlags>::from_bits_truncate(flags);
+
+        let current_size = filesystem.size().await?;
+        let (online, offline) = match target {
+            _ if current_size == 0 => (
+                ResizeFlags::OnlineGrow | ResizeFlags::OnlineShrink,
+                ResizeFlags::OfflineGrow | ResizeFlags::OfflineShrink,
+            ),
+            filesystem::ResizeTarget::Size(size) if size < current_size => (
+                ResizeFlags::OnlineShrink.into(),
+                ResizeFlags::OfflineShrink.into(),
+            ),
+            _ => (
+                ResizeFlags::OnlineGrow.into(),
+                ResizeFlags::OfflineGrow.into(),
+            ),
+        };
+
+        // recorded before unmounting, to verify the mount point after mounting again
+        let mount_points = filesystem.mount_points_paths().await?;
+        if mount_points.is_empty() || flags.contains(online) {
+            return filesystem.resize(target.size(), options).await;
+        }
+        if !flags.contains(offline) {
+            return Err(Error::NotSupported);
+        }
+
+        let no_user_interaction = options
+            .get("auth.no_user_interaction")
+            .and_then(|value| bool::try_from(value).ok())
+            .unwrap_or_default();
+        filesystem
+            .unmount(crate::standard_options(no_user_interaction))
+            .await?;
+        let resized = filesystem.resize(target.size(), options).await;
+        let mounted = filesystem
+            .mount(crate::standard_options(no_user_interaction))
+            .await;
+        resized?;
+        let mount_point = PathBuf::from(mounted?);
+        if !mount_points.contains(&mount_point) {
+            return Err(Error::MountPointChanged {
+                expected: mount_points[0].clone(),
+                actual: mount_point,
+            });
+        }
+        Ok(())
+    }
+
+    /// Starts using the swap space of the given `object`.
+    ///
+    /// Returns the `swapspace-start` [`job::JobProxy`] created by the daemon, if any.
+    /// As starting swap is usually instant, this is mostly [`None`].
+    ///
+    /// # Errors
+    /// Returns an error if the object has no swap space or it could not be started.
+    pub async fn start_swap(
+        &self,
+        object: &Object,
+        options: HashMap<&str, Value<'_>>,
+    ) -> error::Result<Option<job::JobProxy<'static>>> {
+        let swapspace = object.swapspace().await?;
+        let ((), job) = self
+            .call_with_job(object.object_path(), swapspace.start(options))
+            .await?;
+        Ok(job)
+    }
+
+    /// Stops using the swap space of the given `object`.
+    ///
+    /// Returns the `swapspace-stop` [`job::JobProxy`] created by the daemon, if any.
+    ///
+    /// # Errors
+    /// Returns an error if the object has no swap space or it could not be stopped.
+    pub async fn stop_swap(
+        &self,
+        object: &Object,
+        options: HashMap<&str, Value<'_>>,
+    ) -> error::Result<Option<job::JobProxy<'static>>> {
+        let swapspace = object.swapspace().await?;
+        let ((), job) = self
+            .call_with_job(object.object_path(), swapspace.stop(options))
+            .await?;
+        Ok(job)
+    }
+
+    /// Sets the label of the swap space of the given `object`.
+    ///
+    /// Returns the `swapspace-modify` [`job::JobProxy`] created by the daemon, if any.
+    ///
+    /// # Errors
+    /// Returns an error if the object has no swap space or the label could not be set.
+    pub async fn set_swap_label(
+        &self,
+        object: &Object,
+        label: &str,
+        options: HashMap<&str, Value<'_>>,
+    ) -> error::Result<Option<job::JobProxy<'static>>> {
+        let swapspace = object.swapspace().await?;
+        let ((), job) = self
+            .call_with_job(object.object_path(), swapspace.set_label(label, options))
+            .await?;
+        Ok(job)
+    }
+
+    /// Awaits `call`, while watching for the [`job::JobProxy`] the daemon creates for `object_path`.
+    ///
+    /// Returns the result of `call` and the job, if one was created before `call` completed.
+    async fn call_with_job<T>(
+        &self,
+        object_path: &zbus::zvariant::ObjectPath<'_>,
+        call: impl std::future::Future<Output = error::Result<T>>,
+    ) -> error::Result<(T, Option<job::JobProxy<'static>>)> {
+        // subscribe before starting the call to not miss the job
+        let mut interfaces_added = self.object_manager.receive_interfaces_added().await?;
+
+        let find_job = std::pin::pin!(async {
+            while let Some(signal) = interfaces_added.next().await {
+                let Ok(args) = signal.args() else {
+                    continue;
+                };
+                if !args
+                    .interfaces_and_properties()
+                    .keys()
+                    .any(|interface| interface.as_str() == "org.freedesktop.UDisks2.Job")
+                {
+                    continue;
+                }
+                let Ok(builder) =
+                    job::JobProxy::builder(&self.connection).path(args.object_path().to_owned())
+                else {
+                    continue;
+                };
+                let Ok(job) = builder.build().await else {
+                    continue;
+                };
+                if job.objects().await.is_ok_and(|objects| {
+                    objects
+                        .iter()
+                        .any(|path| path.as_str() == object_path.as_str())
+                }) {
+                    return Some(job);
+                }
+            }
+            None
+        });
+
+        match future::select(std::pin::pin!(call), find_job).await {
+            future::Either::Left((result, _)) => Ok((result?, None)),
+            future::Either::Right((job, call)) => Ok((call.await?, job)),
+        }
+    }
+
+    /// Returns the filesystem type that would be used when mounting the given block device.
+    ///
+    /// The type is derived from [`block::BlockProxy::id_type`] and normalized as follows:
+    ///
+    /// - `ntfs` is mounted using the `ntfs3` kernel driver, which UDisks prefers by default
+    /// - `fat`, `msdos`, `fat12`, `fat16` and `fat32` are mounted as `vfat`
+    /// - all other types are used as is, e.g. `ext4`, `exfat`, `btrfs` or `iso9660`
+    ///
+    /// Note that the daemon may be configured to use a different driver
+    /// (e.g. `ntfs_drivers` in `/etc/udisks2/mount_options.conf`).
+    ///
+    /// If the block device does not contain a filesystem, [`None`] is returned.
+    ///
+    /// # Errors
+    /// Returns an error if the properties of the block could not be read.
+    pub async fn mountable_fstype(
+        &self,
+        block: &block::BlockProxy<'_>,
+    ) -> error::Result<Option<String>> {
+        if block.id_usage().await? != block::IdUsage::Filesystem {
+            return Ok(None);
+        }
+        let id_type = block.id_type().await?;
+        Ok(match id_type.as_str() {
+            "" => None,
+            "ntfs" => Some("ntfs3".to_owned()),
+            "fat" | "msdos" | "fat12" | "fat16" | "fat32" => Some("vfat".to_owned()),
+            _ => Some(id_type),
+        })
+    }
+
+    /// Returns the `/etc/fstab` entry of the given block device, if any.
+    ///
+    /// # Errors
+    /// Returns an error if the configuration of the block could not be read.
+    pub async fn fstab_entry_for_block(
+        &self,
+        block: &block::BlockProxy<'_>,
+    ) -> error::Result<Option<block::FstabEntry>> {
+        Ok(block
+            .configuration_items()
+            .await?
             .into_iter()
-            .flatten()
-            .filter_map(|(object_path, _)| self.object(object_path).ok())
-        {
-            let Ok(iter_drive) = object.drive().await else {
-                continue;
-            };
+            .find_map(|item| match item {
+                block::ConfigurationItem::Fstab(entry) => Some(entry),
+                _ => None,
+            }))
+    }
 
-            if
-            // TODO: C version checks if we're the same drive
-            // rust version doesn't implement partial_cmp
-            // iter_drive != drive &&
-            iter_drive.sibling_id().await.as_ref() == sibling_id.as_ref() {
-                drive_siblings.push(iter_drive);
-            }
+    /// Whether the filesystem type in the `/etc/fstab` entry of the given block device differs
+    /// from the detected filesystem type ([`block::BlockProxy::id_type`]).
+    ///
+    /// This can be used to warn about stale `/etc/fstab` entries, e.g. after reformatting a device.
+    /// If the block device has no `/etc/fstab` entry, or the entry uses the type `auto`,
+    /// `false` is returned.
+    ///
+    /// # Errors
+    /// Returns an error if the configuration or filesystem type of the block could not be read.
+    pub async fn fstype_mismatch(&self, block: &block::BlockProxy<'_>) -> error::Result<bool> {
+        let Some(entry) = self.fstab_entry_for_block(block).await? else {
+            return Ok(false);
+        };
+        if entry.type_.is_empty() || entry.type_ == "auto" {
+            return Ok(false);
         }
-        drive_siblings
+        Ok(entry.type_ != block.id_type().await?)
     }
 
-    async fn block_or_blocks_for_mdraid(
+    /// Removes all known filesystem, RAID and partition table signatures from the given `block`.
+    ///
+    /// This formats the block device with the special type `empty`. Use
+    /// [`block::FormatOptions::erase`] to additionally overwrite the whole device.
+    ///
+    /// Note that this does not create a new, empty partition table. To do so, format the device
+    /// with the type `dos` or `gpt` instead.
+    pub async fn wipe_signatures(
         &self,
-        mdraid: &mdraid::MDRaidProxy<'_>,
-        //TODO: pass in a function
-        // member_get: impl Fn(&block::BlockProxy<'a>) -> Future<Output = error::Result<OwnedObjectPath>> + 'a,
-        members: bool,
-        only_first_one: bool,
-        skip_partitions: bool,
-    ) -> Vec<block::BlockProxy> {
-        let mut blocks = Vec::new();
-        // safe to unwrap as the table's object path does not need to be converted
-        let raid_object = self.object(mdraid.inner().path().clone()).unwrap();
+        block: &block::BlockProxy<'_>,
+        options: block::FormatOptions,
+    ) -> error::Result<()> {
+        block.format("empty", options).await
+    }
 
-        let raid_objpath = raid_object.object_path();
+    /// Checks whether the given `block` can be formatted as `type_` with the given `options`.
+    ///
+    /// This calls [`block::BlockProxy::format`] with the `no-block` and `dry-run-first` options set,
+    /// so the daemon returns after authorization and parameter checks, performing a dry run of the
+    /// formatting command first, if it knows how to do that.
+    ///
+    /// **Warning**: This is not side-effect free. The block device has already been modified (wiped)
+    /// when the dry run check is performed and the actual formatting may still continue in the background.
+    /// Only use this on devices that are about to be formatted anyway.
+    pub async fn validate_format(
+        &self,
+        block: &block::BlockProxy<'_>,
+        type_: &str,
+        options: block::FormatOptions,
+    ) -> error::Result<()> {
+        let options = options.no_block(true).dry_run_first(true);
+        block.format(type_, options).await
+    }
 
-        for object in self
-            .object_manager
-            .get_managed_objects()
+    /// Returns information about the given object for presentation in a user information.
+    ///
+    /// The returned information is localized.
+    ///
+    /// If the object is neither a drive, a RAID array nor a block device, generic information
+    /// derived from the object path is returned, see [`ObjectInfo::is_empty`].
+    pub async fn object_info<'a>(&self, object: &'a Object) -> ObjectInfo<'a> {
+        let snapshot = self.snapshot().await;
+        self.object_info_from_snapshot(object, &snapshot).await
+    }
+
+    /// Returns the short name of the given object, without computing the full [`ObjectInfo`].
+    ///
+    /// The name is:
+    ///
+    /// - the backing file for loop devices (e.g. `/home/user/disk.iso`)
+    /// - the name of RAID arrays (e.g. `raid0`)
+    /// - the preferred device file for other block devices (e.g. `/dev/sda1`)
+    /// - the preferred device file of the whole-disk block device for drives (e.g. `/dev/sda`)
+    ///
+    /// If the object has none of these interfaces, or the name is not valid UTF-8,
+    /// [`None`] is returned.
+    ///
+    /// # Errors
+    /// Returns an error if the name could not be read.
+    pub async fn object_name(&self, object: &Object) -> error::Result<Option<String>> {
+        let snapshot = self.snapshot().await;
+        let object_path = object.object_path();
+        let to_string = |bytes: Vec<u8>| {
+            CString::from_vec_with_nul(bytes)
+                .ok()
+                .and_then(|name| name.into_string().ok())
+        };
+
+        if let Some(loop_proxy) = snapshot
+            .proxy::<r#loop::LoopProxy>(object_path, snapshot::LOOP_INTERFACE)
             .await
-            .into_iter()
-            .flatten()
-            .filter_map(|(object_path, _)| self.object(object_path).ok())
         {
-            let Ok(block) = object.block().await else {
-                continue;
-            };
-
-            // skip partitions
-            if skip_partitions && object.partition().await.is_ok() {
-                continue;
-            }
+            return Ok(to_string(loop_proxy.backing_file().await?));
+        }
+        if let Some(mdraid) = snapshot
+            .proxy::<mdraid::MDRaidProxy>(object_path, snapshot::MDRAID_INTERFACE)
+            .await
+        {
+            let name = mdraid.name().await?;
+            return Ok(name
+                .split(':')
+                .next_back()
+                .filter(|name| !name.is_empty())
+                .map(str::to_owned));
+        }
+        let block = match snapshot
+            .proxy::<block::BlockProxy>(object_path, snapshot::BLOCK_INTERFACE)
+            .await
+        {
+            Some(block) => Some(block),
+            None => snapshot.block_for_drive(object_path).await,
+        };
+        match block {
+            Some(block) => Ok(to_string(block.preferred_device().await?)),
+            None => Ok(None),
+        }
+    }
 
-            // if member_get(&block).await.as_ref() == Ok(raid_objpath) {
-            let block_objpath = if members {
-                block.mdraid().await
-            } else {
-                block.mdraid_member().await
-            };
+    /// Returns information about all objects for presentation in a user interface.
+    ///
+    /// This is equivalent to calling [`Self::object_info`] for every object, but fetches
+    /// the managed objects only once and reuses them for all objects.
+    pub async fn all_object_infos(&self) -> Vec<ObjectInfoData> {
+        self.all_object_infos_cancellable(future::pending()).await
+    }
 
-            if block_objpath.as_ref() == Ok(raid_objpath) {
-                blocks.push(block);
-                if only_first_one {
-                    break;
-                }
+    /// Like [`Self::all_object_infos`], but stops once `cancel` completes.
+    ///
+    /// `cancel` is checked before the information of each object is computed. If it completes,
+    /// the information computed so far is returned, i.e. the result is partial and may be empty.
+    /// The order of the objects is unspecified, so a partial result can not be continued.
+    pub async fn all_object_infos_cancellable(
+        &self,
+        cancel: impl Future<Output = ()>,
+    ) -> Vec<ObjectInfoData> {
+        let mut cancel = std::pin::pin!(cancel);
+        let snapshot = self.snapshot().await;
+        let mut infos = Vec::new();
+        for object in snapshot
+            .object_paths()
+            .filter_map(|object_path| self.object(object_path.clone()).ok())
+        {
+            if is_cancelled(&mut cancel) {
+                break;
             }
+            let info = self.object_info_from_snapshot(&object, &snapshot).await;
+            infos.push(info.into());
         }
-
-        blocks
+        infos
     }
 
-    /// Returns the RAID device (e.g. `/dev/md0`) for the given mdraid.
+    /// Returns a summary of all block devices containing a filesystem.
     ///
-    /// In the case of a [split-brain syndrome](https://en.wikipedia.org/wiki/Split-brain_(computing)),
-    /// it is undefined which RAID device is returned. For example this can happen if `/dev/sda` and `/dev/sdb`
-    /// are components of a two-disk RAID-1 and `/dev/md0` and `/dev/md1` are two degraded arrays,
-    /// each one using exactly one of the two devices. Use [`Client::all_blocks_for_mdraid`] to get all RAID devices.
+    /// This is similar to the output of `df`, but also includes filesystems that are not mounted.
+    /// The used and available space of a filesystem is not exported by UDisks,
+    /// use `statvfs(3)` on one of the [`StorageEntry::mount_points`] if it is needed.
     ///
-    /// If no RAID device is running, [`Option::None`] is returned.
-    pub async fn block_for_mdraid(
-        &self,
-        mdraid: &mdraid::MDRaidProxy<'_>,
-    ) -> Option<BlockProxy<'_>> {
-        self.block_or_blocks_for_mdraid(mdraid, false, true, true)
+    /// Entries are sorted by their device path. Block devices whose properties
+    /// could not be read are skipped.
+    pub async fn storage_report(&self) -> Vec<StorageEntry> {
+        let snapshot = self.snapshot().await;
+        let entries = snapshot
+            .object_paths()
+            .filter(|object_path| {
+                snapshot.has_interface(object_path, snapshot::BLOCK_INTERFACE)
+                    && snapshot.has_interface(object_path, snapshot::FILESYSTEM_INTERFACE)
+            })
+            .map(|object_path| async {
+                let block = snapshot
+                    .proxy::<BlockProxy>(object_path, snapshot::BLOCK_INTERFACE)
+                    .await?;
+                let filesystem = snapshot
+                    .proxy::<filesystem::FilesystemProxy>(
+                        object_path,
+                        snapshot::FILESYSTEM_INTERFACE,
+                    )
+                    .await?;
+                let filesystem_size = filesystem.size().await.ok()?;
+                Some(StorageEntry {
+                    device: block.preferred_device_path().await.ok()?,
+                    filesystem_type: block.id_type().await.ok()?,
+                    size: block.size().await.ok()?,
+                    mount_points: filesystem.mount_points_paths().await.ok()?,
+                    filesystem_size: (filesystem_size > 0).then_some(filesystem_size),
+                })
+            });
+        let mut entries: Vec<_> = future::join_all(entries)
             .await
-            .first()
+            .into_iter()
+            .flatten()
+            .collect();
+        entries.sort_unstable_by(|a, b| a.device.cmp(&b.device));
+        entries
+    }
+
+    /// Returns the [`Object`]s layered on top of the given block device.
+    ///
+    /// This is similar to the `holders` directory of a block device in sysfs
+    /// (`/sys/block/<name>/holders`), but follows the relationships known to the daemon:
+    ///
+    /// - the partitions of a partition table
+    /// - the cleartext device of an unlocked encrypted device
+    /// - the block device of a running RAID array the device is a member of
+    ///
+    /// Note that, unlike in sysfs, partitions are included.
+    /// See [`Self::slaves`] for the opposite direction.
+    pub async fn holders(&self, block: &block::BlockProxy<'_>) -> Vec<Object> {
+        let snapshot = self.snapshot().await;
+        let path = OwnedObjectPath::from(block.inner().path().clone());
+
+        let mut holders: Vec<OwnedObjectPath> = snapshot
+            .objects_with(snapshot::PARTITION_INTERFACE, "Table", &path)
+            .chain(snapshot.objects_with(snapshot::BLOCK_INTERFACE, "CryptoBackingDevice", &path))
             .cloned()
+            .collect();
+        if let Some(mdraid) =
+            snapshot.object_path_property(&path, snapshot::BLOCK_INTERFACE, "MDRaidMember")
+        {
+            holders.extend(
+                snapshot
+                    .objects_with(snapshot::BLOCK_INTERFACE, "MDRaid", &mdraid)
+                    .filter(|object_path| {
+                        !snapshot.has_interface(object_path, snapshot::PARTITION_INTERFACE)
+                    })
+                    .cloned(),
+            );
+        }
+        self.unique_objects(holders)
     }
 
-    /// Returns all RAID devices (e.g. `/dev/md0` and `/dev/md1`) for the given mdraid.
+    /// Returns the [`Object`]s the given block device is layered on top of.
     ///
-    /// This is usually only useful [split-brain syndrome](https://en.wikipedia.org/wiki/Split-brain_(computing)),
-    /// and is normally used only to convey the problem in an user interface. See [`Client::block_for_mdraid`] for an example.
-    pub async fn all_blocks_for_mdraid(
-        &self,
-        mdraid: &mdraid::MDRaidProxy<'_>,
-    ) -> Vec<block::BlockProxy<'_>> {
-        self.block_or_blocks_for_mdraid(mdraid, false, false, true)
-            .await
+    /// This is similar to the `slaves` directory of a block device in sysfs
+    /// (`/sys/block/<name>/slaves`), but follows the relationships known to the daemon:
+    ///
+    /// - the partition table (i.e. the whole disk) of a partition
+    /// - the encrypted backing device of a cleartext device
+    /// - the member devices of a running RAID array
+    ///
+    /// Note that, unlike in sysfs, the disk of a partition is included.
+    /// See [`Self::holders`] for the opposite direction.
+    pub async fn slaves(&self, block: &block::BlockProxy<'_>) -> Vec<Object> {
+        let snapshot = self.snapshot().await;
+        let path = OwnedObjectPath::from(block.inner().path().clone());
+
+        let mut slaves: Vec<OwnedObjectPath> = [
+            snapshot.object_path_property(&path, snapshot::PARTITION_INTERFACE, "Table"),
+            snapshot.object_path_property(&path, snapshot::BLOCK_INTERFACE, "CryptoBackingDevice"),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if !snapshot.has_interface(&path, snapshot::PARTITION_INTERFACE) {
+            if let Some(mdraid) =
+                snapshot.object_path_property(&path, snapshot::BLOCK_INTERFACE, "MDRaid")
+            {
+                slaves.extend(
+                    snapshot
+                        .objects_with(snapshot::BLOCK_INTERFACE, "MDRaidMember", &mdraid)
+                        .cloned(),
+                );
+            }
+        }
+        self.unique_objects(slaves)
     }
 
-    /// returns the physical block devices that are part of the given raid.
-    pub async fn members_for_mdraid(
-        &self,
-        mdraid: &mdraid::MDRaidProxy<'_>,
-    ) -> Vec<block::BlockProxy<'_>> {
-        self.block_or_blocks_for_mdraid(mdraid, true, false, false)
-            .await
+    /// Converts the object paths to [`Object`]s, skipping duplicates.
+    fn unique_objects(&self, mut object_paths: Vec<OwnedObjectPath>) -> Vec<Object> {
+        object_paths.sort_unstable_by(|a, b| a.as_str().cmp(b.as_str()));
+        object_paths.dedup();
+        object_paths
+            .into_iter()
+            //safe to unwrap, as the path is already an OwnedObjectPath
+            .map(|object_path| self.object(object_path).unwrap())
+            .collect()
     }
 
-    /// Returns the [`mdraid::MDRaidProxy`] that the given block is the block device for.
+    /// Returns the object paths that may be blocks referencing `target` by their `Drive`,
+    /// `MDRaid` or `MDRaidMember` property.
     ///
-    /// # Errors
-    /// Returns an error if no [`mdraid::MDRaidProxy`] for the block is found, or the block is not
-    /// a MD-RAID block device.
-    pub async fn mdraid_for_block(
-        &self,
-        block: &block::BlockProxy<'_>,
-    ) -> error::Result<mdraid::MDRaidProxy<'_>> {
-        let object = self.object(block.mdraid().await?)?;
-        object.mdraid().await
+    /// These are all managed objects, unless the cache is enabled.
+    async fn block_candidates(&self, target: &OwnedObjectPath) -> Vec<OwnedObjectPath> {
+        match &self.cache {
+            Some(cache) => cache.blocks_referencing(target, self.snapshot()).await,
+            None => self
+                .object_manager
+                .get_managed_objects()
+                .await
+                .into_iter()
+                .flatten()
+                .map(|(object_path, _)| object_path)
+                .collect(),
+        }
     }
 
-    /// Returns information about the given object for presentation in a user information.
-    ///
-    /// The returned information is localized.
-    pub async fn object_info<'a>(&self, object: &'a Object) -> ObjectInfo<'a> {
+    /// Returns a snapshot of all managed objects.
+    async fn snapshot(&self) -> ObjectSnapshot {
+        ObjectSnapshot::new(
+            self.object_manager
+                .get_managed_objects()
+                .await
+                .unwrap_or_default(),
+            self.connection.clone(),
+        )
+    }
+
+    async fn object_info_from_snapshot<'a>(
+        &self,
+        object: &'a Object,
+        snapshot: &ObjectSnapshot,
+    ) -> ObjectInfo<'a> {
         let mut object_info = ObjectInfo::new(object);
+        let object_path = object.object_path();
+        object_info.parent = if snapshot.has_interface(object_path, snapshot::PARTITION_INTERFACE) {
+            snapshot.object_path_property(object_path, snapshot::PARTITION_INTERFACE, "Table")
+        } else {
+            ["Drive", "MDRaid"].into_iter().find_map(|property| {
+                snapshot.object_path_property(object_path, snapshot::BLOCK_INTERFACE, property)
+            })
+        };
 
         //populate object_info
-        if let Ok(drive) = object.drive().await {
-            object_info.info_for_drive(self, &drive, None).await;
-        } else if let Ok(mdraid) = object.mdraid().await {
-            object_info.info_for_mdraid(self, mdraid, None).await;
-        } else if let Ok(block) = object.block().await {
-            let partition = object.partition().await;
-
-            let drive = self.drive_for_block(&block);
-            if let Ok(drive) = drive.await {
-                object_info
-                    .info_for_drive(self, &drive, partition.ok())
-                    .await;
-                return object_info;
+        if let Some(drive) = snapshot
+            .proxy::<drive::DriveProxy>(object_path, snapshot::DRIVE_INTERFACE)
+            .await
+        {
+            object_info
+                .info_for_drive(self, snapshot, &drive, None)
+                .await;
+        } else if let Some(mdraid) = snapshot
+            .proxy::<mdraid::MDRaidProxy>(object_path, snapshot::MDRAID_INTERFACE)
+            .await
+        {
+            object_info
+                .info_for_mdraid(self, snapshot, mdraid, None)
+                .await;
+        } else if let Some(block) = snapshot
+            .proxy::<block::BlockProxy>(object_path, snapshot::BLOCK_INTERFACE)
+            .await
+        {
+            let partition = snapshot
+                .proxy::<partition::PartitionProxy>(object_path, snapshot::PARTITION_INTERFACE)
+                .await;
+
+            if let Some(drive_path) =
+                snapshot.object_path_property(object_path, snapshot::BLOCK_INTERFACE, "Drive")
+            {
+                if let Some(drive) = snapshot
+                    .proxy::<drive::DriveProxy>(&drive_path, snapshot::DRIVE_INTERFACE)
+                    .await
+                {
+                    object_info
+                        .info_for_drive(self, snapshot, &drive, partition)
+                        .await;
+                    return object_info;
+                }
             }
 
-            let mdraid = self.mdraid_for_block(&block);
-            if let Ok(mdraid) = mdraid.await {
-                object_info
-                    .info_for_mdraid(self, mdraid, partition.ok())
-                    .await;
-                return object_info;
+            if let Some(mdraid_path) =
+                snapshot.object_path_property(object_path, snapshot::BLOCK_INTERFACE, "MDRaid")
+            {
+                if let Some(mdraid) = snapshot
+                    .proxy::<mdraid::MDRaidProxy>(&mdraid_path, snapshot::MDRAID_INTERFACE)
+                    .await
+                {
+                    object_info
+                        .info_for_mdraid(self, snapshot, mdraid, partition)
+                        .await;
+                    return object_info;
+                }
             }
 
-            if let Ok(loop_proxy) = object.r#loop().await {
+            if let Some(loop_proxy) = snapshot
+                .proxy::<r#loop::LoopProxy>(object_path, snapshot::LOOP_INTERFACE)
+                .await
+            {
                 object_info
-                    .info_for_loop(self, loop_proxy, block, partition.ok())
+                    .info_for_loop(self, loop_proxy, block, partition)
                     .await;
             } else {
-                object_info
-                    .info_for_block(self, block, partition.ok())
-                    .await;
+                object_info.info_for_block(self, block, partition).await;
             }
+        } else {
+            object_info.info_for_unknown();
         }
 
         object_info
@@ -576,22 +3084,31 @@ impl Client {
     /// The returned string is localized and includes things like the partition type, flags (if
     /// any) and name (if any).
     ///
+    /// Information that cannot be read, e.g. because the partition is in the process of being
+    /// added or removed, is skipped. If the partition table type is unavailable, the flags are
+    /// omitted and if the partition type is unavailable, it is described as unknown.
+    ///
     /// # Errors
-    /// Returns an errors if it fails to read any of the aforementioned information.
+    /// Currently no errors are returned, the [`error::Result`] is kept for compatibility.
     pub async fn partition_info(
         &self,
         partition: &partition::PartitionProxy<'_>,
     ) -> error::Result<String> {
-        let flags = partition.flags().await?;
-        let table = self.partition_table(partition).await?;
-        let mut flags_str = String::new();
+        let flags = partition.flags().await.unwrap_or_default();
+        let table_type = match self.partition_table(partition).await {
+            Ok(table) => table.table_type().await.ok(),
+            Err(_) => None,
+        };
+        let mut flag_names = Vec::new();
 
-        match table.type_().await.as_deref() {
-            Ok("dos") if flags.contains(partition::PartitionFlags::Bootable) => {
+        match table_type {
+            Some(partitiontable::PartitionTableType::Dos)
+                if flags.contains(partition::PartitionFlags::Bootable) =>
+            {
                 // Translators: Corresponds to the DOS/Master-Boot-Record "bootable" flag for a partition
-                flags_str.push_str(&format!(", {}", pgettext("dos-part-flag", "Bootable")))
+                flag_names.push(pgettext("dos-part-flag", "Bootable"))
             }
-            Ok("gpt") => {
+            Some(partitiontable::PartitionTableType::Gpt) => {
                 let flag_map = [
                     (
                         partition::PartitionFlags::SystemPartition,
@@ -627,31 +3144,34 @@ impl Client {
 
                 for (flag, info) in flag_map {
                     if flags.contains(flag) {
-                        flags_str.push_str(&format!(", {}", info));
+                        flag_names.push(info);
                     }
                 }
             }
             _ => {}
         };
-        let type_str = match self
-            .partition_type_for_display(&table.type_().await?, &partition.type_().await?)
-        {
-            Some(val) => val.to_owned(),
-            _ => partition.type_().await?,
-        };
+        let partition_type = partition.type_().await.unwrap_or_default();
+        let type_str = table_type
+            .as_ref()
+            .and_then(|table_type| self.partition_type_for_display(table_type, &partition_type))
+            .unwrap_or(partition_type);
 
-        let partition_info;
-        if !flags_str.is_empty() {
-            // Translators: Partition info. First {} is the type, second {} is a list of flags
-            partition_info = pgettext_f("partition-info", "{} ({})", [type_str, flags_str])
-        } else if type_str.is_empty() {
+        let type_str = if type_str.is_empty() {
             // Translators: The Partition info when unknown
-            partition_info = pgettext("partition-info", "Unknown")
+            pgettext("partition-info", "Unknown")
         } else {
-            partition_info = type_str;
-        }
+            type_str
+        };
 
-        Ok(partition_info)
+        if flag_names.is_empty() {
+            return Ok(type_str);
+        }
+        // Translators: Partition info. First {} is the type, second {} is a list of flags
+        Ok(pgettext_f(
+            "partition-info",
+            "{} ({})",
+            [type_str, flag_names.join(", ")],
+        ))
     }
 
     fn pow2_size(&self, size: u64) -> String {
@@ -716,7 +3236,19 @@ impl Client {
     /// When `use_pow2` is set to true power-of-two units are used instead of power-of-ten
     /// units.
     /// Set `long_str` to true, to produce a long string.
+    ///
+    /// Sizes below one kilobyte (or kibibyte, if `use_pow2` is set), including 0, are
+    /// displayed in bytes, e.g. `512 bytes`, regardless of `long_str`.
     pub fn size_for_display(&self, size: u64, use_pow2: bool, long_str: bool) -> String {
+        let first_factor = if use_pow2 {
+            KIBIBYTE_FACTOR
+        } else {
+            KILOBYTE_FACTOR
+        };
+        if (size as f64) < first_factor {
+            return npgettext_f("byte-size", "{} byte", "{} bytes", size, [size.to_string()]);
+        }
+
         let pow_size = if use_pow2 {
             self.pow2_size(size)
         } else {
@@ -729,23 +3261,43 @@ impl Client {
 
         if use_pow2 {
             // Translators: The first %s is the size in power-of-2 units, e.g. '64 KiB'
-            // the second %s is the size as a number e.g. '65,536' (always > 1)
-            pgettext_f(
+            // the second %s is the size as a number e.g. '65,536'
+            npgettext_f(
                 "byte-size-pow2",
+                "{} ({} byte)",
                 "{} ({} bytes)",
+                size,
                 [pow_size, size.to_string()],
             )
         } else {
             // Translators: The first %s is the size in power-of-10 units, e.g. '100 kB'
-            // the second %s is the size as a number e.g. '100,000' (always > 1)
-            pgettext_f(
+            // the second %s is the size as a number e.g. '100,000'
+            npgettext_f(
                 "byte-size-pow10",
+                "{} ({} byte)",
                 "{} ({} bytes)",
+                size,
                 [pow_size, size.to_string()],
             )
         }
     }
 
+    /// Parses a human-readable size, e.g. as entered by the user, into bytes.
+    ///
+    /// This is the inverse of [`Self::size_for_display`] and understands power-of-ten
+    /// (`KB`, `MB`, `GB`, `TB`) and power-of-two (`KiB`, `MiB`, `GiB`, `TiB`) units,
+    /// plain byte counts and decimal values, such as `1.5GB`. See the [`FromStr`](std::str::FromStr) implementation of [`Size`].
+    ///
+    /// Note that the output of [`Self::size_for_display`] is rounded, so parsing it may not
+    /// return the exact original size.
+    ///
+    /// # Errors
+    /// Returns an error if the string is not a valid size, the unit is unknown
+    /// or the size does not fit into a `u64`.
+    pub fn parse_size(&self, size: &str) -> Result<u64, SizeParseError> {
+        size.parse::<Size>().map(|size| size.bytes())
+    }
+
     /// Returns a human readable localized string for `usage`, `type` and `version`.
     pub fn id_for_display(&self, usage: &str, ty: &str, version: &str, long_str: bool) -> String {
         ID_TYPES
@@ -898,15 +3450,28 @@ impl Client {
         }
     }
 
+    /// Returns a human-readable, localized string of the media described by the given `media_compat`.
+    ///
+    /// Same as [`Client::media_compat_for_display`], but takes the media types as returned by
+    /// [`drive::DriveProxy::media_compatibility`].
+    pub fn media_compat_display(
+        &self,
+        media_compat: &[drive::MediaCompatibility],
+    ) -> Option<String> {
+        let media_compat: Vec<&str> = media_compat.iter().map(|media| media.as_str()).collect();
+        self.media_compat_for_display(&media_compat)
+    }
+
     /// Returns information about all known partition types for `partition_table_type` (e.g. `dos` or `gpt`) and `partition_table_subtype`.
     ///
+    /// The table type can be given as [`partitiontable::PartitionTableType`] or as string.
     /// If `partition_table_subtype` is [`None`], it is equivalent to all known types.
     pub fn partition_type_infos(
         &self,
-        partition_table_type: &str,
+        partition_table_type: impl AsRef<str>,
         partition_table_subtype: Option<&str>,
     ) -> Vec<&PartitionTypeInfo> {
-        //TODO: use enum for table type?
+        let partition_table_type = partition_table_type.as_ref();
         //TODO: C version uses a custom type, which appears to be the same as `PartitionTypeInfo`,
         //but without the name
         //https://github.com/storaged-project/udisks/blob/4f24c900383d3dc28022f62cab3eb434d19b6b82/udisks/udisksclient.c#L2604
@@ -921,7 +3486,8 @@ impl Client {
     }
 
     /// Returns information about all known subtypes for `partition_table_type` (e.g. `dos` or `gpt`) and `partition_table_subtype`.
-    pub fn partition_table_subtypes(&self, partition_table_type: &str) -> Vec<&str> {
+    pub fn partition_table_subtypes(&self, partition_table_type: impl AsRef<str>) -> Vec<&str> {
+        let partition_table_type = partition_table_type.as_ref();
         partition_subtypes::PARTITION_TABLE_SUBTYPES
             .iter()
             .filter(|pt| pt.ty == partition_table_type)
@@ -932,15 +3498,43 @@ impl Client {
     /// Returns, if exists, the human-readable localized name of the [PartitionTypeInfo].
     pub fn partition_type_for_display(
         &self,
-        partition_table_type: &str,
+        partition_table_type: impl AsRef<str>,
         partition_type: &str,
     ) -> Option<String> {
+        let partition_table_type = partition_table_type.as_ref();
         partition_types::PARTITION_TYPES
             .iter()
             .find(|pt| pt.table_type == partition_table_type && pt.ty == partition_type)
             .map(|partition_type| dpgettext("part-type", partition_type.name))
     }
 
+    /// Returns, if exists, the partition type with the given human-readable `name` for `partition_table_type`
+    /// and `partition_table_subtype`.
+    ///
+    /// This is the inverse of [`Client::partition_type_for_display`]. The returned type depends
+    /// on the partition table type: for `gpt` it is a type GUID, e.g.
+    /// `0fc63daf-8483-4772-8e79-3d69d8477de4` for `Linux Filesystem`, while for `dos` it is an
+    /// MBR type code, e.g. `0x83` for `Linux`. It can be passed to
+    /// [`partition::PartitionProxy::set_type`].
+    ///
+    /// If `partition_table_subtype` is [`None`], all known types are searched, like for
+    /// [`Client::partition_type_infos`].
+    /// The name is compared case-insensitively to both the untranslated and localized names.
+    pub fn partition_type_for_name(
+        &self,
+        partition_table_type: impl AsRef<str>,
+        partition_table_subtype: Option<&str>,
+        name: &str,
+    ) -> Option<&'static str> {
+        self.partition_type_infos(partition_table_type, partition_table_subtype)
+            .into_iter()
+            .find(|pt| {
+                pt.name.eq_ignore_ascii_case(name)
+                    || dpgettext("part-type", pt.name).to_lowercase() == name.to_lowercase()
+            })
+            .map(|pt| pt.ty)
+    }
+
     /// Returns, if existing, the human-readable localized name of the [`PartitionTypeInfo`].
     ///
     /// It is similar to [`Client::partition_type_for_display`], but also accounts for the `partition_table_subtype`, if available.
@@ -948,10 +3542,11 @@ impl Client {
     /// type.
     pub fn partition_type_and_subtype_for_display(
         &self,
-        partition_table_type: &str,
+        partition_table_type: impl AsRef<str>,
         partition_table_subtype: &str,
         partition_type: &str,
     ) -> Option<String> {
+        let partition_table_type = partition_table_type.as_ref();
         PARTITION_TYPES
             .iter()
             .filter(|pt| pt.table_type == partition_table_type && pt.ty == partition_type)
@@ -960,30 +3555,36 @@ impl Client {
             .next()
     }
 
-    /// Returns, if exists, the human-readable localized string for `partition_table_type` (e.g.
-    /// `dos` or `gpt`).
-    pub fn partition_table_type_for_display(&self, partition_table_type: &str) -> Option<String> {
-        //TODO: use enum
-        [
+    /// Returns, if exists, the human-readable localized string for `partition_table_type`.
+    ///
+    /// The table type can be given as [`partitiontable::PartitionTableType`] or as string,
+    /// e.g. `dos` or `gpt`.
+    /// For [`partitiontable::PartitionTableType::Unknown`], [`None`] is returned.
+    pub fn partition_table_type_for_display(
+        &self,
+        partition_table_type: impl AsRef<str>,
+    ) -> Option<String> {
+        match partitiontable::PartitionTableType::from(partition_table_type.as_ref()) {
             // Translators: name of partition table format
-            ("dos", pgettext("dos", "Master Boot Record")),
+            partitiontable::PartitionTableType::Dos => Some(pgettext("dos", "Master Boot Record")),
             // Translators: name of partition table format
-            ("gpt", pgettext("gpt", "GUID Partition Table")),
+            partitiontable::PartitionTableType::Gpt => {
+                Some(pgettext("gpt", "GUID Partition Table"))
+            }
             // Translators: name of partition table format
-            ("apm", pgettext("apm", "Apple Partition Map")),
-        ]
-        .iter()
-        .find(|(ty, _)| ty == &partition_table_type)
-        .map(|(_, name)| name.to_string())
+            partitiontable::PartitionTableType::Apm => Some(pgettext("apm", "Apple Partition Map")),
+            partitiontable::PartitionTableType::Unknown(_) => None,
+        }
     }
 
     /// Returns a human-readable localized description for `partition_table_type` (e.g. `dos` or `gpt`)
     /// and `partition_table_subtype` (e.g. `dos` or `gpt`).
     pub fn partition_table_subtype_for_display(
         &self,
-        partition_table_type: &str,
+        partition_table_type: impl AsRef<str>,
         partition_table_subtype: &str,
     ) -> Option<String> {
+        let partition_table_type = partition_table_type.as_ref();
         //TODO: C version docs for subtype and type are identical, bug?
         partition_subtypes::PARTITION_TABLE_SUBTYPES
             .iter()
@@ -991,3 +3592,257 @@ impl Client {
             .map(|pt| dpgettext("partition-subtype", pt.name))
     }
 }
+
+/// Converts an `InterfacesAdded` or `InterfacesRemoved` signal into a [`DeviceEvent`],
+/// updating the interfaces of the known `objects`.
+fn device_event(
+    client: &Client,
+    objects: &mut HashMap<OwnedObjectPath, Vec<String>>,
+    message: zbus::Message,
+) -> Option<DeviceEvent> {
+    if let Some(signal) = zbus::fdo::InterfacesAdded::from_message(message.clone()) {
+        let args = signal.args().ok()?;
+        let object_path = OwnedObjectPath::from(args.object_path().clone());
+        let interfaces: Vec<String> = args
+            .interfaces_and_properties()
+            .keys()
+            .map(|name| name.to_string())
+            .collect();
+
+        let known = objects.entry(object_path.clone()).or_default();
+        let added = known.is_empty();
+        known.extend(interfaces.iter().cloned());
+        let object = client.object(object_path).ok()?;
+        return Some(if added {
+            DeviceEvent::Added(object)
+        } else {
+            DeviceEvent::InterfacesAdded { object, interfaces }
+        });
+    }
+
+    let signal = zbus::fdo::InterfacesRemoved::from_message(message)?;
+    let args = signal.args().ok()?;
+    let object_path = OwnedObjectPath::from(args.object_path().clone());
+    let interfaces: Vec<String> = args
+        .interfaces()
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+
+    let known = objects.entry(object_path.clone()).or_default();
+    known.retain(|name| !interfaces.contains(name));
+    if known.is_empty() {
+        objects.remove(&object_path);
+        return Some(DeviceEvent::Removed(object_path));
+    }
+    let object = client.object(object_path).ok()?;
+    Some(DeviceEvent::InterfacesRemoved { object, interfaces })
+}
+
+/// Clones the given options, so they can be passed to multiple method calls.
+fn clone_options<'a>(
+    options: &HashMap<&'a str, Value<'_>>,
+) -> zbus::zvariant::Result<HashMap<&'a str, Value<'static>>> {
+    options
+        .iter()
+        .map(|(key, value)| Ok((*key, value.try_to_owned()?.into())))
+        .collect()
+}
+
+/// Reads the logical block size of the given device file, e.g. `/dev/sda`, from sysfs.
+///
+/// Reading sysfs attributes does not block on disk I/O, so it is done synchronously.
+fn logical_block_size(device: &Path) -> Option<u64> {
+    let name = device.file_name()?;
+    let path = Path::new("/sys/class/block")
+        .join(name)
+        .join("queue/logical_block_size");
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Whether `cancel` has completed.
+///
+/// Must not be called again after it returned `true`, as the future has completed.
+fn is_cancelled<F: Future<Output = ()> + Unpin>(cancel: &mut F) -> bool {
+    cancel.now_or_never().is_some()
+}
+
+/// Returns the localized description and short label of a known job operation.
+///
+/// Both strings are returned from the same match arm, so every operation has both.
+fn job_operation_strings(operation: &str) -> Option<(String, String)> {
+    Some(match operation {
+        "ata-smart-selftest" => (
+            pgettext("job", "SMART self-test"),
+            pgettext("job-short", "Self-Test"),
+        ),
+        "drive-eject" => (
+            pgettext("job", "Ejecting Medium"),
+            pgettext("job-short", "Eject"),
+        ),
+        "encrypted-unlock" => (
+            pgettext("job", "Unlocking Device"),
+            pgettext("job-short", "Unlock"),
+        ),
+        "encrypted-lock" => (
+            pgettext("job", "Locking Device"),
+            pgettext("job-short", "Lock"),
+        ),
+        "encrypted-modify" => (
+            pgettext("job", "Modifying Encrypted Device"),
+            pgettext("job-short", "Modify"),
+        ),
+        "encrypted-resize" => (
+            pgettext("job", "Resizing Encrypted Device"),
+            pgettext("job-short", "Resize"),
+        ),
+        "swapspace-start" => (
+            pgettext("job", "Starting Swap Device"),
+            pgettext("job-short", "Start"),
+        ),
+        "swapspace-stop" => (
+            pgettext("job", "Stopping Swap Device"),
+            pgettext("job-short", "Stop"),
+        ),
+        "swapspace-modify" => (
+            pgettext("job", "Modifying Swap Device"),
+            pgettext("job-short", "Modify"),
+        ),
+        "filesystem-check" => (
+            pgettext("job", "Checking Filesystem"),
+            pgettext("job-short", "Check"),
+        ),
+        "filesystem-mount" => (
+            pgettext("job", "Mounting Filesystem"),
+            pgettext("job-short", "Mount"),
+        ),
+        "filesystem-unmount" => (
+            pgettext("job", "Unmounting Filesystem"),
+            pgettext("job-short", "Unmount"),
+        ),
+        "filesystem-modify" => (
+            pgettext("job", "Modifying Filesystem"),
+            pgettext("job-short", "Modify"),
+        ),
+        "filesystem-repair" => (
+            pgettext("job", "Repairing Filesystem"),
+            pgettext("job-short", "Repair"),
+        ),
+        "filesystem-resize" => (
+            pgettext("job", "Resizing Filesystem"),
+            pgettext("job-short", "Resize"),
+        ),
+        "format-erase" => (
+            pgettext("job", "Erasing Device"),
+            pgettext("job-short", "Erase"),
+        ),
+        "format-mkfs" => (
+            pgettext("job", "Creating Filesystem"),
+            pgettext("job-short", "Format"),
+        ),
+        "loop-setup" => (
+            pgettext("job", "Setting Up Loop Device"),
+            pgettext("job-short", "Set Up"),
+        ),
+        "partition-modify" => (
+            pgettext("job", "Modifying Partition"),
+            pgettext("job-short", "Modify"),
+        ),
+        "partition-delete" => (
+            pgettext("job", "Deleting Partition"),
+            pgettext("job-short", "Delete"),
+        ),
+        "partition-create" => (
+            pgettext("job", "Creating Partition"),
+            pgettext("job-short", "Create"),
+        ),
+        "cleanup" => (
+            pgettext("job", "Cleaning Up"),
+            pgettext("job-short", "Clean Up"),
+        ),
+        "ata-secure-erase" => (
+            pgettext("job", "ATA Secure Erase"),
+            pgettext("job-short", "Erase"),
+        ),
+        "ata-enhanced-secure-erase" => (
+            pgettext("job", "ATA Enhanced Secure Erase"),
+            pgettext("job-short", "Erase"),
+        ),
+        "md-raid-stop" => (
+            pgettext("job", "Stopping RAID Array"),
+            pgettext("job-short", "Stop"),
+        ),
+        "md-raid-start" => (
+            pgettext("job", "Starting RAID Array"),
+            pgettext("job-short", "Start"),
+        ),
+        "md-raid-fault-device" => (
+            pgettext("job", "Marking Device as Faulty"),
+            pgettext("job-short", "Mark as Faulty"),
+        ),
+        "md-raid-remove-device" => (
+            pgettext("job", "Removing Device from Array"),
+            pgettext("job-short", "Remove"),
+        ),
+        "md-raid-add-device" => (
+            pgettext("job", "Adding Device to Array"),
+            pgettext("job-short", "Add"),
+        ),
+        "md-raid-set-bitmap" => (
+            pgettext("job", "Setting Write-Intent Bitmap"),
+            pgettext("job-short", "Set Bitmap"),
+        ),
+        "md-raid-create" => (
+            pgettext("job", "Creating RAID Array"),
+            pgettext("job-short", "Create"),
+        ),
+        "nvme-selftest" => (
+            pgettext("job", "NVMe Self-Test"),
+            pgettext("job-short", "Self-Test"),
+        ),
+        "nvme-sanitize" => (
+            pgettext("job", "Sanitizing Device"),
+            pgettext("job-short", "Sanitize"),
+        ),
+        "nvme-format-ns" => (
+            pgettext("job", "Formatting Namespace"),
+            pgettext("job-short", "Format"),
+        ),
+        _ => return None,
+    })
+}
+
+/// Combines the `major` and `minor` number into a device number, like `makedev(3)`.
+fn makedev(major: u64, minor: u64) -> u64 {
+    ((major & 0xffff_f000) << 32)
+        | ((major & 0x0000_0fff) << 8)
+        | ((minor & 0xffff_ff00) << 12)
+        | (minor & 0x0000_00ff)
+}
+
+/// Unescapes a path of the mount table, in which space, tab, newline and backslash
+/// are escaped as octal sequences, e.g. `\040`.
+fn unescape_mount_path(path: &str) -> PathBuf {
+    use std::os::unix::ffi::OsStringExt;
+
+    let bytes = path.as_bytes();
+    let mut unescaped = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 4)
+            .filter(|_| bytes[i] == b'\\')
+            .and_then(|octal| u8::from_str_radix(std::str::from_utf8(octal).ok()?, 8).ok());
+        match escaped {
+            Some(byte) => {
+                unescaped.push(byte);
+                i += 4;
+            }
+            None => {
+                unescaped.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    PathBuf::from(std::ffi::OsString::from_vec(unescaped))
+}
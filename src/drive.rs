@@ -7,12 +7,12 @@
 //! to the same drive, there will be only one `org.freedesktop.UDisks2.Drive`
 //! object but two `org.freedesktop.UDisks2.Block` objects.
 
-use std::str::FromStr;
+use std::{str::FromStr, time::SystemTime};
 
 use serde::{de::IntoDeserializer, Deserialize, Serialize};
 use zbus::{
     proxy,
-    zvariant::{OwnedValue, Type, Value},
+    zvariant::{OwnedValue, Type},
 };
 
 use crate::error;
@@ -41,6 +41,118 @@ impl TryFrom<OwnedValue> for RotationRate {
     }
 }
 
+/// Physical connection bus of a drive, as seen by the user.
+///
+/// See [`DriveProxy::connection_bus`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Type)]
+#[zvariant(signature = "s")]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ConnectionBus {
+    /// Universal Serial Bus
+    Usb,
+    /// Secure Digital Input Output
+    Sdio,
+    /// FireWire
+    Ieee1394,
+    /// Any other bus, usually an internal bus such as SATA or NVMe.
+    #[serde(rename(deserialize = ""))]
+    Unknown,
+}
+
+impl FromStr for ConnectionBus {
+    type Err = serde::de::value::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let res: Result<_, Self::Err> = Self::deserialize(s.into_deserializer());
+        Ok(res.unwrap_or(Self::Unknown))
+    }
+}
+
+impl_try_from_string_value!(ConnectionBus);
+
+/// Group of a drive, as used to build sections of a device list.
+///
+/// Returned by [`crate::Client::drives_grouped`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DriveGroup {
+    /// Internal, non-removable drives.
+    Internal,
+    /// Drives connected using USB.
+    Usb,
+    /// Optical drives.
+    Optical,
+    /// Card readers for flash media.
+    CardReader,
+    /// All other drives, e.g. removable drives on other buses.
+    Other,
+}
+
+/// How a drive can be removed by the user.
+///
+/// Returned by [`crate::Client::removability`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Removability {
+    /// The drive is not considered removable, e.g. an internal disk.
+    /// Neither an eject nor a power off button should be shown.
+    Fixed,
+    /// The drive has removable media that can be ejected, e.g. an optical drive.
+    /// An eject button should be shown.
+    Eject {
+        /// Whether the drive itself can additionally be powered off, e.g. an external optical drive.
+        can_power_off: bool,
+    },
+    /// The drive can be powered off before unplugging it, e.g. a USB hard disk or thumb drive.
+    /// A power off button should be shown.
+    PowerOff,
+    /// The drive is removable, but can neither be ejected nor powered off,
+    /// e.g. a card reader. The filesystems should be unmounted before removing it.
+    UnmountOnly,
+}
+
+impl Removability {
+    /// Whether an eject button should be shown.
+    pub fn can_eject(&self) -> bool {
+        matches!(self, Self::Eject { .. })
+    }
+
+    /// Whether a power off button should be shown.
+    pub fn can_power_off(&self) -> bool {
+        matches!(
+            self,
+            Self::PowerOff
+                | Self::Eject {
+                    can_power_off: true
+                }
+        )
+    }
+}
+
+/// Summary of the optical disc in a drive.
+///
+/// Returned by [`crate::Client::optical_disc_info`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpticalDiscInfo {
+    /// Whether the disc is blank.
+    pub blank: bool,
+    /// Number of sessions.
+    pub num_sessions: u32,
+    /// Number of tracks.
+    pub num_tracks: u32,
+    /// Number of audio tracks.
+    pub num_audio_tracks: u32,
+    /// Number of data tracks.
+    pub num_data_tracks: u32,
+    /// Human-readable, localized type of the disc (e.g. `DVD-RW`), if known.
+    ///
+    /// This is the name of the [`DriveProxy::media`], as also used by [`crate::ObjectInfo`].
+    pub media_type: Option<String>,
+    /// Size of the disc in bytes.
+    ///
+    /// For blank discs, this is usually 0, as the size of the written data is reported.
+    pub capacity: u64,
+}
+
 /// The physical kind of media a drive uses or the type of the drive.
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Eq, Type)]
 #[zvariant(signature = "s")]
@@ -131,21 +243,197 @@ impl FromStr for MediaCompatibility {
     }
 }
 
-//TODO: use macro when this is used for multiple types
-impl TryFrom<Value<'_>> for MediaCompatibility {
-    type Error = <String as TryFrom<Value<'static>>>::Error;
+impl_try_from_string_value!(MediaCompatibility);
 
-    fn try_from(value: Value<'_>) -> Result<Self, Self::Error> {
-        let val: String = value.downcast_ref()?;
-        Ok(Self::from_str(&val).unwrap_or(Self::Unknown))
+/// Category of a [`MediaCompatibility`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MediaCategory {
+    /// Thumb-drives with non-removable media, e.g. USB sticks.
+    Thumb,
+    /// Flash cards, such as SD cards or CompactFlash.
+    Flash,
+    /// Floppy, Zip and Jaz disks.
+    Floppy,
+    /// Optical discs, such as CDs, DVDs and Blu-ray discs.
+    Optical,
+    /// Unknown media.
+    Unknown,
+}
+
+impl DriveProxy<'_> {
+    /// Returns the [`DriveProxy::connection_bus`] of the drive as a [`ConnectionBus`].
+    pub async fn connection_bus_typed(&self) -> error::Result<ConnectionBus> {
+        let bus = self.connection_bus().await?;
+        //safe to unwrap, as parsing is infallible
+        Ok(bus.parse().unwrap())
+    }
+
+    /// Returns the [`DriveProxy::time_detected`] as [`SystemTime`].
+    ///
+    /// If the time is unknown, [`None`] is returned.
+    pub async fn time_detected_system_time(&self) -> error::Result<Option<SystemTime>> {
+        Ok(crate::system_time_from_micros(self.time_detected().await?))
+    }
+
+    /// Returns the [`DriveProxy::time_media_detected`] as [`SystemTime`].
+    ///
+    /// If no media is available, [`None`] is returned.
+    pub async fn time_media_detected_system_time(&self) -> error::Result<Option<SystemTime>> {
+        Ok(crate::system_time_from_micros(
+            self.time_media_detected().await?,
+        ))
     }
 }
 
-impl TryFrom<OwnedValue> for MediaCompatibility {
-    type Error = <String as TryFrom<OwnedValue>>::Error;
+impl MediaCompatibility {
+    /// Returns all known media types.
+    ///
+    /// As the enum is non-exhaustive, more media types may be added in the future.
+    ///
+    /// # Examples
+    /// ```
+    /// use udisks2::drive::{MediaCategory, MediaCompatibility};
+    ///
+    /// // every known media type, except `Unknown`, belongs to a known category
+    /// for media in MediaCompatibility::all() {
+    ///     assert_eq!(
+    ///         *media == MediaCompatibility::Unknown,
+    ///         media.category() == MediaCategory::Unknown
+    ///     );
+    /// }
+    /// ```
+    pub fn all() -> &'static [MediaCompatibility] {
+        &[
+            Self::Thumb,
+            Self::Flash,
+            Self::FlashCf,
+            Self::FlashMs,
+            Self::FlashSm,
+            Self::FlashSd,
+            Self::FlashSdhc,
+            Self::FlashSdxc,
+            Self::FlashSdio,
+            Self::FlashSdCombo,
+            Self::FlashMmc,
+            Self::Floppy,
+            Self::FloppyZip,
+            Self::FloppyJaz,
+            Self::Optical,
+            Self::OpticalCd,
+            Self::OpticalCdR,
+            Self::OpticalCdRw,
+            Self::OpticalDvd,
+            Self::OpticalDvdR,
+            Self::OpticalDvdRw,
+            Self::OpticalDvdRam,
+            Self::OpticalDvdPlusR,
+            Self::OpticalDvdPlusRw,
+            Self::OpticalDvdPlusRDl,
+            Self::OpticalDvdPlusRwDl,
+            Self::OpticalBd,
+            Self::OpticalBdR,
+            Self::OpticalBdRe,
+            Self::OpticalHddvd,
+            Self::OpticalHddvdR,
+            Self::OpticalHddvdRw,
+            Self::OpticalMo,
+            Self::OpticalMrw,
+            Self::OpticalMrwW,
+            Self::Unknown,
+        ]
+    }
+
+    /// Returns the string used by the daemon for the media type, e.g. `optical_cd`.
+    ///
+    /// For [`MediaCompatibility::Unknown`], an empty string is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use udisks2::drive::MediaCompatibility;
+    ///
+    /// for media in MediaCompatibility::all() {
+    ///     assert_eq!(media.as_str().parse::<MediaCompatibility>().unwrap(), *media);
+    /// }
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Thumb => "thumb",
+            Self::Flash => "flash",
+            Self::FlashCf => "flash_cf",
+            Self::FlashMs => "flash_ms",
+            Self::FlashSm => "flash_sm",
+            Self::FlashSd => "flash_sd",
+            Self::FlashSdhc => "flash_sdhc",
+            Self::FlashSdxc => "flash_sdxc",
+            Self::FlashSdio => "flash_sdio",
+            Self::FlashSdCombo => "flash_sd_combo",
+            Self::FlashMmc => "flash_mmc",
+            Self::Floppy => "floppy",
+            Self::FloppyZip => "floppy_zip",
+            Self::FloppyJaz => "floppy_jaz",
+            Self::Optical => "optical",
+            Self::OpticalCd => "optical_cd",
+            Self::OpticalCdR => "optical_cd_r",
+            Self::OpticalCdRw => "optical_cd_rw",
+            Self::OpticalDvd => "optical_dvd",
+            Self::OpticalDvdR => "optical_dvd_r",
+            Self::OpticalDvdRw => "optical_dvd_rw",
+            Self::OpticalDvdRam => "optical_dvd_ram",
+            Self::OpticalDvdPlusR => "optical_dvd_plus_r",
+            Self::OpticalDvdPlusRw => "optical_dvd_plus_rw",
+            Self::OpticalDvdPlusRDl => "optical_dvd_plus_r_dl",
+            Self::OpticalDvdPlusRwDl => "optical_dvd_plus_rw_dl",
+            Self::OpticalBd => "optical_bd",
+            Self::OpticalBdR => "optical_bd_r",
+            Self::OpticalBdRe => "optical_bd_re",
+            Self::OpticalHddvd => "optical_hddvd",
+            Self::OpticalHddvdR => "optical_hddvd_r",
+            Self::OpticalHddvdRw => "optical_hddvd_rw",
+            Self::OpticalMo => "optical_mo",
+            Self::OpticalMrw => "optical_mrw",
+            Self::OpticalMrwW => "optical_mrw_w",
+            Self::Unknown => "",
+        }
+    }
 
-    fn try_from(v: OwnedValue) -> Result<Self, Self::Error> {
-        Self::try_from(Into::<Value<'_>>::into(v))
+    /// Returns the category of the media type.
+    pub fn category(&self) -> MediaCategory {
+        match self {
+            Self::Thumb => MediaCategory::Thumb,
+            Self::Flash
+            | Self::FlashCf
+            | Self::FlashMs
+            | Self::FlashSm
+            | Self::FlashSd
+            | Self::FlashSdhc
+            | Self::FlashSdxc
+            | Self::FlashSdio
+            | Self::FlashSdCombo
+            | Self::FlashMmc => MediaCategory::Flash,
+            Self::Floppy | Self::FloppyZip | Self::FloppyJaz => MediaCategory::Floppy,
+            Self::Optical
+            | Self::OpticalCd
+            | Self::OpticalCdR
+            | Self::OpticalCdRw
+            | Self::OpticalDvd
+            | Self::OpticalDvdR
+            | Self::OpticalDvdRw
+            | Self::OpticalDvdRam
+            | Self::OpticalDvdPlusR
+            | Self::OpticalDvdPlusRw
+            | Self::OpticalDvdPlusRDl
+            | Self::OpticalDvdPlusRwDl
+            | Self::OpticalBd
+            | Self::OpticalBdR
+            | Self::OpticalBdRe
+            | Self::OpticalHddvd
+            | Self::OpticalHddvdR
+            | Self::OpticalHddvdRw
+            | Self::OpticalMo
+            | Self::OpticalMrw
+            | Self::OpticalMrwW => MediaCategory::Optical,
+            Self::Unknown => MediaCategory::Unknown,
+        }
     }
 }
 
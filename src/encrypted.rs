@@ -10,9 +10,214 @@
 //! section of the zbus documentation.
 //!
 
-use zbus::proxy;
+use std::{collections::HashMap, fmt, str::FromStr};
 
-use crate::error;
+use serde::{de::IntoDeserializer, Deserialize, Serialize};
+use zbus::{
+    proxy,
+    zvariant::{OwnedObjectPath, Type, Value},
+};
+
+use crate::{error, Size};
+
+/// Type of encryption used to encrypt a device.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Type)]
+#[zvariant(signature = "s")]
+#[non_exhaustive]
+pub enum EncryptionType {
+    /// LUKS version 1
+    #[serde(rename = "LUKS1", alias = "luks1")]
+    Luks1,
+    /// LUKS version 2
+    #[serde(rename = "LUKS2", alias = "luks2")]
+    Luks2,
+    /// Plain dm-crypt
+    #[serde(rename = "PLAIN", alias = "plain")]
+    Plain,
+    /// TrueCrypt or VeraCrypt
+    #[serde(rename = "TCRYPT", alias = "tcrypt")]
+    TCrypt,
+    /// BitLocker
+    #[serde(rename = "BITLK", alias = "bitlk")]
+    BitLocker,
+    /// FileVault2
+    #[serde(rename = "FVAULT2", alias = "fvault2")]
+    FileVault2,
+    /// The encryption type is not known.
+    ///
+    /// This is the case if the device has never been unlocked.
+    #[default]
+    #[serde(rename(deserialize = ""))]
+    Unknown,
+}
+
+impl FromStr for EncryptionType {
+    type Err = serde::de::value::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let res: Result<_, Self::Err> = Self::deserialize(s.into_deserializer());
+        Ok(res.unwrap_or(Self::Unknown))
+    }
+}
+
+impl_try_from_string_value!(EncryptionType);
+
+/// State of an encrypted device.
+///
+/// Returned by [`EncryptedProxy::status`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncryptedStatus {
+    /// The cleartext device, if the device is unlocked.
+    pub cleartext_device: Option<OwnedObjectPath>,
+    /// The type of encryption used.
+    ///
+    /// This is [`EncryptionType::Unknown`] if the device has never been unlocked.
+    pub encryption_type: EncryptionType,
+    /// Size of the metadata on the encrypted device.
+    pub metadata_size: Size,
+}
+
+impl EncryptedStatus {
+    /// Whether the device is unlocked.
+    pub fn is_unlocked(&self) -> bool {
+        self.cleartext_device.is_some()
+    }
+}
+
+/// Secret used to unlock an encrypted device.
+#[derive(Clone, PartialEq, Eq)]
+pub enum Credential {
+    /// A passphrase.
+    Passphrase(String),
+    /// The contents of a keyfile.
+    Keyfile(Vec<u8>),
+}
+
+impl Credential {
+    /// Returns the passphrase argument and, for keyfiles, the option containing the contents.
+    fn split(&self) -> (&str, Option<Value<'static>>) {
+        match self {
+            Self::Passphrase(passphrase) => (passphrase, None),
+            Self::Keyfile(contents) => ("", Some(contents.clone().into())),
+        }
+    }
+}
+
+impl fmt::Debug for Credential {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // never print the secret itself
+        match self {
+            Self::Passphrase(_) => f.write_str("Passphrase(..)"),
+            Self::Keyfile(_) => f.write_str("Keyfile(..)"),
+        }
+    }
+}
+
+/// Options for [`crate::Client::unlock`].
+#[derive(Default, Clone, PartialEq, Eq)]
+pub struct UnlockOptions {
+    read_only: bool,
+    keyfile_contents: Option<Vec<u8>>,
+    no_user_interaction: bool,
+}
+
+impl UnlockOptions {
+    /// Creates new, empty options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the cleartext device should be set up read-only.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Contents of a keyfile to unlock the device with, instead of the passphrase.
+    pub fn keyfile_contents(mut self, keyfile_contents: Vec<u8>) -> Self {
+        self.keyfile_contents = Some(keyfile_contents);
+        self
+    }
+
+    /// Whether no user interaction should happen, when checking if the operation is authorized.
+    ///
+    /// See [`crate::standard_options`].
+    pub fn no_user_interaction(mut self, no_user_interaction: bool) -> Self {
+        self.no_user_interaction = no_user_interaction;
+        self
+    }
+}
+
+impl fmt::Debug for UnlockOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // never print the keyfile contents
+        f.debug_struct("UnlockOptions")
+            .field("read_only", &self.read_only)
+            .field(
+                "keyfile_contents",
+                &self.keyfile_contents.as_ref().map(|_| ".."),
+            )
+            .field("no_user_interaction", &self.no_user_interaction)
+            .finish()
+    }
+}
+
+impl From<UnlockOptions> for HashMap<&'static str, Value<'static>> {
+    fn from(options: UnlockOptions) -> Self {
+        let mut map = crate::standard_options(options.no_user_interaction);
+        if options.read_only {
+            map.insert("read-only", true.into());
+        }
+        if let Some(contents) = options.keyfile_contents {
+            map.insert("keyfile_contents", contents.into());
+        }
+        map
+    }
+}
+
+impl EncryptedProxy<'_> {
+    /// Changes the credential used to unlock the device from `old` to `new`.
+    ///
+    /// Both credentials can either be a passphrase or a keyfile, so this can also be used to
+    /// switch from a passphrase to a keyfile or vice versa.
+    /// Keyfiles are passed using the `old_keyfile_contents` and `new_keyfile_contents` options,
+    /// which take precedence over the passphrase arguments of [`EncryptedProxy::change_passphrase`].
+    /// Any of these keys in `options` are overridden.
+    pub async fn change_passphrase_with(
+        &self,
+        old: &Credential,
+        new: &Credential,
+        mut options: HashMap<&str, Value<'_>>,
+    ) -> error::Result<()> {
+        let (old_passphrase, old_keyfile) = old.split();
+        let (new_passphrase, new_keyfile) = new.split();
+        options.remove("old_keyfile_contents");
+        options.remove("new_keyfile_contents");
+        if let Some(contents) = old_keyfile {
+            options.insert("old_keyfile_contents", contents);
+        }
+        if let Some(contents) = new_keyfile {
+            options.insert("new_keyfile_contents", contents);
+        }
+        self.change_passphrase(old_passphrase, new_passphrase, options)
+            .await
+    }
+
+    /// Whether the device is unlocked, i.e. it has a cleartext device.
+    pub async fn is_unlocked(&self) -> error::Result<bool> {
+        Ok(self.cleartext_device().await?.as_str() != "/")
+    }
+
+    /// Returns the unlock state, encryption type and metadata size of the device.
+    pub async fn status(&self) -> error::Result<EncryptedStatus> {
+        let cleartext_device = self.cleartext_device().await?;
+        Ok(EncryptedStatus {
+            cleartext_device: (cleartext_device.as_str() != "/").then_some(cleartext_device),
+            encryption_type: self.hint_encryption_type().await?,
+            metadata_size: Size::from_bytes(self.metadata_size().await?),
+        })
+    }
+}
 
 #[proxy(
     interface = "org.freedesktop.UDisks2.Encrypted",
@@ -65,7 +270,7 @@ pub trait Encrypted {
 
     /// HintEncryptionType property
     #[zbus(property)]
-    fn hint_encryption_type(&self) -> error::Result<String>;
+    fn hint_encryption_type(&self) -> error::Result<EncryptionType>;
 
     /// MetadataSize property
     #[zbus(property)]
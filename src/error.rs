@@ -1,4 +1,8 @@
-use std::{convert::Infallible, fmt::Display};
+use std::{convert::Infallible, fmt::Display, path::PathBuf};
+
+use enumflags2::BitFlags;
+
+use crate::{partition::PartitionFlags, partitiontable::PartitionTableType};
 
 /// Alias for a `Result` with the error type [`Error`].
 pub type Result<T> = std::result::Result<T, Error>;
@@ -7,6 +11,7 @@ pub type Result<T> = std::result::Result<T, Error>;
 ///
 /// Possible errors and their corresponding D-Bus error names.
 #[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
 pub enum Error {
     /// The operation failed.
     Failed,
@@ -38,7 +43,34 @@ pub enum Error {
     WouldWakeup,
     /// Attempting to unmount a device that is busy.
     DeviceBusy,
+    /// The UDisks daemon is not running and could not be activated.
+    ServiceUnavailable,
     Iscsi(Iscsi),
+    /// A job failed with the given message.
+    JobFailed(String),
+    /// The requested partition does not fit into the free space of the partition table,
+    /// i.e. it overlaps an existing partition or extends past the end of the device.
+    PartitionOverlap {
+        /// Requested start of the partition in bytes.
+        offset: u64,
+        /// Requested size of the partition in bytes.
+        size: u64,
+    },
+    /// The partition flags do not apply to partitions of the partition table type.
+    UnsupportedPartitionFlags {
+        /// Type of the partition table.
+        table_type: PartitionTableType,
+        /// The flags that do not apply.
+        flags: BitFlags<PartitionFlags>,
+    },
+    /// The filesystem was mounted again at a different mount point than before,
+    /// e.g. by [`crate::Client::resize_filesystem`].
+    MountPointChanged {
+        /// The original mount point.
+        expected: PathBuf,
+        /// The mount point chosen by the daemon.
+        actual: PathBuf,
+    },
     /// The operation failed due to an [`zbus::Error`].
     Zbus(zbus::Error),
 }
@@ -59,6 +91,28 @@ pub enum Iscsi {
     UnknownDiscoveryType,
 }
 
+impl Error {
+    /// Whether the error is caused by missing authorization.
+    ///
+    /// This includes both UDisks authorization errors, as well as D-Bus errors
+    /// such as [`zbus::fdo::Error::AccessDenied`].
+    ///
+    /// # Examples
+    /// ```
+    /// use udisks2::Error;
+    ///
+    /// assert!(Error::NotAuthorizedDismissed.is_authorization_error());
+    /// assert!(Error::from(zbus::fdo::Error::AccessDenied(String::new())).is_authorization_error());
+    /// assert!(!Error::NotMounted.is_authorization_error());
+    /// ```
+    pub fn is_authorization_error(&self) -> bool {
+        matches!(
+            self,
+            Error::NotAuthorized | Error::NotAuthorizedCanObtain | Error::NotAuthorizedDismissed
+        )
+    }
+}
+
 impl std::error::Error for Error {}
 
 impl Display for Error {
@@ -79,7 +133,30 @@ impl Display for Error {
             Error::TimedOut => write!(f, "The operation timed out."),
             Error::WouldWakeup => write!(f, "The operation would wake up a disk that is in a deep-sleep state."),
             Error::DeviceBusy => write!(f, "Attempting to unmount a device that is busy."),
+            Error::ServiceUnavailable => write!(f, "The UDisks daemon is not available."),
             Error::Iscsi(_) => write!(f, "An ISCSI error occured."),
+            Error::JobFailed(message) => write!(f, "The job failed: {}", message),
+            Error::PartitionOverlap { offset, size } => write!(
+                f,
+                "The partition at offset {} with size {} does not fit into the free space.",
+                offset, size
+            ),
+            Error::UnsupportedPartitionFlags { table_type, flags } => write!(
+                f,
+                "The partition flags {} do not apply to {} partitions.",
+                flags
+                    .iter()
+                    .map(|flag| format!("{:?}", flag))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                table_type
+            ),
+            Error::MountPointChanged { expected, actual } => write!(
+                f,
+                "The filesystem was mounted at {} instead of {}.",
+                actual.display(),
+                expected.display()
+            ),
             Error::Zbus(err) => err.fmt(f),
         }
     }
@@ -87,8 +164,10 @@ impl Display for Error {
 
 impl From<zbus::Error> for Error {
     fn from(value: zbus::Error) -> Self {
-        let zbus::Error::MethodError(ref name, ref _msg, ref _info) = value else {
-            return Error::Zbus(value);
+        let name = match value {
+            zbus::Error::FDO(fdo) => return (*fdo).into(),
+            zbus::Error::MethodError(ref name, ref _msg, ref _info) => name,
+            _ => return Error::Zbus(value),
         };
 
         match name.as_str() {
@@ -129,6 +208,9 @@ impl From<zbus::Error> for Error {
             "org.freedesktop.UDisks2.Error.ISCSI.UnknownDiscoveryType" => {
                 Error::Iscsi(Iscsi::UnknownDiscoveryType)
             }
+            name if name.starts_with("org.freedesktop.DBus.Error.") => {
+                zbus::fdo::Error::from(value).into()
+            }
             _ => Error::Zbus(value),
         }
     }
@@ -136,7 +218,16 @@ impl From<zbus::Error> for Error {
 
 impl From<zbus::fdo::Error> for Error {
     fn from(value: zbus::fdo::Error) -> Self {
-        Error::Zbus(value.into())
+        use zbus::fdo::Error as Fdo;
+
+        match value {
+            Fdo::ServiceUnknown(_) | Fdo::NameHasNoOwner(_) => Error::ServiceUnavailable,
+            Fdo::AccessDenied(_) | Fdo::AuthFailed(_) => Error::NotAuthorized,
+            Fdo::InteractiveAuthorizationRequired(_) => Error::NotAuthorizedCanObtain,
+            Fdo::NoReply(_) | Fdo::Timeout(_) | Fdo::TimedOut(_) => Error::TimedOut,
+            Fdo::NotSupported(_) => Error::NotSupported,
+            _ => Error::Zbus(value.into()),
+        }
     }
 }
 impl From<zbus::zvariant::Error> for Error {
@@ -150,3 +241,49 @@ impl From<Infallible> for Error {
         match i {}
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use zbus::fdo::Error as Fdo;
+
+    use super::Error;
+
+    #[test]
+    fn from_fdo_error() {
+        let cases = [
+            (Fdo::AccessDenied(String::new()), Error::NotAuthorized),
+            (Fdo::AuthFailed(String::new()), Error::NotAuthorized),
+            (
+                Fdo::InteractiveAuthorizationRequired(String::new()),
+                Error::NotAuthorizedCanObtain,
+            ),
+            (
+                Fdo::ServiceUnknown(String::new()),
+                Error::ServiceUnavailable,
+            ),
+            (
+                Fdo::NameHasNoOwner(String::new()),
+                Error::ServiceUnavailable,
+            ),
+            (Fdo::NoReply(String::new()), Error::TimedOut),
+            (Fdo::Timeout(String::new()), Error::TimedOut),
+            (Fdo::TimedOut(String::new()), Error::TimedOut),
+            (Fdo::NotSupported(String::new()), Error::NotSupported),
+        ];
+        for (fdo, expected) in cases {
+            assert_eq!(Error::from(fdo.clone()), expected, "{fdo:?}");
+            assert_eq!(
+                Error::from(zbus::Error::from(fdo.clone())),
+                expected,
+                "{fdo:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn from_unmapped_fdo_error() {
+        let error = Error::from(Fdo::UnknownObject(String::new()));
+        assert!(matches!(error, Error::Zbus(_)));
+        assert!(!error.is_authorization_error());
+    }
+}
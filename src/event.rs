@@ -0,0 +1,30 @@
+use zbus::zvariant::OwnedObjectPath;
+
+use crate::Object;
+
+/// Change of the objects exported by the daemon.
+///
+/// Returned by [`crate::Client::events`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DeviceEvent {
+    /// A new object, e.g. a drive or block device, was added.
+    Added(Object),
+    /// An object was removed, together with all its interfaces.
+    Removed(OwnedObjectPath),
+    /// Interfaces were added to an existing object, e.g. the `Filesystem` interface after
+    /// formatting a block device.
+    InterfacesAdded {
+        /// The object the interfaces were added to.
+        object: Object,
+        /// Names of the added interfaces, e.g. `org.freedesktop.UDisks2.Filesystem`.
+        interfaces: Vec<String>,
+    },
+    /// Interfaces were removed from an object, which still implements other interfaces.
+    InterfacesRemoved {
+        /// The object the interfaces were removed from.
+        object: Object,
+        /// Names of the removed interfaces.
+        interfaces: Vec<String>,
+    },
+}
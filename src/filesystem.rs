@@ -10,9 +10,188 @@
 //! section of the zbus documentation.
 //!
 
-use zbus::proxy;
+use std::{collections::HashMap, path::PathBuf};
 
-use crate::error;
+use zbus::{proxy, zvariant::Value};
+
+use crate::{bytestring_path, error, job};
+
+/// Target size of a filesystem resize.
+///
+/// Used by [`crate::Client::resize_filesystem`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResizeTarget {
+    /// Resize the filesystem to the given size in bytes.
+    Size(u64),
+    /// Grow the filesystem to fill the whole block device.
+    Fill,
+}
+
+impl ResizeTarget {
+    /// Size as passed to [`FilesystemProxy::resize`], where 0 fills the block device.
+    pub(crate) fn size(&self) -> u64 {
+        match self {
+            Self::Size(size) => *size,
+            Self::Fill => 0,
+        }
+    }
+}
+
+/// Result of checking a filesystem.
+///
+/// Returned by [`crate::Client::check_filesystem`].
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    /// Whether the filesystem is consistent, i.e. undamaged.
+    pub clean: bool,
+    /// The job that performed the check, if it was observed before the check completed.
+    ///
+    /// The daemon removes the job once the check is finished, so only the properties cached
+    /// when the job was created can be read, such as its operation and start time.
+    pub job: Option<job::JobProxy<'static>>,
+}
+
+/// Result of repairing a filesystem.
+///
+/// Returned by [`crate::Client::repair_filesystem`].
+#[derive(Debug, Clone)]
+pub struct RepairResult {
+    /// Whether the filesystem was repaired, as reported by the daemon.
+    pub repaired: bool,
+    /// The job that performed the repair, if it was observed before the repair completed.
+    ///
+    /// Like for [`CheckResult::job`], only the properties cached when the job was created
+    /// can be read.
+    pub job: Option<job::JobProxy<'static>>,
+}
+
+/// Options for mounting a filesystem.
+///
+/// Used by [`crate::Client::mount`] and can be converted into the options
+/// of [`FilesystemProxy::mount`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MountOptions {
+    fstype: Option<String>,
+    options: Option<String>,
+    as_user: Option<String>,
+    auto_readonly: bool,
+    no_user_interaction: bool,
+}
+
+impl Default for MountOptions {
+    fn default() -> Self {
+        Self {
+            fstype: None,
+            options: None,
+            as_user: None,
+            auto_readonly: true,
+            no_user_interaction: false,
+        }
+    }
+}
+
+impl MountOptions {
+    /// Creates new, empty options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Filesystem type to use.
+    ///
+    /// If unset, the type is determined by the daemon.
+    pub fn fstype(mut self, fstype: impl Into<String>) -> Self {
+        self.fstype = Some(fstype.into());
+        self
+    }
+
+    /// Comma-separated mount options, e.g. `ro,noexec`.
+    ///
+    /// Only options permitted by the daemon can be used.
+    pub fn options(mut self, options: impl Into<String>) -> Self {
+        self.options = Some(options.into());
+        self
+    }
+
+    /// Name of the user to mount the filesystem as.
+    ///
+    /// The filesystem is mounted in the media directory of the user, e.g. `/run/media/<user>`.
+    /// This requires additional authorization, as the caller is usually a different user.
+    pub fn as_user(mut self, user: impl Into<String>) -> Self {
+        self.as_user = Some(user.into());
+        self
+    }
+
+    /// Whether to automatically mount the filesystem read-only, if the underlying block device is read-only.
+    ///
+    /// This only applies if neither `ro` nor `rw` are part of [`Self::options`].
+    /// If disabled, mounting a read-only device without the `ro` option fails.
+    ///
+    /// Defaults to `true`.
+    pub fn auto_readonly(mut self, auto_readonly: bool) -> Self {
+        self.auto_readonly = auto_readonly;
+        self
+    }
+
+    /// Whether no user interaction should happen, when checking if the operation is authorized.
+    ///
+    /// See [`crate::standard_options`].
+    pub fn no_user_interaction(mut self, no_user_interaction: bool) -> Self {
+        self.no_user_interaction = no_user_interaction;
+        self
+    }
+
+    /// Whether the filesystem is automatically mounted read-only on read-only devices.
+    pub fn is_auto_readonly(&self) -> bool {
+        self.auto_readonly
+    }
+
+    /// Whether the mount options explicitly set `ro` or `rw`.
+    pub(crate) fn has_access_mode(&self) -> bool {
+        self.options
+            .as_deref()
+            .is_some_and(|options| options.split(',').any(|opt| opt == "ro" || opt == "rw"))
+    }
+
+    /// Adds the `ro` option to the mount options.
+    pub(crate) fn add_readonly(mut self) -> Self {
+        self.options = Some(match self.options {
+            Some(options) if !options.is_empty() => format!("{options},ro"),
+            _ => "ro".to_owned(),
+        });
+        self
+    }
+}
+
+impl From<MountOptions> for HashMap<&'static str, Value<'static>> {
+    fn from(options: MountOptions) -> Self {
+        let mut map = crate::standard_options(options.no_user_interaction);
+        if let Some(fstype) = options.fstype {
+            map.insert("fstype", fstype.into());
+        }
+        if let Some(mount_options) = options.options {
+            map.insert("options", mount_options.into());
+        }
+        if let Some(user) = options.as_user {
+            map.insert("as-user", user.into());
+        }
+        map
+    }
+}
+
+impl FilesystemProxy<'_> {
+    /// Returns the [`Self::mount_points`] as paths.
+    ///
+    /// The trailing NUL byte of each mount point is removed. Mount points that are not valid
+    /// UTF-8 are preserved as is. If the filesystem is not mounted, the list is empty.
+    pub async fn mount_points_paths(&self) -> error::Result<Vec<PathBuf>> {
+        Ok(self
+            .mount_points()
+            .await?
+            .into_iter()
+            .map(bytestring_path)
+            .collect())
+    }
+}
 
 #[proxy(
     interface = "org.freedesktop.UDisks2.Filesystem",
@@ -27,6 +206,8 @@ pub trait Filesystem {
     ) -> error::Result<bool>;
 
     /// Mount method
+    ///
+    /// The options can be created using [`MountOptions`].
     fn mount(
         &self,
         options: std::collections::HashMap<&str, zbus::zvariant::Value<'_>>,
@@ -73,6 +254,9 @@ pub trait Filesystem {
     ) -> error::Result<()>;
 
     /// MountPoints property
+    ///
+    /// Each mount point is a NUL-terminated byte string,
+    /// use [`FilesystemProxy::mount_points_paths`] to get them as paths.
     #[zbus(property)]
     fn mount_points(&self) -> error::Result<Vec<Vec<u8>>>;
 
@@ -1,6 +1,35 @@
+use std::{io, path::Path};
+
 /// gettext package
 pub(crate) const GETTEXT_PACKAGE: &str = "udisks2";
 
+/// Directory containing the translations of the system.
+const LOCALE_DIR: &str = "/usr/share/locale";
+
+/// Separator between the context and the message, as used by `msgfmt`.
+const MSG_SEPARATOR: char = '\u{004}';
+
+/// Binds the `udisks2` gettext domain, so the strings returned by the crate are translated.
+///
+/// The translations are loaded from `dir`, or the system locale directory (`/usr/share/locale`)
+/// if `dir` is `None`, e.g. `/usr/share/locale/de/LC_MESSAGES/udisks2.mo`.
+/// Passing a directory can be used to load translations from a different prefix or for testing.
+///
+/// Applications should call this once at startup, after setting the locale using
+/// `setlocale(LC_ALL, "")` (e.g. [`gettextrs::setlocale`]). Without it, translations are only
+/// found if the `udisks2` domain is installed in the default directory of the C library.
+/// The default text domain of the application is not changed, as all strings of the crate
+/// are looked up in the `udisks2` domain.
+///
+/// # Errors
+/// Returns an error if the domain could not be bound.
+pub fn bind_text_domain(dir: Option<&Path>) -> io::Result<()> {
+    let dir = dir.unwrap_or(Path::new(LOCALE_DIR));
+    gettextrs::bindtextdomain(GETTEXT_PACKAGE, dir)?;
+    gettextrs::bind_textdomain_codeset(GETTEXT_PACKAGE, "UTF-8")?;
+    Ok(())
+}
+
 /// Translate msgid to localized message from the specified domain (with context support).
 ///
 /// For more information, see [`dpgettext2`](https://docs.gtk.org/glib/func.dpgettext2.html)
@@ -9,11 +38,48 @@ where
     T: Into<String>,
     U: Into<String>,
 {
-    const MSG_SEPARATOR: char = '\u{004}';
-    gettextrs::dgettext(
+    let msgid = msgid.into();
+    let msg_ctxt_id = format!("{}{MSG_SEPARATOR}{}", msgctxt.into(), msgid);
+    let translation = gettextrs::dgettext(GETTEXT_PACKAGE, msg_ctxt_id.as_str());
+    // untranslated messages are returned unchanged, including the context
+    if translation == msg_ctxt_id {
+        msgid
+    } else {
+        translation
+    }
+}
+
+/// Like [`gettextrs::pgettext`], but using the `udisks2` domain.
+pub(crate) fn pgettext<T, U>(msgctxt: T, msgid: U) -> String
+where
+    T: Into<String>,
+    U: Into<String>,
+{
+    dpgettext(msgctxt, msgid)
+}
+
+/// Like [`gettextrs::gettext`], but using the `udisks2` domain.
+pub(crate) fn gettext<T: Into<String>>(msgid: T) -> String {
+    gettextrs::dgettext(GETTEXT_PACKAGE, msgid)
+}
+
+/// Like [`gettextrs::npgettext`], but using the `udisks2` domain.
+fn npgettext(msgctxt: &str, singular: String, plural: String, n: u32) -> String {
+    let singular_ctxt_id = format!("{msgctxt}{MSG_SEPARATOR}{singular}");
+    let plural_ctxt_id = format!("{msgctxt}{MSG_SEPARATOR}{plural}");
+    let translation = gettextrs::dngettext(
         GETTEXT_PACKAGE,
-        format!("{}{MSG_SEPARATOR}{}", msgctxt.into(), msgid.into()),
-    )
+        singular_ctxt_id.as_str(),
+        plural_ctxt_id.as_str(),
+        n,
+    );
+    if translation == singular_ctxt_id {
+        singular
+    } else if translation == plural_ctxt_id {
+        plural
+    } else {
+        translation
+    }
 }
 
 /// Similar to [`gettextrs::pgettext`], but with support for formatted strings.
@@ -34,7 +100,38 @@ pub(crate) fn pgettext_f(
     args: impl IntoIterator<Item = impl AsRef<str>>,
 ) -> String {
     // map Rust style string formatting to C style formatting
-    let s = gettextrs::pgettext(msgctxt, format.replace("{}", "%s"));
+    let s = pgettext(msgctxt, format.replace("{}", "%s"));
+    arg_replace(s, args)
+}
+
+/// Similar to [`gettextrs::npgettext`], but with support for formatted strings.
+///
+/// Counts that do not fit into an [`u32`] are mapped to a smaller number
+/// with the same plural form, as recommended by the gettext manual.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// # use crate::gettext::npgettext_f;
+/// let formatted_string = npgettext_f("apples", "{} apple", "{} apples", 2, ["2"]);
+/// assert_eq!(formatted_string, "2 apples");
+/// ```
+//TODO: add function name to gettext keywords for extraction
+pub(crate) fn npgettext_f(
+    msgctxt: &str,
+    singular: &str,
+    plural: &str,
+    n: u64,
+    args: impl IntoIterator<Item = impl AsRef<str>>,
+) -> String {
+    let n = u32::try_from(n).unwrap_or((n % 1_000_000 + 1_000_000) as u32);
+    // map Rust style string formatting to C style formatting
+    let s = npgettext(
+        msgctxt,
+        singular.replace("{}", "%s"),
+        plural.replace("{}", "%s"),
+        n,
+    );
     arg_replace(s, args)
 }
 
@@ -52,7 +149,7 @@ pub(crate) fn pgettext_f(
 //TODO: add function name to gettext keywords for extraction
 pub(crate) fn gettext_f(format: &str, args: impl IntoIterator<Item = impl AsRef<str>>) -> String {
     // map Rust style string formatting to C style formatting
-    let s = gettextrs::gettext(format.replace("{}", "%s"));
+    let s = gettext(format.replace("{}", "%s"));
     arg_replace(s, args)
 }
 
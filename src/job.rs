@@ -10,7 +10,13 @@
 //! section of the zbus documentation.
 //!
 
-use zbus::proxy;
+use std::time::{Duration, SystemTime};
+
+use futures_util::{
+    future::{self, Either},
+    FutureExt, StreamExt,
+};
+use zbus::{fdo::ObjectManagerProxy, proxy};
 
 use crate::error;
 
@@ -70,3 +76,99 @@ pub trait Job {
     #[zbus(property, name = "StartedByUID")]
     fn started_by_uid(&self) -> error::Result<u32>;
 }
+
+impl JobProxy<'_> {
+    /// Returns the [`JobProxy::start_time`] as [`SystemTime`].
+    ///
+    /// If the time is unknown, [`None`] is returned.
+    pub async fn start_time_system_time(&self) -> error::Result<Option<SystemTime>> {
+        Ok(crate::system_time_from_micros(self.start_time().await?))
+    }
+
+    /// Returns the [`JobProxy::expected_end_time`] as [`SystemTime`].
+    ///
+    /// If the daemon can not estimate the end time, e.g. because the job does not report
+    /// its progress, [`None`] is returned.
+    /// Use [`JobProxy::remaining`] for the time left until then.
+    pub async fn expected_end_time_system_time(&self) -> error::Result<Option<SystemTime>> {
+        Ok(crate::system_time_from_micros(
+            self.expected_end_time().await?,
+        ))
+    }
+
+    /// Returns the estimated time until the job completes.
+    ///
+    /// The time is computed from [`JobProxy::expected_end_time_system_time`] and the current
+    /// [`SystemTime::now`] when this is called, so it decreases between calls even if the
+    /// estimate does not change. If the end time is unknown or has already passed,
+    /// [`None`] is returned.
+    pub async fn remaining(&self) -> error::Result<Option<Duration>> {
+        Ok(self
+            .expected_end_time_system_time()
+            .await?
+            .and_then(|end| end.duration_since(SystemTime::now()).ok()))
+    }
+
+    /// Waits for the job to complete.
+    ///
+    /// Resolves to the message of the [`JobProxy::receive_completed`] signal, if the job was successful.
+    /// If the job failed, [`error::Error::JobFailed`] with the failure message is returned.
+    ///
+    /// The future can be cancelled by dropping it, this does not cancel the job itself,
+    /// use [`JobProxy::cancel`] for that.
+    ///
+    /// # Errors
+    /// Returns [`zbus::Error::InterfaceNotFound`] if the job disappears without completing,
+    /// e.g. because it has already completed or the daemon exited.
+    pub async fn into_completion(self) -> error::Result<String> {
+        let path = self.inner().path().to_owned();
+        let object_manager = ObjectManagerProxy::builder(self.inner().connection())
+            .destination(self.inner().destination().to_owned())?
+            .path("/org/freedesktop/UDisks2")?
+            .build()
+            .await?;
+
+        // subscribe before checking whether the job exists, to not miss any signals
+        let mut completed = self.receive_completed().await?;
+        let mut removed = object_manager.receive_interfaces_removed().await?;
+        let mut owner_changed = self.inner().receive_owner_changed().await?;
+
+        if !object_manager
+            .get_managed_objects()
+            .await?
+            .contains_key(&path)
+        {
+            return Err(zbus::Error::InterfaceNotFound.into());
+        }
+
+        loop {
+            let disappeared = future::select(removed.next(), owner_changed.next());
+            match future::select(completed.next(), disappeared).await {
+                Either::Left((Some(signal), _)) => return completion_result(signal),
+                Either::Right((Either::Left((Some(signal), _)), _)) => {
+                    if signal.args()?.object_path() == &*path {
+                        break;
+                    }
+                }
+                // the daemon got a new owner
+                Either::Right((Either::Right((Some(Some(_)), _)), _)) => {}
+                _ => break,
+            }
+        }
+
+        // the completed signal is emitted before the job is removed
+        match completed.next().now_or_never().flatten() {
+            Some(signal) => completion_result(signal),
+            None => Err(zbus::Error::InterfaceNotFound.into()),
+        }
+    }
+}
+
+fn completion_result(signal: Completed) -> error::Result<String> {
+    let args = signal.args()?;
+    if *args.success() {
+        Ok(args.message().to_string())
+    } else {
+        Err(error::Error::JobFailed(args.message().to_string()))
+    }
+}
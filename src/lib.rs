@@ -3,23 +3,61 @@
 #[cfg(not(target_os = "linux"))]
 compile_error!("UDisks only supports Linux");
 
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    ffi::OsStr,
+    os::unix::ffi::OsStrExt,
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
 
 //re-eport zbus
 pub use zbus;
 
+/// Implements the conversions from a D-Bus string value for an enum implementing [`std::str::FromStr`].
+///
+/// This allows the enum to be used as the return type of a string property.
+macro_rules! impl_try_from_string_value {
+    ($type:ty) => {
+        impl TryFrom<zbus::zvariant::Value<'_>> for $type {
+            type Error = <String as TryFrom<zbus::zvariant::Value<'static>>>::Error;
+
+            fn try_from(value: zbus::zvariant::Value<'_>) -> Result<Self, Self::Error> {
+                let val: String = value.downcast_ref()?;
+                Ok(<Self as std::str::FromStr>::from_str(&val).unwrap_or(Self::Unknown))
+            }
+        }
+
+        impl TryFrom<zbus::zvariant::OwnedValue> for $type {
+            type Error = <String as TryFrom<zbus::zvariant::OwnedValue>>::Error;
+
+            fn try_from(v: zbus::zvariant::OwnedValue) -> Result<Self, Self::Error> {
+                Self::try_from(Into::<zbus::zvariant::Value<'_>>::into(v))
+            }
+        }
+    };
+}
+
 pub mod ata;
 pub mod block;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+mod cache;
 mod client;
 pub mod drive;
 pub mod encrypted;
 mod error;
+mod event;
+pub use event::DeviceEvent;
 pub mod filesystem;
 pub(crate) mod gettext;
+pub use gettext::bind_text_domain;
 mod id;
 pub mod job;
 pub mod r#loop;
 pub mod manager;
+mod matching;
+pub use matching::MatchOptions;
 pub mod mdraid;
 mod media;
 pub mod nvme;
@@ -28,9 +66,18 @@ mod object_info;
 mod partition_subtypes;
 pub mod partition_types;
 pub use object::Object;
-pub use object_info::ObjectInfo;
+pub use object_info::{DeviceKind, ObjectInfo, ObjectInfoData};
 pub mod partition;
 pub mod partitiontable;
+mod report;
+pub use report::StorageEntry;
+mod safety;
+pub use safety::{SafetyConcern, SafetyReport};
+mod size;
+pub use size::{Size, SizeParseError};
+mod smart;
+pub use smart::{SelfTestResult, SmartSummary};
+mod snapshot;
 pub mod swapspace;
 pub use client::Client;
 pub use error::{Error, Iscsi, Result};
@@ -39,9 +86,28 @@ pub use error::{Error, Iscsi, Result};
 ///
 /// Many functions inlude a parameter `options`, which includes the following options:
 /// - `no_user_auth_interaction` if set to `true`, no user interaction will happen,
-///    when checking if the called function is authorized
+///   when checking if the called function is authorized
 pub fn standard_options(
     no_user_auth_interaction: bool,
 ) -> HashMap<&'static str, zbus::zvariant::Value<'static>> {
     HashMap::from([("auth.no_user_interaction", no_user_auth_interaction.into())])
 }
+
+/// Converts micro-seconds since the Unix epoch into a [`SystemTime`], with 0 meaning unknown.
+pub(crate) fn system_time_from_micros(micros: u64) -> Option<SystemTime> {
+    (micros != 0).then(|| SystemTime::UNIX_EPOCH + Duration::from_micros(micros))
+}
+
+/// Converts seconds since the Unix epoch into a [`SystemTime`], with 0 meaning unknown.
+pub(crate) fn system_time_from_secs(secs: u64) -> Option<SystemTime> {
+    (secs != 0).then(|| SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+}
+
+/// Converts a D-Bus bytestring, which is usually NUL-terminated, into a path,
+/// e.g. a device file or mount point.
+pub(crate) fn bytestring_path(mut bytes: Vec<u8>) -> PathBuf {
+    if bytes.last() == Some(&0) {
+        bytes.pop();
+    }
+    PathBuf::from(OsStr::from_bytes(&bytes))
+}
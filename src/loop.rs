@@ -10,10 +10,90 @@
 //! section of the zbus documentation.
 //!
 
-use zbus::proxy;
+use std::collections::HashMap;
+
+use zbus::{proxy, zvariant::Value};
 
 use crate::error;
 
+/// Options for setting up a loop device.
+///
+/// Used by [`crate::Client::create_loop_device`] and can be converted into the options
+/// of [`crate::manager::ManagerProxy::loop_setup`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LoopSetupOptions {
+    offset: Option<u64>,
+    size: Option<u64>,
+    read_only: bool,
+    no_part_scan: bool,
+    no_user_interaction: bool,
+}
+
+impl LoopSetupOptions {
+    /// Creates new, empty options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start of the loop device, in bytes, relative to the start of the backing file.
+    pub fn offset(mut self, offset: u64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Size of the loop device, in bytes.
+    ///
+    /// If unset, the loop device extends to the end of the backing file.
+    pub fn size(mut self, size: u64) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Whether the loop device should be read-only.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Whether the kernel should not scan the loop device for partitions.
+    pub fn no_part_scan(mut self, no_part_scan: bool) -> Self {
+        self.no_part_scan = no_part_scan;
+        self
+    }
+
+    /// Whether no user interaction should happen, when checking if the operation is authorized.
+    ///
+    /// See [`crate::standard_options`].
+    pub fn no_user_interaction(mut self, no_user_interaction: bool) -> Self {
+        self.no_user_interaction = no_user_interaction;
+        self
+    }
+
+    /// Whether the loop device is set up as read-only.
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+}
+
+impl From<LoopSetupOptions> for HashMap<&'static str, Value<'static>> {
+    fn from(options: LoopSetupOptions) -> Self {
+        let mut map = crate::standard_options(options.no_user_interaction);
+        if let Some(offset) = options.offset {
+            map.insert("offset", offset.into());
+        }
+        if let Some(size) = options.size {
+            map.insert("size", size.into());
+        }
+        if options.read_only {
+            map.insert("read-only", true.into());
+        }
+        if options.no_part_scan {
+            map.insert("no-part-scan", true.into());
+        }
+        map
+    }
+}
+
 #[proxy(
     interface = "org.freedesktop.UDisks2.Loop",
     default_service = "org.freedesktop.UDisks2",
@@ -27,6 +107,8 @@ pub trait Loop {
     ) -> error::Result<()>;
 
     /// SetAutoclear method
+    ///
+    /// Sets [`LoopProxy::autoclear`], see its documentation for when the loop device is cleared.
     fn set_autoclear(
         &self,
         value: bool,
@@ -34,6 +116,12 @@ pub trait Loop {
     ) -> error::Result<()>;
 
     /// Autoclear property
+    ///
+    /// Whether the loop device is cleared (i.e. detached from its backing file) by the kernel,
+    /// once it is no longer in use. This happens when the last user closes the device,
+    /// e.g. when its last filesystem is unmounted. A loop device that is never opened,
+    /// such as one without a mountable filesystem, is not cleared automatically and has to be
+    /// deleted using [`LoopProxy::delete`].
     #[zbus(property)]
     fn autoclear(&self) -> error::Result<bool>;
 
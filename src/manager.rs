@@ -10,10 +10,226 @@
 //! section of the zbus documentation.
 //!
 
-use zbus::proxy;
+use std::{collections::HashMap, fmt::Display, num::ParseIntError, str::FromStr};
+
+use enumflags2::bitflags;
+use zbus::{proxy, zvariant::Value};
 
 use crate::error;
 
+/// Version of the UDisks daemon.
+///
+/// Versions are compared component-wise, which allows checking whether a feature is available.
+///
+/// # Examples
+///
+/// ```
+/// # use udisks2::manager::Version;
+/// assert_eq!("2.10.0".parse(), Ok(Version::new(2, 10, 0)));
+/// assert_eq!("2.9".parse(), Ok(Version::new(2, 9, 0)));
+/// assert_eq!("2.10.1-3.fc40".parse(), Ok(Version::new(2, 10, 1)));
+/// assert!(Version::new(2, 10, 1) >= Version::new(2, 10, 0));
+/// assert!(Version::new(2, 9, 0) < Version::new(2, 10, 0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version {
+    /// The major version.
+    pub major: u32,
+    /// The minor version.
+    pub minor: u32,
+    /// The micro version.
+    pub micro: u32,
+}
+
+impl Version {
+    /// Creates a new version.
+    pub const fn new(major: u32, minor: u32, micro: u32) -> Self {
+        Self {
+            major,
+            minor,
+            micro,
+        }
+    }
+}
+
+impl FromStr for Version {
+    type Err = ParseIntError;
+
+    /// Parses a version in the form of `major.minor.micro`.
+    ///
+    /// Missing components default to `0` and packaging suffixes, such as `-3.fc40`, are ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let end = s
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(s.len());
+        let mut components = s[..end].split('.');
+
+        let major = components.next().unwrap_or_default().parse()?;
+        let minor = match components.next() {
+            Some(minor) => minor.parse()?,
+            None => 0,
+        };
+        let micro = match components.next() {
+            Some(micro) if !micro.is_empty() => micro.parse()?,
+            _ => 0,
+        };
+        Ok(Self::new(major, minor, micro))
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.micro)
+    }
+}
+
+/// Specification of block devices, used by [`crate::Client::resolve_device`].
+///
+/// Only block devices matching all of the set criteria are resolved.
+///
+/// # Examples
+/// ```
+/// # use udisks2::manager::DevSpec;
+/// let devspec = DevSpec::new().label("Data").partlabel("Data");
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DevSpec {
+    path: Option<String>,
+    label: Option<String>,
+    uuid: Option<String>,
+    partuuid: Option<String>,
+    partlabel: Option<String>,
+}
+
+impl DevSpec {
+    /// Creates a new, empty specification.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Device file of the block device or one of its symlinks, e.g. `/dev/sda1` or
+    /// `/dev/disk/by-id/...`.
+    pub fn path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Label of the filesystem or other structured data on the block device.
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// UUID of the filesystem or other structured data on the block device.
+    pub fn uuid(mut self, uuid: impl Into<String>) -> Self {
+        self.uuid = Some(uuid.into());
+        self
+    }
+
+    /// UUID of the partition, as stored in the partition table.
+    pub fn partuuid(mut self, partuuid: impl Into<String>) -> Self {
+        self.partuuid = Some(partuuid.into());
+        self
+    }
+
+    /// Name of the partition, as stored in the partition table.
+    pub fn partlabel(mut self, partlabel: impl Into<String>) -> Self {
+        self.partlabel = Some(partlabel.into());
+        self
+    }
+}
+
+impl From<DevSpec> for HashMap<&'static str, Value<'static>> {
+    fn from(devspec: DevSpec) -> Self {
+        [
+            ("path", devspec.path),
+            ("label", devspec.label),
+            ("uuid", devspec.uuid),
+            ("partuuid", devspec.partuuid),
+            ("partlabel", devspec.partlabel),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| Some((key, Value::from(value?))))
+        .collect()
+    }
+}
+
+/// Resize modes supported for a filesystem type.
+///
+/// Returned as part of [`ManagerProxy::can_resize`], use
+/// [`enumflags2::BitFlags::from_bits_truncate`] to convert the returned value.
+#[bitflags]
+#[repr(u64)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum ResizeFlags {
+    /// The filesystem can be shrunk while it is not mounted.
+    OfflineShrink = 1 << 1,
+    /// The filesystem can be grown while it is not mounted.
+    OfflineGrow = 1 << 2,
+    /// The filesystem can be shrunk while it is mounted.
+    OnlineShrink = 1 << 3,
+    /// The filesystem can be grown while it is mounted.
+    OnlineGrow = 1 << 4,
+}
+
+/// Optional module of the daemon, which adds support for additional storage technologies.
+///
+/// Modules can be loaded using [`ManagerProxy::enable_module`].
+/// See [`crate::Client::available_modules`] to find out which modules are available.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Module {
+    /// Logical Volume Manager (LVM2)
+    Lvm2,
+    /// iSCSI initiator
+    Iscsi,
+    /// Btrfs filesystem management
+    Btrfs,
+    /// Compressed RAM-based block devices (zram)
+    Zram,
+    /// Block layer cache (bcache)
+    Bcache,
+}
+
+impl Module {
+    /// All known modules.
+    pub const ALL: [Self; 5] = [
+        Self::Lvm2,
+        Self::Iscsi,
+        Self::Btrfs,
+        Self::Zram,
+        Self::Bcache,
+    ];
+
+    /// Returns the name of the module, as passed to [`ManagerProxy::enable_module`].
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Lvm2 => "lvm2",
+            Self::Iscsi => "iscsi",
+            Self::Btrfs => "btrfs",
+            Self::Zram => "zram",
+            Self::Bcache => "bcache",
+        }
+    }
+
+    /// Returns the interface the module exports on the manager object once it is loaded.
+    pub(crate) fn manager_interface(&self) -> &'static str {
+        match self {
+            Self::Lvm2 => "org.freedesktop.UDisks2.Manager.LVM2",
+            Self::Iscsi => "org.freedesktop.UDisks2.Manager.ISCSI.Initiator",
+            Self::Btrfs => "org.freedesktop.UDisks2.Manager.BTRFS",
+            Self::Zram => "org.freedesktop.UDisks2.Manager.ZRAM",
+            Self::Bcache => "org.freedesktop.UDisks2.Manager.Bcache",
+        }
+    }
+}
+
+impl Display for Module {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
 #[proxy(
     interface = "org.freedesktop.UDisks2.Manager",
     default_service = "org.freedesktop.UDisks2",
@@ -30,6 +246,9 @@ pub trait Manager {
     fn can_repair(&self, type_: &str) -> error::Result<(bool, String)>;
 
     /// CanResize method
+    ///
+    /// Returns whether resizing is available, the supported [`ResizeFlags`]
+    /// and the name of the missing utility, if any.
     fn can_resize(&self, type_: &str) -> error::Result<(bool, u64, String)>;
 
     /// EnableModule method
@@ -0,0 +1,57 @@
+/// Options for comparing strings, such as filesystem labels.
+///
+/// By default, strings have to match exactly.
+///
+/// # Example
+///
+/// ```
+/// use udisks2::MatchOptions;
+///
+/// let options = MatchOptions::new().trim(true);
+/// assert!(options.matches("Backup ", "Backup"));
+/// assert!(!options.matches("Backup ", "backup"));
+///
+/// let options = options.case_insensitive(true);
+/// assert!(options.matches("BACKUP ", "backup"));
+///
+/// assert!(!MatchOptions::new().matches("Backup ", "Backup"));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MatchOptions {
+    case_insensitive: bool,
+    trim: bool,
+}
+
+impl MatchOptions {
+    /// Creates new options, matching strings exactly.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the comparison should ignore case.
+    pub fn case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    /// Whether leading and trailing whitespace should be ignored.
+    pub fn trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+        self
+    }
+
+    /// Whether `value` matches `pattern` using these options.
+    pub fn matches(&self, value: &str, pattern: &str) -> bool {
+        let (value, pattern) = if self.trim {
+            (value.trim(), pattern.trim())
+        } else {
+            (value, pattern)
+        };
+
+        if self.case_insensitive {
+            value.to_lowercase() == pattern.to_lowercase()
+        } else {
+            value == pattern
+        }
+    }
+}
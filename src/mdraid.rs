@@ -10,9 +10,61 @@
 //! section of the zbus documentation.
 //!
 
+use std::time::Duration;
+
 use zbus::proxy;
 
-use crate::error;
+use crate::{error, Size};
+
+/// Status of an ongoing sync operation of a RAID array.
+///
+/// Returned by [`MDRaidProxy::sync_status`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncStatus {
+    /// The sync action that is performed, e.g. `resync`, `recover`, `check` or `repair`.
+    ///
+    /// See [`MDRaidProxy::sync_action`].
+    pub action: String,
+    /// Fraction of the operation that has been completed, between `0.0` and `1.0`.
+    pub fraction: f64,
+    /// Number of bytes synced per second, if known.
+    pub rate: Option<Size>,
+    /// Estimated time until the operation is finished, if known.
+    pub eta: Option<Duration>,
+}
+
+impl MDRaidProxy<'_> {
+    /// Returns the status of the currently ongoing sync operation.
+    ///
+    /// If the array is not running or no sync operation is in progress, [`None`] is returned.
+    pub async fn sync_status(&self) -> error::Result<Option<SyncStatus>> {
+        if !self.running().await? {
+            return Ok(None);
+        }
+
+        let action = self.sync_action().await?;
+        if action.is_empty() || action == "idle" {
+            return Ok(None);
+        }
+
+        let rate = self.sync_rate().await?;
+        Ok(Some(SyncStatus {
+            action,
+            fraction: self.sync_completed().await?,
+            rate: (rate != 0).then_some(Size::from_bytes(rate)),
+            eta: self.sync_remaining().await?,
+        }))
+    }
+
+    /// Returns the [`MDRaidProxy::sync_remaining_time`] as [`Duration`].
+    ///
+    /// If the remaining time is unknown, e.g. because no sync operation is in progress,
+    /// [`None`] is returned.
+    pub async fn sync_remaining(&self) -> error::Result<Option<Duration>> {
+        let remaining_time = self.sync_remaining_time().await?;
+        Ok((remaining_time != 0).then(|| Duration::from_micros(remaining_time)))
+    }
+}
 
 #[proxy(
     interface = "org.freedesktop.UDisks2.MDRaid",
@@ -10,9 +10,353 @@
 //! section of the zbus documentation.
 //!
 
-use zbus::proxy;
+use std::{collections::HashMap, fmt, str::FromStr, time::SystemTime};
 
-use crate::error;
+use enumflags2::{bitflags, BitFlags};
+use serde::{de::IntoDeserializer, Deserialize, Serialize};
+use zbus::{
+    proxy,
+    zvariant::{Type, Value},
+};
+
+use crate::{error, gettext::pgettext};
+
+/// Status of the last NVMe device self-test operation.
+///
+/// See [`crate::SelfTestResult`] for a classification shared with ATA self-tests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Type)]
+#[zvariant(signature = "s")]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum SmartSelftestStatus {
+    /// Operation completed without error.
+    Success,
+    /// Operation was aborted by a Device Self-test command.
+    Aborted,
+    /// Operation was aborted by a Controller Level Reset.
+    CtrlReset,
+    /// Operation was aborted due to a removal of a namespace from the namespace inventory.
+    NsRemoved,
+    /// Operation was aborted due to the processing of a Format NVM command.
+    AbortedFormat,
+    /// A fatal error or unknown test error occurred while the controller was executing
+    /// the device self-test operation and the operation did not complete.
+    FatalError,
+    /// Operation completed with a segment that failed and the segment that failed is not known.
+    UnknownSegFail,
+    /// Operation completed with one or more failed segments and the first segment
+    /// that failed is indicated in the Segment Number field.
+    KnownSegFail,
+    /// Operation was aborted for an unknown reason.
+    AbortedUnknown,
+    /// Operation was aborted due to a sanitize operation.
+    AbortedSanitize,
+    /// Self-test is currently in progress.
+    #[serde(rename = "inprogress")]
+    InProgress,
+    /// Status is unknown, e.g. because no self-test has been run.
+    #[serde(rename(deserialize = ""))]
+    Unknown,
+}
+
+impl FromStr for SmartSelftestStatus {
+    type Err = serde::de::value::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let res: Result<_, Self::Err> = Self::deserialize(s.into_deserializer());
+        Ok(res.unwrap_or(Self::Unknown))
+    }
+}
+
+impl_try_from_string_value!(SmartSelftestStatus);
+
+/// Critical warning reported by an NVMe controller.
+///
+/// The values correspond to the bits of the Critical Warning field of the SMART / Health
+/// Information log page.
+#[bitflags]
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SmartCriticalWarning {
+    /// The available spare capacity has fallen below the threshold.
+    Spare = 1 << 0,
+    /// A temperature is above an over temperature threshold or below an under temperature threshold.
+    Temperature = 1 << 1,
+    /// The reliability is degraded due to excessive media or internal errors.
+    Degraded = 1 << 2,
+    /// All of the media has been placed in read-only mode.
+    ReadOnly = 1 << 3,
+    /// The volatile memory backup device has failed.
+    VolatileMem = 1 << 4,
+    /// The Persistent Memory Region has become read-only or unreliable.
+    PmrReadOnly = 1 << 5,
+}
+
+impl SmartCriticalWarning {
+    /// Returns the warning as used by [`ControllerProxy::smart_critical_warning`].
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Spare => "spare",
+            Self::Temperature => "temperature",
+            Self::Degraded => "degraded",
+            Self::ReadOnly => "readonly",
+            Self::VolatileMem => "volatile_mem",
+            Self::PmrReadOnly => "pmr_readonly",
+        }
+    }
+
+    /// Returns a human-readable, localized description of the warning.
+    pub fn description(&self) -> String {
+        match self {
+            Self::Spare => pgettext(
+                "nvme-critical-warning",
+                "Available spare capacity is below the threshold",
+            ),
+            Self::Temperature => pgettext(
+                "nvme-critical-warning",
+                "Temperature is outside of the safe range",
+            ),
+            Self::Degraded => pgettext(
+                "nvme-critical-warning",
+                "Reliability is degraded due to media or internal errors",
+            ),
+            Self::ReadOnly => pgettext("nvme-critical-warning", "Media is in read-only mode"),
+            Self::VolatileMem => pgettext(
+                "nvme-critical-warning",
+                "Volatile memory backup device has failed",
+            ),
+            Self::PmrReadOnly => pgettext(
+                "nvme-critical-warning",
+                "Persistent memory region is read-only or unreliable",
+            ),
+        }
+    }
+}
+
+/// Set of critical warnings reported by an NVMe controller.
+///
+/// Returned by [`ControllerProxy::smart_critical_warnings`].
+///
+/// # Examples
+/// ```
+/// # use udisks2::nvme::controller::{SmartCriticalWarning, SmartCriticalWarnings};
+/// let warnings: SmartCriticalWarnings = ["spare", "readonly"].into_iter().collect();
+/// assert!(!warnings.is_healthy());
+/// assert!(warnings.contains(SmartCriticalWarning::ReadOnly));
+/// assert!(!warnings.contains(SmartCriticalWarning::Temperature));
+/// assert_eq!(
+///     warnings.to_string(),
+///     "Available spare capacity is below the threshold, Media is in read-only mode"
+/// );
+///
+/// assert!(SmartCriticalWarnings::default().is_healthy());
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SmartCriticalWarnings(BitFlags<SmartCriticalWarning>);
+
+impl SmartCriticalWarnings {
+    /// Whether no critical warning is set.
+    pub fn is_healthy(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Whether the given warning is set.
+    pub fn contains(&self, warning: SmartCriticalWarning) -> bool {
+        self.0.contains(warning)
+    }
+
+    /// Returns an iterator over the set warnings.
+    pub fn iter(&self) -> impl Iterator<Item = SmartCriticalWarning> {
+        self.0.iter()
+    }
+
+    /// Returns the set warnings as [`BitFlags`].
+    pub fn flags(&self) -> BitFlags<SmartCriticalWarning> {
+        self.0
+    }
+}
+
+impl From<BitFlags<SmartCriticalWarning>> for SmartCriticalWarnings {
+    fn from(flags: BitFlags<SmartCriticalWarning>) -> Self {
+        Self(flags)
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<S> for SmartCriticalWarnings {
+    /// Collects the warnings from the strings used by the daemon, ignoring unknown warnings.
+    fn from_iter<T: IntoIterator<Item = S>>(iter: T) -> Self {
+        let flags = iter
+            .into_iter()
+            .filter_map(|name| {
+                BitFlags::<SmartCriticalWarning>::all()
+                    .iter()
+                    .find(|warning| warning.as_str() == name.as_ref())
+            })
+            .collect();
+        Self(flags)
+    }
+}
+
+impl fmt::Display for SmartCriticalWarnings {
+    /// Formats the localized descriptions of all set warnings, separated by commas.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let descriptions: Vec<String> = self.iter().map(|warning| warning.description()).collect();
+        f.write_str(&descriptions.join(", "))
+    }
+}
+
+/// Type of a sanitize operation, as used by [`ControllerProxy::sanitize_start_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SanitizeAction {
+    /// Alter user data with a low-level block erase method that is specific to the media.
+    BlockErase,
+    /// Overwrite user data with a data pattern, see [`SanitizeOptions::overwrite_pass_count`],
+    /// [`SanitizeOptions::overwrite_pattern`] and [`SanitizeOptions::overwrite_invert_pattern`].
+    Overwrite,
+    /// Change the media encryption keys of all user data, making it unreadable.
+    CryptoErase,
+    /// Exit a failed sanitize operation, i.e. the controller's restricted processing mode.
+    ExitFailure,
+}
+
+impl SanitizeAction {
+    /// Returns the action as used by [`ControllerProxy::sanitize_start`].
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::BlockErase => "block-erase",
+            Self::Overwrite => "overwrite",
+            Self::CryptoErase => "crypto-erase",
+            Self::ExitFailure => "exit-failure",
+        }
+    }
+}
+
+/// Options for starting a sanitize operation.
+///
+/// Can be converted into the options of [`ControllerProxy::sanitize_start`].
+/// The overwrite options are only used by [`SanitizeAction::Overwrite`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SanitizeOptions {
+    overwrite_pass_count: Option<u8>,
+    overwrite_pattern: Option<u32>,
+    overwrite_invert_pattern: bool,
+    no_user_interaction: bool,
+}
+
+impl SanitizeOptions {
+    /// Minimum number of overwrite passes.
+    pub const MIN_OVERWRITE_PASS_COUNT: u8 = 1;
+    /// Maximum number of overwrite passes.
+    pub const MAX_OVERWRITE_PASS_COUNT: u8 = 15;
+
+    /// Creates new, empty options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of overwrite passes, between [`Self::MIN_OVERWRITE_PASS_COUNT`] and
+    /// [`Self::MAX_OVERWRITE_PASS_COUNT`].
+    ///
+    /// The value is validated by [`ControllerProxy::sanitize_start_with`].
+    pub fn overwrite_pass_count(mut self, count: u8) -> Self {
+        self.overwrite_pass_count = Some(count);
+        self
+    }
+
+    /// 32-bit pattern used for overwriting.
+    pub fn overwrite_pattern(mut self, pattern: u32) -> Self {
+        self.overwrite_pattern = Some(pattern);
+        self
+    }
+
+    /// Whether the pattern should be inverted between overwrite passes.
+    pub fn overwrite_invert_pattern(mut self, invert: bool) -> Self {
+        self.overwrite_invert_pattern = invert;
+        self
+    }
+
+    /// Whether no user interaction should happen, when checking if the operation is authorized.
+    ///
+    /// See [`crate::standard_options`].
+    pub fn no_user_interaction(mut self, no_user_interaction: bool) -> Self {
+        self.no_user_interaction = no_user_interaction;
+        self
+    }
+
+    fn validate(&self) -> error::Result<()> {
+        match self.overwrite_pass_count {
+            Some(count)
+                if !(Self::MIN_OVERWRITE_PASS_COUNT..=Self::MAX_OVERWRITE_PASS_COUNT)
+                    .contains(&count) =>
+            {
+                Err(zbus::fdo::Error::InvalidArgs(format!(
+                    "Overwrite pass count must be between {} and {}, got {}",
+                    Self::MIN_OVERWRITE_PASS_COUNT,
+                    Self::MAX_OVERWRITE_PASS_COUNT,
+                    count
+                ))
+                .into())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl From<SanitizeOptions> for HashMap<&'static str, Value<'static>> {
+    fn from(value: SanitizeOptions) -> Self {
+        let mut options = crate::standard_options(value.no_user_interaction);
+        if let Some(count) = value.overwrite_pass_count {
+            options.insert("overwrite_pass_count", count.into());
+        }
+        if let Some(pattern) = value.overwrite_pattern {
+            options.insert("overwrite_pattern", pattern.into());
+        }
+        if value.overwrite_invert_pattern {
+            options.insert("overwrite_invert_pattern", true.into());
+        }
+        options
+    }
+}
+
+impl ControllerProxy<'_> {
+    /// Returns the [`ControllerProxy::smart_updated`] as [`SystemTime`].
+    ///
+    /// If the SMART data has never been collected, [`None`] is returned.
+    pub async fn smart_updated_system_time(&self) -> error::Result<Option<SystemTime>> {
+        Ok(crate::system_time_from_secs(self.smart_updated().await?))
+    }
+
+    /// Returns the [`ControllerProxy::smart_critical_warning`] as [`SmartCriticalWarnings`].
+    ///
+    /// Unknown warnings are ignored.
+    pub async fn smart_critical_warnings(&self) -> error::Result<SmartCriticalWarnings> {
+        Ok(self.smart_critical_warning().await?.into_iter().collect())
+    }
+
+    /// Starts a sanitize operation on the controller with typed options.
+    ///
+    /// A sanitize operation alters all user data in the NVM subsystem, so that recovery of
+    /// previous user data is not possible. The operation is performed in the background;
+    /// it cannot be aborted, survives resets and is resumed after power loss.
+    /// Its progress is reported by [`ControllerProxy::sanitize_status`] and
+    /// [`ControllerProxy::sanitize_percent_remaining`].
+    ///
+    /// The daemon sets the No-Deallocate After Sanitize bit, so the controller shall not
+    /// deallocate the media after the operation and the sanitized data, e.g. the overwrite
+    /// pattern, remains readable.
+    ///
+    /// # Errors
+    /// Returns [`zbus::fdo::Error::InvalidArgs`] without contacting the daemon if the overwrite
+    /// pass count is out of range, or an error if the operation could not be started.
+    pub async fn sanitize_start_with(
+        &self,
+        action: SanitizeAction,
+        options: SanitizeOptions,
+    ) -> error::Result<()> {
+        options.validate()?;
+        self.sanitize_start(action.as_str(), options.into()).await
+    }
+}
 
 #[proxy(
     interface = "org.freedesktop.UDisks2.NVMe.Controller",
@@ -86,7 +430,7 @@ pub trait Controller {
 
     /// SmartSelftestStatus property
     #[zbus(property)]
-    fn smart_selftest_status(&self) -> error::Result<String>;
+    fn smart_selftest_status(&self) -> error::Result<SmartSelftestStatus>;
 
     /// SmartTemperature property
     #[zbus(property)]
@@ -1,3 +1,5 @@
+use std::hash::{Hash, Hasher};
+
 use zbus::fdo::ObjectManagerProxy;
 use zbus::zvariant::OwnedObjectPath;
 
@@ -8,6 +10,8 @@ use crate::{
 use crate::{error, nvme};
 
 /// Utility struct for easily accessing interfaces.
+///
+/// Objects are compared and hashed by their object path.
 #[derive(Debug, Clone)]
 pub struct Object {
     connection: zbus::Connection,
@@ -70,6 +74,13 @@ impl Object {
         &self.path
     }
 
+    /// Whether `proxy` is an interface of this object, i.e. it uses the same object path.
+    ///
+    /// This can be used to compare proxies, e.g. a [`drive::DriveProxy`], with an object.
+    pub fn path_eq<'p>(&self, proxy: &impl AsRef<zbus::Proxy<'p>>) -> bool {
+        proxy.as_ref().path().as_str() == self.path.as_str()
+    }
+
     impl_get_interface!(
         block, block::BlockProxy<'static>, "org.freedesktop.UDisks2.Block";
         drive, drive::DriveProxy<'static>, "org.freedesktop.UDisks2.Drive";
@@ -79,12 +90,26 @@ impl Object {
         swapspace, swapspace::SwapspaceProxy<'static>, "org.freedesktop.UDisks2.Swapspace";
         encrypted, encrypted::EncryptedProxy<'static>, "org.freedesktop.UDisks2.Encrypted";
         r#loop, r#loop::LoopProxy<'static>, "org.freedesktop.UDisks2.Loop";
-        manager_nvme, nvme::NVMeProxy<'static>, "org.freedesktop.UDisks2.Manager.Nvme";
+        manager_nvme, nvme::NVMeProxy<'static>, "org.freedesktop.UDisks2.Manager.NVMe";
         partition, partition::PartitionProxy<'static>, "org.freedesktop.UDisks2.Partition";
         partition_table, partitiontable::PartitionTableProxy<'static>, "org.freedesktop.UDisks2.PartitionTable";
-        mdraid, mdraid::MDRaidProxy<'static>, "org.freedesktop.UDisks2.Mdraid";
+        mdraid, mdraid::MDRaidProxy<'static>, "org.freedesktop.UDisks2.MDRaid";
         nvme_controller, nvme::controller::ControllerProxy<'static>, "org.freedesktop.UDisks2.NVMe.Controller";
         nvme_namespace, nvme::namespace::NamespaceProxy<'static>, "org.freedesktop.UDisks2.NVMe.Namespace";
-        nvme_fabrics, nvme::fabrics::FabricsProxy<'static>, "org.freedesktop.UDisks2.Nvme.Fabrics"
+        nvme_fabrics, nvme::fabrics::FabricsProxy<'static>, "org.freedesktop.UDisks2.NVMe.Fabrics"
     );
 }
+
+impl PartialEq for Object {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path
+    }
+}
+
+impl Eq for Object {}
+
+impl Hash for Object {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.path.hash(state);
+    }
+}
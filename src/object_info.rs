@@ -1,14 +1,17 @@
 use std::ffi::CString;
 
-use gettextrs::{gettext, pgettext};
+use zbus::zvariant::OwnedObjectPath;
 
 use crate::{
     block,
     drive::{self, RotationRate},
-    error, mdraid,
-    gettext::{dpgettext, gettext_f, pgettext_f},
+    error,
+    gettext::{dpgettext, gettext, gettext_f, pgettext, pgettext_f},
+    mdraid,
     media::{self, DriveType},
-    partition, r#loop, Client, Object,
+    partition, r#loop,
+    snapshot::{self, ObjectSnapshot},
+    Client, Object,
 };
 
 /// Icon
@@ -47,6 +50,146 @@ impl Icon {
     pub fn name_symbolic(&self) -> Option<&String> {
         self.name_symbolic.as_ref()
     }
+
+    /// Converts the icon into a [`gio::ThemedIcon`].
+    ///
+    /// The symbolic icon is preferred, falling back to the normal icon if it is not available
+    /// in the icon theme.
+    /// If the icon has neither name, [`None`] is returned.
+    ///
+    /// Requires the `gio` feature.
+    #[cfg(feature = "gio")]
+    pub fn to_gicon(&self) -> Option<gio::ThemedIcon> {
+        let names: Vec<&str> = [self.name_symbolic.as_deref(), self.name.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect();
+        if names.is_empty() {
+            return None;
+        }
+        Some(gio::ThemedIcon::from_names(&names))
+    }
+}
+
+/// Kind of device an [`ObjectInfo`] describes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeviceKind {
+    /// A drive, or a block device belonging to a drive.
+    Drive,
+    /// A RAID array, or a block device belonging to a RAID array.
+    MDRaid,
+    /// A block device, which belongs neither to a drive nor to a RAID array.
+    Block,
+    /// A loop device.
+    Loop,
+    /// An object without any recognized interface.
+    #[default]
+    Unknown,
+}
+
+impl DeviceKind {
+    /// Parses the kind from the prefix of an [`ObjectInfo::sort_key`].
+    ///
+    /// Prefer [`ObjectInfo::kind`], which is set directly when the info is created.
+    /// This is useful if only the sort key is available, e.g. because it was stored.
+    ///
+    /// # Examples
+    /// ```
+    /// use udisks2::DeviceKind;
+    ///
+    /// assert_eq!(DeviceKind::from_sort_key("00_drive_sda"), DeviceKind::Drive);
+    /// assert_eq!(DeviceKind::from_sort_key("01_mdraid_md0_0"), DeviceKind::MDRaid);
+    /// assert_eq!(DeviceKind::from_sort_key("02_block_dm_2d0_0"), DeviceKind::Block);
+    /// assert_eq!(DeviceKind::from_sort_key("03_loop_loop0_1"), DeviceKind::Loop);
+    /// assert_eq!(DeviceKind::from_sort_key("04_unknown_foo"), DeviceKind::Unknown);
+    /// assert_eq!(DeviceKind::from_sort_key("drive"), DeviceKind::Unknown);
+    /// ```
+    pub fn from_sort_key(sort_key: &str) -> Self {
+        let mut parts = sort_key.splitn(3, '_');
+        match (parts.next(), parts.next()) {
+            (Some("00"), Some("drive")) => Self::Drive,
+            (Some("01"), Some("mdraid")) => Self::MDRaid,
+            (Some("02"), Some("block")) => Self::Block,
+            (Some("03"), Some("loop")) => Self::Loop,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// Prefix of the sort keys of this kind, e.g. `00_drive`.
+    pub(crate) fn sort_key_prefix(&self) -> &'static str {
+        match self {
+            Self::Drive => "00_drive",
+            Self::MDRaid => "01_mdraid",
+            Self::Block => "02_block",
+            Self::Loop => "03_loop",
+            Self::Unknown => "04_unknown",
+        }
+    }
+}
+
+/// Builder for the localized single-line description of an object.
+///
+/// The used template depends on which parts are present:
+/// - `primary — secondary [revision] (device)`
+/// - `primary — secondary (device)`
+/// - `primary — secondary`
+/// - `primary (device)`
+/// - `primary`
+///
+/// The revision is only used if both the secondary part and the device are present.
+struct OneLiner<'a> {
+    context: &'static str,
+    primary: &'a str,
+    secondary: Option<&'a str>,
+    revision: Option<&'a str>,
+    device: Option<&'a str>,
+}
+
+impl<'a> OneLiner<'a> {
+    fn new(context: &'static str, primary: &'a str) -> Self {
+        Self {
+            context,
+            primary,
+            secondary: None,
+            revision: None,
+            device: None,
+        }
+    }
+
+    fn secondary(mut self, secondary: impl Into<Option<&'a str>>) -> Self {
+        self.secondary = secondary.into();
+        self
+    }
+
+    fn revision(mut self, revision: impl Into<Option<&'a str>>) -> Self {
+        self.revision = revision.into();
+        self
+    }
+
+    fn device(mut self, device: impl Into<Option<&'a str>>) -> Self {
+        self.device = device.into();
+        self
+    }
+
+    fn build(self) -> String {
+        match (self.secondary, self.revision, self.device) {
+            (Some(secondary), Some(revision), Some(device)) => pgettext_f(
+                self.context,
+                "{} — {} [{}] ({})",
+                [self.primary, secondary, revision, device],
+            ),
+            (Some(secondary), _, Some(device)) => pgettext_f(
+                self.context,
+                "{} — {} ({})",
+                [self.primary, secondary, device],
+            ),
+            (Some(secondary), _, None) => {
+                pgettext_f(self.context, "{} — {}", [self.primary, secondary])
+            }
+            (None, _, Some(device)) => pgettext_f(self.context, "{} ({})", [self.primary, device]),
+            (None, _, None) => pgettext_f(self.context, "{}", [self.primary]),
+        }
+    }
 }
 
 /// Detailed information about the D-Bus interfaces (such as [`block::BlockProxy`] and [`drive::DriveProxy`])
@@ -87,6 +230,124 @@ pub struct ObjectInfo<'a> {
     ///
     /// This can be used to sort objects.
     pub sort_key: Option<String>,
+
+    /// Whether [`Self::description`] and [`Self::media_description`] were overridden by
+    /// [`block::BlockProxy::hint_name()`].
+    pub description_from_hint: bool,
+
+    /// Whether [`Self::icon`] and [`Self::media_icon`] were overridden by
+    /// [`block::BlockProxy::hint_icon_name()`] or [`block::BlockProxy::hint_symbolic_icon_name()`].
+    pub icon_from_hint: bool,
+
+    /// Whether the object is a drive with removable media, e.g. an optical drive or card reader.
+    ///
+    /// Block devices of a drive report the value of their drive, while objects without a drive
+    /// are always `false`.
+    ///
+    /// Together with [`Self::media_available`], this allows distinguishing the cases where
+    /// [`Self::media_description`] is [`None`]:
+    ///
+    /// - without removable media, there is no media to describe
+    /// - with removable media, but no media available, the drive is empty ("No media")
+    /// - with removable media and media available, the media is not known ("Unknown media")
+    pub has_removable_media: bool,
+
+    /// Whether media is available in the drive.
+    ///
+    /// Always `true` for drives without removable media. See [`Self::has_removable_media`].
+    /// Block devices of a drive, such as its partitions, report the value of their drive.
+    /// Always `false` for objects without a drive, such as loop devices or RAID arrays,
+    /// as well as for drives whose media availability could not be read.
+    pub media_available: bool,
+
+    /// Object path of the object the object belongs to, e.g. to link objects in a tree view.
+    ///
+    /// This is the whole-disk block device (i.e. the partition table) for partitions,
+    /// and the drive or RAID array for other block devices.
+    /// For top-level objects, such as drives, RAID arrays and loop devices, it is [`None`].
+    pub parent: Option<OwnedObjectPath>,
+
+    kind: DeviceKind,
+}
+
+/// Owned version of [`ObjectInfo`].
+///
+/// Returned by [`Client::all_object_infos`].
+#[derive(Debug, Clone)]
+pub struct ObjectInfoData {
+    /// The [`Object`] that the info is about
+    pub object: Object,
+
+    /// See [`ObjectInfo::name`].
+    pub name: Option<String>,
+
+    /// See [`ObjectInfo::description`].
+    pub description: Option<String>,
+
+    /// See [`ObjectInfo::icon`].
+    pub icon: Icon,
+
+    /// See [`ObjectInfo::media_description`].
+    pub media_description: Option<String>,
+
+    /// See [`ObjectInfo::media_icon`].
+    pub media_icon: Icon,
+
+    /// See [`ObjectInfo::one_liner`].
+    pub one_liner: Option<String>,
+
+    /// See [`ObjectInfo::sort_key`].
+    pub sort_key: Option<String>,
+
+    /// See [`ObjectInfo::description_from_hint`].
+    pub description_from_hint: bool,
+
+    /// See [`ObjectInfo::icon_from_hint`].
+    pub icon_from_hint: bool,
+
+    /// See [`ObjectInfo::has_removable_media`].
+    pub has_removable_media: bool,
+
+    /// See [`ObjectInfo::media_available`].
+    pub media_available: bool,
+
+    /// See [`ObjectInfo::parent`].
+    pub parent: Option<OwnedObjectPath>,
+
+    kind: DeviceKind,
+}
+
+impl ObjectInfoData {
+    /// See [`ObjectInfo::kind`].
+    pub fn kind(&self) -> DeviceKind {
+        self.kind
+    }
+
+    /// See [`ObjectInfo::is_empty`].
+    pub fn is_empty(&self) -> bool {
+        self.kind == DeviceKind::Unknown
+    }
+}
+
+impl From<ObjectInfo<'_>> for ObjectInfoData {
+    fn from(info: ObjectInfo<'_>) -> Self {
+        Self {
+            object: info.object.clone(),
+            name: info.name,
+            description: info.description,
+            icon: info.icon,
+            media_description: info.media_description,
+            media_icon: info.media_icon,
+            one_liner: info.one_liner,
+            sort_key: info.sort_key,
+            description_from_hint: info.description_from_hint,
+            icon_from_hint: info.icon_from_hint,
+            has_removable_media: info.has_removable_media,
+            media_available: info.media_available,
+            parent: info.parent,
+            kind: info.kind,
+        }
+    }
 }
 
 impl<'a> ObjectInfo<'a> {
@@ -100,25 +361,63 @@ impl<'a> ObjectInfo<'a> {
             media_icon: Icon::default(),
             one_liner: None,
             sort_key: None,
+            description_from_hint: false,
+            icon_from_hint: false,
+            has_removable_media: false,
+            media_available: false,
+            parent: None,
+            kind: DeviceKind::Unknown,
         }
     }
 
+    /// The kind of device the info describes.
+    pub fn kind(&self) -> DeviceKind {
+        self.kind
+    }
+
+    /// Whether the object has no recognized interface.
+    ///
+    /// In this case, the info only contains a generic name, description, icon and sort key
+    /// derived from the object path, all other fields are empty.
+    pub fn is_empty(&self) -> bool {
+        self.kind == DeviceKind::Unknown
+    }
+
+    pub(crate) fn info_for_unknown(&mut self) {
+        self.kind = DeviceKind::Unknown;
+        // safe to unwrap, object path always have at least one `/`
+        let name = self.object.object_path().split('/').next_back().unwrap();
+        self.name = Some(name.to_owned());
+        // Translators: Used to describe an object that is neither a drive, RAID array nor block device
+        self.description = Some(pgettext("unknown-object", "Unknown Device"));
+        self.icon = Icon::new(
+            Some("drive-harddisk".to_owned()),
+            Some("drive-harddisk-symbolic".to_owned()),
+        );
+        self.one_liner = Some(
+            OneLiner::new("one-liner-unknown", self.description.as_deref().unwrap())
+                .device(name)
+                .build(),
+        );
+        self.sort_key = Some(format!("{}_{}", self.kind.sort_key_prefix(), name));
+    }
+
     pub(crate) async fn info_for_block(
         &mut self,
         client: &Client,
         block: block::BlockProxy<'_>,
         partition: Option<partition::PartitionProxy<'_>>,
     ) {
+        self.kind = DeviceKind::Block;
         self.icon = Icon::new(
             Some("drive-removable-media".to_owned()),
             Some("drive-removable-media-symbolic".to_owned()),
         );
         self.name = block
-            .preferred_device()
+            .preferred_device_path()
             .await
             .ok()
-            .and_then(|dev| CString::from_vec_with_nul(dev).ok())
-            .and_then(|dev| dev.to_str().map(|p| p.to_string()).ok());
+            .map(|dev| dev.to_string_lossy().into_owned());
 
         let size = block.size().await;
         if let Ok(size) = size {
@@ -157,23 +456,21 @@ impl<'a> ObjectInfo<'a> {
             );
         }
 
-        // Translators: String used for one-liner description of a block device.
-        //              The first %s is the description of the object (e.g. "50 GB Block Device").
-        //              The second %s is the special device file (e.g. "/dev/sda2").
         //TODO: C version calls preferred_device again, instead of using name, why?
-        self.one_liner = Some(pgettext_f(
-            "one-liner-block",
-            "{} ({})",
-            [
-                self.description.as_ref().unwrap(),
-                self.name.as_ref().unwrap(),
-            ],
-        ));
+        self.one_liner = Some(
+            // Translators: String used for one-liner description of a block device.
+            //              The first %s is the description of the object (e.g. "50 GB Block Device").
+            //              The second %s is the special device file (e.g. "/dev/sda2").
+            OneLiner::new("one-liner-block", self.description.as_deref().unwrap())
+                .device(self.name.as_deref())
+                .build(),
+        );
 
         self.sort_key = Some(format!(
-            "02_block_{}_{}",
+            "{}_{}_{}",
+            self.kind.sort_key_prefix(),
             // safe to unwrap, object path always have at least one `/`
-            self.object.object_path().split('/').last().unwrap(),
+            self.object.object_path().split('/').next_back().unwrap(),
             //TODO: use async closure when stable
             partition_number.unwrap_or(0)
         ))
@@ -186,6 +483,7 @@ impl<'a> ObjectInfo<'a> {
         block: block::BlockProxy<'_>,
         partition: Option<partition::PartitionProxy<'_>>,
     ) {
+        self.kind = DeviceKind::Loop;
         self.icon = Icon::new(
             Some("drive-removable-media".to_owned()),
             Some("drive-removable-media-symbolic".to_owned()),
@@ -234,31 +532,28 @@ impl<'a> ObjectInfo<'a> {
             );
         }
 
-        // Translators: String used for one-liner description of a loop device.
-        //              The first %s is the description of the object (e.g. "2 GB Loop Device").
-        //              The second %s is the name of the backing file (e.g. "/home/davidz/file.iso").
-        //              The third %s is the special device file (e.g. "/dev/loop2").
-        self.one_liner = Some(pgettext_f(
-            "one-liner-loop",
-            "{} — {} ({})",
-            [
-                self.description.as_ref().unwrap(),
-                //safe to unwrap, has been set previously
-                self.name.as_ref().unwrap(),
-                &block
-                    .preferred_device()
-                    .await
-                    .ok()
-                    .and_then(|dev| CString::from_vec_with_nul(dev).ok())
-                    .and_then(|dev| dev.to_str().map(|p| p.to_string()).ok())
-                    .unwrap_or_default(),
-            ],
-        ));
+        let preferred_device = block
+            .preferred_device_path()
+            .await
+            .ok()
+            .map(|dev| dev.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        self.one_liner = Some(
+            // Translators: String used for one-liner description of a loop device.
+            //              The first %s is the description of the object (e.g. "2 GB Loop Device").
+            //              The second %s is the name of the backing file (e.g. "/home/davidz/file.iso").
+            //              The third %s is the special device file (e.g. "/dev/loop2").
+            OneLiner::new("one-liner-loop", self.description.as_deref().unwrap())
+                .secondary(self.name.as_deref())
+                .device(preferred_device.as_str())
+                .build(),
+        );
 
         self.sort_key = Some(format!(
-            "03_loop_{}_{}",
+            "{}_{}_{}",
+            self.kind.sort_key_prefix(),
             // safe to unwrap, object path always have at least one `/`
-            self.object.object_path().split('/').last().unwrap(),
+            self.object.object_path().split('/').next_back().unwrap(),
             //TODO: use async closure when stable
             partition_number.unwrap_or(0)
         ));
@@ -267,11 +562,18 @@ impl<'a> ObjectInfo<'a> {
     pub(crate) async fn info_for_mdraid(
         &mut self,
         client: &Client,
+        snapshot: &ObjectSnapshot,
         mdraid: mdraid::MDRaidProxy<'_>,
         partition: Option<partition::PartitionProxy<'_>>,
     ) {
+        self.kind = DeviceKind::MDRaid;
         let name = mdraid.name().await.unwrap_or_default();
-        self.name = Some(name.split(':').last().unwrap_or_else(|| &name).to_string());
+        self.name = Some(
+            name.split(':')
+                .next_back()
+                .unwrap_or_else(|| &name)
+                .to_string(),
+        );
         self.icon = Icon::new(
             Some("drive-multidisk".to_owned()),
             Some("drive-multidisk-symbolic".to_owned()),
@@ -324,77 +626,77 @@ impl<'a> ObjectInfo<'a> {
             );
         }
 
-        let block = client.block_for_mdraid(&mdraid).await;
+        let block = snapshot
+            .block_for_mdraid(&mdraid.inner().path().to_owned().into())
+            .await;
         if self.name.as_deref().is_some_and(|name| !name.is_empty()) {
             if let Some(block) = block {
                 let preferred_device = block
-                    .preferred_device()
+                    .preferred_device_path()
                     .await
                     .ok()
-                    .and_then(|dev| CString::from_vec_with_nul(dev).ok())
-                    .and_then(|dev| dev.to_str().map(|p| p.to_string()).ok())
+                    .map(|dev| dev.to_string_lossy().into_owned())
                     .expect("Failed to get preferred device");
 
-                // Translators: String used for one-liner description of running RAID array.
-                //              The first %s is the array name (e.g. "AlphaGo").
-                //              The second %s is the size and level (e.g. "2 TB RAID-5").
-                //              The third %s is the special device file (e.g. "/dev/sda").
-                self.one_liner = Some(pgettext_f(
-                    "one-liner-mdraid-running",
-                    "{} — {} ({})",
-                    [
-                        self.name.as_deref().unwrap(),
-                        self.description.as_deref().unwrap_or_default(),
-                        &preferred_device,
-                    ],
-                ));
+                self.one_liner = Some(
+                    // Translators: String used for one-liner description of running RAID array.
+                    //              The first %s is the array name (e.g. "AlphaGo").
+                    //              The second %s is the size and level (e.g. "2 TB RAID-5").
+                    //              The third %s is the special device file (e.g. "/dev/sda").
+                    OneLiner::new("one-liner-mdraid-running", self.name.as_deref().unwrap())
+                        .secondary(self.description.as_deref().unwrap_or_default())
+                        .device(preferred_device.as_str())
+                        .build(),
+                );
             } else {
-                // Translators: String used for one-liner description of non-running RAID array.
-                //              The first %s is the array name (e.g. "AlphaGo").
-                //              The second %s is the size and level (e.g. "2 TB RAID-5").
-                self.one_liner = Some(pgettext_f(
-                    "one-liner-mdraid-not-running",
-                    "{} — {}",
-                    [
+                self.one_liner = Some(
+                    // Translators: String used for one-liner description of non-running RAID array.
+                    //              The first %s is the array name (e.g. "AlphaGo").
+                    //              The second %s is the size and level (e.g. "2 TB RAID-5").
+                    OneLiner::new(
+                        "one-liner-mdraid-not-running",
                         self.name.as_deref().unwrap_or_default(),
-                        self.description.as_deref().unwrap_or_default(),
-                    ],
-                ));
+                    )
+                    .secondary(self.description.as_deref().unwrap_or_default())
+                    .build(),
+                );
             }
         } else if let Some(block) = block {
             let preferred_device = block
-                .preferred_device()
+                .preferred_device_path()
                 .await
                 .ok()
-                .and_then(|dev| CString::from_vec_with_nul(dev).ok())
-                .and_then(|dev| dev.to_str().map(|p| p.to_string()).ok())
+                .map(|dev| dev.to_string_lossy().into_owned())
                 .expect("Failed to get preferred device");
 
-            // Translators: String used for one-liner description of running RAID array.
-            //              The first %s is the array name (e.g. "AlphaGo").
-            //              The second %s is the size and level (e.g. "2 TB RAID-5").
-            //              The third %s is the special device file (e.g. "/dev/sda").
-            self.one_liner = Some(pgettext_f(
-                "one-liner-mdraid-no-name-running",
-                "{} — {}",
-                [
+            self.one_liner = Some(
+                // Translators: String used for one-liner description of running RAID array.
+                //              The first %s is the array name (e.g. "AlphaGo").
+                //              The second %s is the size and level (e.g. "2 TB RAID-5").
+                //              The third %s is the special device file (e.g. "/dev/sda").
+                OneLiner::new(
+                    "one-liner-mdraid-no-name-running",
                     self.description.as_deref().unwrap_or_default(),
-                    &preferred_device,
-                ],
-            ));
+                )
+                .secondary(preferred_device.as_str())
+                .build(),
+            );
         } else {
-            // Translators: String used for one-liner description of non-running RAID array.
-            //              The first %s is the array name (e.g. "AlphaGo").
-            //              The second %s is the size and level (e.g. "2 TB RAID-5").
-            self.one_liner = Some(pgettext_f(
-                "one-liner-mdraid-no-name-not-running",
-                "{}",
-                [self.description.as_deref().unwrap_or_default()],
-            ));
+            self.one_liner = Some(
+                // Translators: String used for one-liner description of non-running RAID array.
+                //              The first %s is the array name (e.g. "AlphaGo").
+                //              The second %s is the size and level (e.g. "2 TB RAID-5").
+                OneLiner::new(
+                    "one-liner-mdraid-no-name-not-running",
+                    self.description.as_deref().unwrap_or_default(),
+                )
+                .build(),
+            );
         }
 
         self.sort_key = Some(format!(
-            "01_mdraid_{}_{}",
+            "{}_{}_{}",
+            self.kind.sort_key_prefix(),
             mdraid.uuid().await.expect("Failed to get mdraid uuid"),
             //TODO: use async closure when stable
             partition_number.unwrap_or(0)
@@ -404,22 +706,54 @@ impl<'a> ObjectInfo<'a> {
     pub(crate) async fn info_for_drive(
         &mut self,
         client: &Client,
+        snapshot: &ObjectSnapshot,
         drive: &drive::DriveProxy<'_>,
         partition: Option<partition::PartitionProxy<'_>>,
     ) {
-        let vendor = drive.vendor().await.unwrap_or_default();
+        self.kind = DeviceKind::Drive;
+        // the properties are independent of each other, so fetch them concurrently
+        let (
+            vendor,
+            model,
+            media_removable,
+            media_available,
+            media,
+            media_compat,
+            size,
+            rotation_rate,
+            connection_bus,
+            revision,
+            sort_key,
+        ) = futures_util::join!(
+            drive.vendor(),
+            drive.model(),
+            drive.media_removable(),
+            drive.media_available(),
+            drive.media(),
+            drive.media_compatibility(),
+            drive.size(),
+            drive.rotation_rate(),
+            drive.connection_bus(),
+            drive.revision(),
+            drive.sort_key(),
+        );
+
+        let vendor = vendor.unwrap_or_default();
         // "%vendor $model"
         self.name = Some(format!(
             "{}{}{}",
             vendor,
             if vendor.is_empty() { "" } else { " " },
-            drive.model().await.unwrap_or_default()
+            model.unwrap_or_default()
         ));
 
-        let media_removable = drive.media_removable().await.unwrap_or_default();
-        let media_available = drive.media_available().await.unwrap_or_default();
-        let media = drive.media().await.unwrap();
-        let media_compat = drive.media_compatibility().await.unwrap_or_default();
+        let media_removable = media_removable.unwrap_or_default();
+        let media_available = media_available.unwrap_or_default();
+        self.has_removable_media = media_removable;
+        self.media_available = media_available;
+        // an unreadable media is treated like no media
+        let media = media.ok();
+        let media_compat = media_compat.unwrap_or_default();
 
         let mut desc = String::new();
         let mut desc_type = None;
@@ -440,7 +774,7 @@ impl<'a> ObjectInfo<'a> {
 
             if media_removable && media_available {
                 //media
-                if media == media_data.id {
+                if media == Some(media_data.id) {
                     if self.media_description.is_none() {
                         self.media_description = Some(match media_data.media_type {
                             media::DriveType::Drive => {
@@ -486,12 +820,10 @@ impl<'a> ObjectInfo<'a> {
             }
         }
 
-        let size = drive
-            .size()
-            .await
+        let size = size
             .ok()
             .map(|size| client.size_for_display(size, false, false));
-        let rotation_rate = drive.rotation_rate().await.unwrap_or_default();
+        let rotation_rate = rotation_rate.unwrap_or_default();
         self.description = Some(match desc_type {
             None => {
                 if media_removable {
@@ -541,9 +873,7 @@ impl<'a> ObjectInfo<'a> {
             }
         });
 
-        let hyphenated_connection_bus = drive
-            .connection_bus()
-            .await
+        let hyphenated_connection_bus = connection_bus
             .ok()
             .filter(|bus| !bus.is_empty())
             .map(|bus| format!("-{}", bus))
@@ -601,39 +931,28 @@ impl<'a> ObjectInfo<'a> {
                 .set_if_none(media_icon_fallback, media_icon_symbolic_fallback);
         }
 
-        //TODO: refactor
         //prepend a qualifier to the media description, based on the disc state
-        if drive.optical_blank().await.unwrap_or_default() {
+        let (optical_blank, num_audio_tracks, num_data_tracks) = futures_util::join!(
+            drive.optical_blank(),
+            drive.optical_num_audio_tracks(),
+            drive.optical_num_data_tracks(),
+        );
+        let has_audio_tracks = num_audio_tracks.is_ok_and(|tracks| tracks > 0);
+        if optical_blank.unwrap_or_default() {
             // Translators: String used for a blank disc. The %s is the disc type e.g. "CD-RW Disc"
             self.media_description = Some(pgettext_f(
                 "optical-media",
                 "Blank {}",
                 [self.media_description.as_deref().unwrap_or_default()],
             ));
-        } else if drive
-            .optical_num_audio_tracks()
-            .await
-            .is_ok_and(|tracks| tracks > 0)
-            && drive
-                .optical_num_data_tracks()
-                .await
-                .is_ok_and(|tracks| tracks > 0)
-        {
+        } else if has_audio_tracks && num_data_tracks.as_ref().is_ok_and(|tracks| *tracks > 0) {
             // Translators: String used for a mixed disc. The %s is the disc type e.g. "CD-ROM Disc"
             self.media_description = Some(pgettext_f(
                 "optical-media",
                 "Mixed {}",
                 [self.media_description.as_deref().unwrap_or_default()],
             ));
-        } else if drive
-            .optical_num_audio_tracks()
-            .await
-            .is_ok_and(|tracks| tracks > 0)
-            && drive
-                .optical_num_data_tracks()
-                .await
-                .is_ok_and(|tracks| tracks == 0)
-        {
+        } else if has_audio_tracks && num_data_tracks.is_ok_and(|tracks| tracks == 0) {
             // Translators: String used for an audio disc. The %s is the disc type e.g. "CD-ROM Disc"
             self.media_description = Some(pgettext_f(
                 "optical-media",
@@ -643,33 +962,46 @@ impl<'a> ObjectInfo<'a> {
         }
 
         // Apply UDISKS_NAME, UDISKS_ICON_NAME, UDISKS_SYMBOLIC_ICON_NAME hints, if available
-        let block = client.block_for_drive(drive, true).await;
+        let block = snapshot
+            .block_for_drive(&drive.inner().path().to_owned().into())
+            .await;
         if let Some(ref block) = block {
-            if let Ok(hint) = block.hint_name().await {
+            let (hint, hint_icon, hint_icon_symbolic) = futures_util::join!(
+                block.hint_name(),
+                block.hint_icon_name(),
+                block.hint_symbolic_icon_name(),
+            );
+            if let Ok(hint) = hint {
                 if !hint.is_empty() {
                     self.description = Some(hint.clone());
                     self.media_description = Some(hint);
+                    self.description_from_hint = true;
                 }
             }
-            if let Ok(hint_icon) = block.hint_icon_name().await {
+            if let Ok(hint_icon) = hint_icon {
                 if !hint_icon.is_empty() {
                     self.icon.name = Some(hint_icon.clone());
                     self.media_icon.name = Some(hint_icon);
+                    self.icon_from_hint = true;
                 }
             }
-            if let Ok(hint_icon_symbolic) = block.hint_symbolic_icon_name().await {
+            if let Ok(hint_icon_symbolic) = hint_icon_symbolic {
                 if !hint_icon_symbolic.is_empty() {
                     self.icon.name_symbolic = Some(hint_icon_symbolic.clone());
                     self.media_icon.name_symbolic = Some(hint_icon_symbolic);
+                    self.icon_from_hint = true;
                 }
             }
         }
 
         let mut block_for_partition = None;
         if let Some(ref partition) = partition {
-            // safe to unwrap as the table's object path does not need to be converted
-            let object = client.object(partition.inner().path().clone()).unwrap();
-            block_for_partition = object.block().await.ok();
+            block_for_partition = snapshot
+                .proxy(
+                    &partition.inner().path().to_owned().into(),
+                    snapshot::BLOCK_INTERFACE,
+                )
+                .await;
         }
         block_for_partition = block_for_partition.or_else(|| block.clone());
 
@@ -694,56 +1026,57 @@ impl<'a> ObjectInfo<'a> {
 
         //calculate and set one-liner
         if let Some(block) = block {
-            if let Ok(drive_revision) = drive.revision().await {
-                // Translators: String used for one-liner description of drive.
-                //  The first %s is the description of the object (e.g. "80 GB Disk" or "Partition 2 of 2 GB Thumb Drive").
-                //  The second %s is the name of the object (e.g. "INTEL SSDSA2MH080G1GC").
-                //  The third %s is the fw revision (e.g "45ABX21").
-                //  The fourth %s is the special device file (e.g. "/dev/sda").
-                self.one_liner = Some(pgettext_f(
-                    "one-liner-drive",
-                    "{} — {} [{}] ({})",
-                    [
+            if let Ok(drive_revision) = revision {
+                let preferred_device = block
+                    .preferred_device_path()
+                    .await
+                    .ok()
+                    .map(|dev| dev.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                self.one_liner = Some(
+                    // Translators: String used for one-liner description of drive.
+                    //  The first %s is the description of the object (e.g. "80 GB Disk" or "Partition 2 of 2 GB Thumb Drive").
+                    //  The second %s is the name of the object (e.g. "INTEL SSDSA2MH080G1GC").
+                    //  The third %s is the fw revision (e.g "45ABX21").
+                    //  The fourth %s is the special device file (e.g. "/dev/sda").
+                    OneLiner::new(
+                        "one-liner-drive",
                         self.description.as_deref().unwrap_or_default(),
-                        self.name.as_deref().unwrap_or_default(),
-                        &drive_revision,
-                        &block
-                            .preferred_device()
-                            .await
-                            .ok()
-                            .and_then(|dev| CString::from_vec_with_nul(dev).ok())
-                            .and_then(|dev| dev.to_str().map(|p| p.to_string()).ok())
-                            .unwrap_or_default(),
-                    ],
-                ));
+                    )
+                    .secondary(self.name.as_deref().unwrap_or_default())
+                    .revision(drive_revision.as_str())
+                    .device(preferred_device.as_str())
+                    .build(),
+                );
             } else {
-                // Translators: String used for one-liner description of drive w/o known fw revision.
-                //    The first %s is the description of the object (e.g. "80 GB Disk").
-                //    The second %s is the name of the object (e.g. "INTEL SSDSA2MH080G1GC").
-                //    The third %s is the special device file (e.g. "/dev/sda").
-                self.one_liner = Some(pgettext_f(
-                    "one-liner-drive",
-                    "{} — {} ({})",
-                    [
+                //safe to unwrap has been set before if it was none
+                let preferred_device = block_for_partition
+                    .unwrap()
+                    .preferred_device_path()
+                    .await
+                    .ok()
+                    .map(|dev| dev.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                self.one_liner = Some(
+                    // Translators: String used for one-liner description of drive w/o known fw revision.
+                    //    The first %s is the description of the object (e.g. "80 GB Disk").
+                    //    The second %s is the name of the object (e.g. "INTEL SSDSA2MH080G1GC").
+                    //    The third %s is the special device file (e.g. "/dev/sda").
+                    OneLiner::new(
+                        "one-liner-drive",
                         self.description.as_deref().unwrap_or_default(),
-                        self.name.as_deref().unwrap_or_default(),
-                        //safe to unwrap has been set before if it was none
-                        &block_for_partition
-                            .unwrap()
-                            .preferred_device()
-                            .await
-                            .ok()
-                            .and_then(|dev| CString::from_vec_with_nul(dev).ok())
-                            .and_then(|dev| dev.to_str().map(|p| p.to_string()).ok())
-                            .unwrap_or_default(),
-                    ],
-                ));
+                    )
+                    .secondary(self.name.as_deref().unwrap_or_default())
+                    .device(preferred_device.as_str())
+                    .build(),
+                );
             }
         }
 
         self.sort_key = Some(format!(
-            "00_drive_{}",
-            drive.sort_key().await.unwrap_or_default(),
+            "{}_{}",
+            self.kind.sort_key_prefix(),
+            sort_key.unwrap_or_default()
         ));
     }
 
@@ -36,6 +36,51 @@ pub enum PartitionFlags {
     NoAutoMount = 1 << 63,
 }
 
+/// Raw GPT partition attribute bits.
+///
+/// Bits 0–2 are defined for all partitions and modeled by [`PartitionFlags`].
+/// Bits 48–63 are specific to the partition type, their meaning depends on the type.
+/// The constants below are the bits defined by the
+/// [Discoverable Partitions Specification](https://uapi-group.org/specifications/specs/discoverable_partitions_specification/)
+/// for Linux partition types, and are used by e.g. `systemd`.
+///
+/// Use [`PartitionProxy::gpt_raw_attributes`] and [`PartitionProxy::set_gpt_raw_attributes`]
+/// to check and set them.
+pub mod gpt_attributes {
+    /// The partition should be grown to fill all available space on first boot.
+    pub const GROW_FS: u64 = 1 << 59;
+    /// The partition should be mounted read-only.
+    pub const READ_ONLY: u64 = 1 << 60;
+    /// The partition should not be mounted automatically.
+    pub const NO_AUTO: u64 = 1 << 63;
+}
+
+impl PartitionProxy<'_> {
+    /// Returns all bits of the [`PartitionProxy::flags`] property, including the ones
+    /// not modeled by [`PartitionFlags`], such as type-specific GPT attribute bits
+    /// (see [`gpt_attributes`]).
+    pub async fn gpt_raw_attributes(&self) -> error::Result<u64> {
+        Ok(self.inner().get_property::<u64>("Flags").await?)
+    }
+
+    /// Sets all bits of the [`PartitionProxy::flags`] property, including the ones
+    /// not modeled by [`PartitionFlags`].
+    ///
+    /// To change a single bit, read the current attributes using
+    /// [`PartitionProxy::gpt_raw_attributes`] first.
+    pub async fn set_gpt_raw_attributes(
+        &self,
+        attributes: u64,
+        options: std::collections::HashMap<&str, zbus::zvariant::Value<'_>>,
+    ) -> error::Result<()> {
+        Ok(self
+            .inner()
+            .call_method("SetFlags", &(attributes, options))
+            .await
+            .map(|_| ())?)
+    }
+}
+
 /// Generated code for the [`org.freedesktop.UDisks2.Partition`](https://storaged.org/doc/udisks2-api/latest/gdbus-org.freedesktop.UDisks2.Partition.html) D-Bus interface.
 #[proxy(
     interface = "org.freedesktop.UDisks2.Partition",
@@ -59,9 +59,21 @@ impl PartitionTypeInfo {
     }
 }
 
+/// Partition type GUID of an EFI System Partition (ESP) on `gpt` partition tables.
+pub const ESP_GUID: &str = "c12a7328-f81f-11d2-ba4b-00a0c93ec93b";
+
+/// Partition type of an EFI System Partition (ESP) on `dos` partition tables.
+pub const ESP_DOS_TYPE: &str = "0xef";
+
+/// Whether the partition type `ty` is the type of an EFI System Partition,
+/// either on a `gpt` or a `dos` partition table.
+pub(crate) fn is_esp_type(ty: &str) -> bool {
+    ty.eq_ignore_ascii_case(ESP_GUID) || ty.eq_ignore_ascii_case(ESP_DOS_TYPE)
+}
+
 /// Known [PartitionType]s.
 /// see http://en.wikipedia.org/wiki/GUID_Partition_Table
-pub(crate) const PARTITION_TYPES: [PartitionTypeInfo; 228] = [
+pub(crate) static PARTITION_TYPES: [PartitionTypeInfo; 228] = [
     // Not associated with any OS
     PartitionTypeInfo::new(
         "gpt",
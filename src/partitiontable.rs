@@ -10,9 +10,299 @@
 //! section of the zbus documentation.
 //!
 
-use zbus::proxy;
+use std::{collections::HashMap, convert::Infallible, fmt, str::FromStr, time::Duration};
 
-use crate::error;
+use enumflags2::BitFlags;
+use zbus::{proxy, zvariant::Value};
+
+use crate::{error, partition::PartitionFlags};
+
+/// Type of a partition table.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PartitionTableType {
+    /// Master Boot Record
+    Dos,
+    /// GUID Partition Table
+    Gpt,
+    /// Apple Partition Map
+    Apm,
+    /// An unknown partition table type, containing the raw type reported by the daemon.
+    Unknown(String),
+}
+
+impl PartitionTableType {
+    /// Returns the type as used by the daemon, e.g. `dos` or `gpt`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Dos => "dos",
+            Self::Gpt => "gpt",
+            Self::Apm => "apm",
+            Self::Unknown(ty) => ty,
+        }
+    }
+
+    /// Returns the space in bytes at the start and at the end of a partition table of this type
+    /// that is never used by partitions, for a device with the given logical block size.
+    ///
+    /// The start is aligned to 1 MiB and covers the headers at the start of the device.
+    /// For `gpt` partition tables, the end covers the backup header and partition entries.
+    ///
+    /// # Examples
+    /// ```
+    /// use udisks2::partitiontable::PartitionTableType;
+    ///
+    /// assert_eq!(PartitionTableType::Gpt.reserved_space(512), (1024 * 1024, 34 * 512));
+    /// assert_eq!(PartitionTableType::Gpt.reserved_space(4096), (1024 * 1024, 6 * 4096));
+    /// assert_eq!(PartitionTableType::Dos.reserved_space(4096), (1024 * 1024, 0));
+    /// ```
+    pub fn reserved_space(&self, logical_block_size: u64) -> (u64, u64) {
+        let logical_block_size = logical_block_size.max(1);
+        // the partition entries and a header, plus the protective MBR or the last unusable block
+        let gpt = GPT_ENTRIES_SIZE + 2 * logical_block_size;
+        let start = PARTITION_ALIGNMENT
+            .max(gpt)
+            .next_multiple_of(logical_block_size);
+        let end = match self {
+            Self::Gpt => gpt,
+            _ => 0,
+        };
+        (start, end)
+    }
+
+    /// Returns the [`PartitionFlags`] that apply to partitions of this table type.
+    ///
+    /// Returns `None` for table types without known flags.
+    pub fn supported_flags(&self) -> Option<BitFlags<PartitionFlags>> {
+        match self {
+            Self::Dos => Some(PartitionFlags::Bootable.into()),
+            Self::Gpt => Some(BitFlags::all() & !PartitionFlags::Bootable),
+            Self::Apm | Self::Unknown(_) => None,
+        }
+    }
+}
+
+impl From<&str> for PartitionTableType {
+    fn from(s: &str) -> Self {
+        match s {
+            "dos" => Self::Dos,
+            "gpt" => Self::Gpt,
+            "apm" => Self::Apm,
+            other => Self::Unknown(other.to_owned()),
+        }
+    }
+}
+
+impl FromStr for PartitionTableType {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from(s))
+    }
+}
+
+impl AsRef<str> for PartitionTableType {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for PartitionTableType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Specification of a partition to be created.
+///
+/// Used by [`crate::Client::create_partition`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartitionSpec {
+    offset: u64,
+    size: u64,
+    type_: String,
+    name: String,
+    partition_type: Option<String>,
+    timeout: Duration,
+    no_user_interaction: bool,
+}
+
+impl PartitionSpec {
+    /// Default time to wait for the created partition to appear.
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// Creates a new specification for a partition at `offset` with `size`, both in bytes.
+    ///
+    /// The actual partition may be slightly larger and start slightly later, due to alignment.
+    /// If `size` is 0, the partition takes up all the free space at `offset`.
+    pub fn new(offset: u64, size: u64) -> Self {
+        Self {
+            offset,
+            size,
+            type_: String::new(),
+            name: String::new(),
+            partition_type: None,
+            timeout: Self::DEFAULT_TIMEOUT,
+            no_user_interaction: false,
+        }
+    }
+
+    /// Partition type, e.g. `0x83` for `dos` or a GUID for `gpt` partition tables.
+    ///
+    /// If unset, a default type is chosen by the daemon.
+    pub fn type_(mut self, type_: impl Into<String>) -> Self {
+        self.type_ = type_.into();
+        self
+    }
+
+    /// Name of the partition.
+    ///
+    /// Must be blank for partition tables that do not support names.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// The kind of partition to create on `dos` partition tables,
+    /// either `primary`, `extended` or `logical`.
+    pub fn partition_type(mut self, partition_type: impl Into<String>) -> Self {
+        self.partition_type = Some(partition_type.into());
+        self
+    }
+
+    /// Time to wait for the created partition to appear.
+    ///
+    /// Defaults to [`Self::DEFAULT_TIMEOUT`].
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Whether no user interaction should happen, when checking if the operation is authorized.
+    ///
+    /// See [`crate::standard_options`].
+    pub fn no_user_interaction(mut self, no_user_interaction: bool) -> Self {
+        self.no_user_interaction = no_user_interaction;
+        self
+    }
+
+    /// Start of the partition in bytes.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Size of the partition in bytes.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub(crate) fn partition_type_str(&self) -> &str {
+        &self.type_
+    }
+
+    pub(crate) fn name_str(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn is_logical(&self) -> bool {
+        self.partition_type.as_deref() == Some("logical")
+    }
+
+    pub(crate) fn wait_timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    pub(crate) fn options(&self) -> HashMap<&'static str, Value<'static>> {
+        let mut map = crate::standard_options(self.no_user_interaction);
+        if let Some(partition_type) = &self.partition_type {
+            map.insert("partition-type", partition_type.clone().into());
+        }
+        map
+    }
+}
+
+/// Alignment of partitions, so the first MiB is never used by partitions.
+const PARTITION_ALIGNMENT: u64 = 1024 * 1024;
+/// Size of the partition entries of a `gpt` partition table, i.e. 128 entries of 128 bytes.
+const GPT_ENTRIES_SIZE: u64 = 128 * 128;
+/// Logical block size used if the actual size of the device is unknown.
+pub(crate) const DEFAULT_LOGICAL_BLOCK_SIZE: u64 = 512;
+
+/// A contiguous region of unallocated space in a partition table.
+///
+/// Returned by [`crate::Client::free_regions`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FreeRegion {
+    /// Start of the region in bytes.
+    pub offset: u64,
+    /// Size of the region in bytes.
+    pub size: u64,
+    /// Whether the region is located within an extended partition of a `dos` partition table,
+    /// i.e. only `logical` partitions can be created in it.
+    pub contained: bool,
+}
+
+impl FreeRegion {
+    /// End of the region in bytes (exclusive).
+    pub fn end(&self) -> u64 {
+        self.offset.saturating_add(self.size)
+    }
+
+    /// Whether a partition at `offset` with `size` fits into this region.
+    ///
+    /// A `size` of 0 means the partition takes up all the free space at `offset`,
+    /// so only the offset has to lie within the region.
+    pub fn contains(&self, offset: u64, size: u64) -> bool {
+        if offset < self.offset || offset >= self.end() {
+            return false;
+        }
+        size == 0 || offset.saturating_add(size) <= self.end()
+    }
+}
+
+/// Computes the unallocated regions between `start` and `end` not covered by `occupied`,
+/// which contains the offset and size of each partition.
+pub(crate) fn free_regions(
+    start: u64,
+    end: u64,
+    mut occupied: Vec<(u64, u64)>,
+    contained: bool,
+) -> Vec<FreeRegion> {
+    if start >= end {
+        return Vec::new();
+    }
+    occupied.sort_unstable();
+
+    let mut regions = Vec::new();
+    let mut cursor = start;
+    for (offset, size) in occupied {
+        if offset > cursor {
+            regions.push(FreeRegion {
+                offset: cursor,
+                size: offset.min(end) - cursor,
+                contained,
+            });
+        }
+        cursor = cursor.max(offset.saturating_add(size));
+        if cursor >= end {
+            return regions;
+        }
+    }
+    if end > cursor {
+        regions.push(FreeRegion {
+            offset: cursor,
+            size: end - cursor,
+            contained,
+        });
+    }
+    regions
+}
+
+impl PartitionTableProxy<'_> {
+    /// Returns the [`Self::type_`] of the partition table as a [`PartitionTableType`].
+    pub async fn table_type(&self) -> error::Result<PartitionTableType> {
+        Ok(PartitionTableType::from(self.type_().await?.as_str()))
+    }
+}
 
 #[proxy(
     interface = "org.freedesktop.UDisks2.PartitionTable",
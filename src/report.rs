@@ -0,0 +1,21 @@
+use std::path::PathBuf;
+
+/// Summary of a block device containing a filesystem.
+///
+/// Returned by [`crate::Client::storage_report`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StorageEntry {
+    /// Preferred device file of the block device, e.g. `/dev/sda1`.
+    pub device: PathBuf,
+    /// Type of the filesystem, e.g. `ext4`.
+    pub filesystem_type: String,
+    /// Size of the block device in bytes.
+    pub size: u64,
+    /// Mount points of the filesystem, empty if it is not mounted.
+    pub mount_points: Vec<PathBuf>,
+    /// Size of the filesystem in bytes.
+    ///
+    /// This is only known for mounted ext2/3/4 and XFS filesystems, see
+    /// [`crate::filesystem::FilesystemProxy::size`].
+    pub filesystem_size: Option<u64>,
+}
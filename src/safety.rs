@@ -0,0 +1,55 @@
+/// Reason why a destructive operation (such as [`crate::block::BlockProxy::format`] or
+/// [`crate::partition::PartitionProxy::delete`]) on a block device may be dangerous.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SafetyConcern {
+    /// The device is considered a system device.
+    ///
+    /// See [`crate::block::BlockProxy::hint_system`].
+    System,
+    /// The device can only be read from.
+    ///
+    /// See [`crate::block::BlockProxy::read_only`].
+    ReadOnly,
+    /// The device, or one of its partitions, has a mounted filesystem.
+    Mounted,
+    /// The device, or one of its partitions, is an active swap device.
+    SwapActive,
+    /// The device is a member of a running MD-RAID array.
+    RaidMember,
+    /// The device is an unlocked encrypted device, i.e. it has a cleartext device.
+    Unlocked,
+}
+
+/// Report about whether it is safe to perform destructive operations on a block device.
+///
+/// Returned by [`crate::Client::is_destructive_safe`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SafetyReport {
+    concerns: Vec<SafetyConcern>,
+}
+
+impl SafetyReport {
+    pub(crate) fn push(&mut self, concern: SafetyConcern) {
+        if !self.concerns.contains(&concern) {
+            self.concerns.push(concern);
+        }
+    }
+
+    /// Whether no concerns were found.
+    ///
+    /// Note that this is only a guess, it is still the caller's responsibility to ensure
+    /// that the device is ready for destructive operations.
+    pub fn is_safe(&self) -> bool {
+        self.concerns.is_empty()
+    }
+
+    /// Whether the report contains the given concern.
+    pub fn contains(&self, concern: SafetyConcern) -> bool {
+        self.concerns.contains(&concern)
+    }
+
+    /// All concerns found for the device.
+    pub fn concerns(&self) -> &[SafetyConcern] {
+        &self.concerns
+    }
+}
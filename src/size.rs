@@ -0,0 +1,134 @@
+use std::{fmt, str::FromStr};
+
+/// A size in bytes.
+///
+/// Use [`crate::Client::size_for_display`] to get a human-readable representation.
+/// Human-readable sizes can be parsed using [`str::parse`], see [`Size::from_str`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Size(u64);
+
+impl Size {
+    /// Creates a new size from the given number of bytes.
+    pub const fn from_bytes(bytes: u64) -> Self {
+        Self(bytes)
+    }
+
+    /// Number of bytes.
+    pub const fn bytes(&self) -> u64 {
+        self.0
+    }
+}
+
+impl From<u64> for Size {
+    fn from(bytes: u64) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<Size> for u64 {
+    fn from(size: Size) -> Self {
+        size.0
+    }
+}
+
+/// Error returned when parsing a [`Size`] fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SizeParseError {
+    /// The string is empty.
+    Empty,
+    /// The numeric part of the string is missing or not a valid number.
+    InvalidNumber,
+    /// The unit is not known.
+    UnknownUnit(String),
+    /// The size does not fit into a `u64`.
+    Overflow,
+}
+
+impl std::error::Error for SizeParseError {}
+
+impl fmt::Display for SizeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "The size is empty."),
+            Self::InvalidNumber => write!(f, "The size is not a valid number."),
+            Self::UnknownUnit(unit) => write!(f, "Unknown size unit `{}`.", unit),
+            Self::Overflow => write!(f, "The size is too large."),
+        }
+    }
+}
+
+/// Returns the number of bytes of the given (lowercase) unit.
+fn unit_factor(unit: &str) -> Option<u64> {
+    const KILO: u64 = 1000;
+    const KIBI: u64 = 1024;
+
+    Some(match unit {
+        "" | "b" | "byte" | "bytes" => 1,
+        "k" | "kb" => KILO,
+        "m" | "mb" => KILO.pow(2),
+        "g" | "gb" => KILO.pow(3),
+        "t" | "tb" => KILO.pow(4),
+        "p" | "pb" => KILO.pow(5),
+        "ki" | "kib" => KIBI,
+        "mi" | "mib" => KIBI.pow(2),
+        "gi" | "gib" => KIBI.pow(3),
+        "ti" | "tib" => KIBI.pow(4),
+        "pi" | "pib" => KIBI.pow(5),
+        _ => return None,
+    })
+}
+
+impl FromStr for Size {
+    type Err = SizeParseError;
+
+    /// Parses a human-readable size, such as `512MiB`, `10 GB` or `1.5T`.
+    ///
+    /// Both power-of-ten (`kB`, `MB`, `GB`, `TB`, `PB`) and power-of-two
+    /// (`KiB`, `MiB`, `GiB`, `TiB`, `PiB`) units are supported, as well as plain byte counts.
+    /// Units are case-insensitive and may be separated from the number by whitespace.
+    /// Single-letter units (`k`, `M`, `G`, `T`, `P`) are power-of-ten units, like in the output
+    /// of [`crate::Client::size_for_display`]. Fractional values are rounded to the nearest byte.
+    ///
+    /// # Examples
+    /// ```
+    /// use udisks2::Size;
+    ///
+    /// assert_eq!("4.0 GiB".parse(), Ok(Size::from_bytes(4 * 1024 * 1024 * 1024)));
+    /// assert_eq!("500 KB".parse(), Ok(Size::from_bytes(500_000)));
+    /// assert_eq!("1.5T".parse(), Ok(Size::from_bytes(1_500_000_000_000)));
+    /// assert_eq!("512mib".parse(), Ok(Size::from_bytes(512 * 1024 * 1024)));
+    /// assert_eq!("4096".parse(), Ok(Size::from_bytes(4096)));
+    /// assert!("10 XB".parse::<Size>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(SizeParseError::Empty);
+        }
+
+        let split = s
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(s.len());
+        let (number, unit) = s.split_at(split);
+        let unit = unit.trim();
+        if number.is_empty() {
+            return Err(SizeParseError::InvalidNumber);
+        }
+        let factor = unit_factor(&unit.to_lowercase())
+            .ok_or_else(|| SizeParseError::UnknownUnit(unit.to_owned()))?;
+
+        if let Ok(number) = number.parse::<u64>() {
+            return number
+                .checked_mul(factor)
+                .map(Self)
+                .ok_or(SizeParseError::Overflow);
+        }
+
+        let number: f64 = number.parse().map_err(|_| SizeParseError::InvalidNumber)?;
+        let bytes = (number * factor as f64).round();
+        if bytes >= u64::MAX as f64 {
+            return Err(SizeParseError::Overflow);
+        }
+        Ok(Self(bytes as u64))
+    }
+}
@@ -0,0 +1,150 @@
+use std::time::{Duration, SystemTime};
+
+use crate::{ata, nvme};
+
+/// Summary of the SMART health data of a drive, independent of the interface of the drive.
+///
+/// Returned by [`crate::Client::smart_for_path`] and [`crate::Client::smart_status`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SmartSummary {
+    /// Whether the drive reports an imminent failure.
+    ///
+    /// For ATA drives this is [`ata::AtaProxy::smart_failing`],
+    /// for NVMe drives whether any critical warning is set.
+    pub failing: bool,
+    /// Temperature of the drive in Kelvin, if known.
+    pub temperature: Option<f64>,
+    /// Time the drive has been powered on, if known.
+    pub power_on: Option<Duration>,
+    /// Result of the last self-test.
+    pub self_test: SelfTestResult,
+    /// Point in time the data was last updated, as seconds since the Unix epoch,
+    /// or 0 if the data has never been collected.
+    pub updated: u64,
+}
+
+impl SmartSummary {
+    /// Returns the temperature of the drive in degrees Celsius, if known.
+    ///
+    /// # Examples
+    /// ```
+    /// # use udisks2::{SelfTestResult, SmartSummary};
+    /// let summary = SmartSummary {
+    ///     failing: false,
+    ///     temperature: Some(309.15),
+    ///     power_on: None,
+    ///     self_test: SelfTestResult::Unknown,
+    ///     updated: 0,
+    /// };
+    /// assert_eq!(summary.temperature_celsius().map(f64::round), Some(36.0));
+    /// assert_eq!(summary.updated_time(), None);
+    /// ```
+    pub fn temperature_celsius(&self) -> Option<f64> {
+        self.temperature.map(|kelvin| kelvin - 273.15)
+    }
+
+    /// Returns the point in time the data was last updated, or [`None`] if the data has
+    /// never been collected.
+    pub fn updated_time(&self) -> Option<SystemTime> {
+        crate::system_time_from_secs(self.updated)
+    }
+
+    pub(crate) async fn from_ata(ata: &ata::AtaProxy<'_>) -> crate::error::Result<Self> {
+        let temperature = ata.smart_temperature().await?;
+        let power_on = ata.smart_power_on_seconds().await?;
+        Ok(Self {
+            failing: ata.smart_failing().await?,
+            temperature: (temperature > 0.0).then_some(temperature),
+            power_on: (power_on > 0).then(|| Duration::from_secs(power_on)),
+            self_test: ata.smart_selftest_status().await?.into(),
+            updated: ata.smart_updated().await?,
+        })
+    }
+
+    pub(crate) async fn from_nvme(
+        controller: &nvme::controller::ControllerProxy<'_>,
+    ) -> crate::error::Result<Self> {
+        let temperature = controller.smart_temperature().await?;
+        let power_on_hours = controller.smart_power_on_hours().await?;
+        Ok(Self {
+            failing: !controller.smart_critical_warning().await?.is_empty(),
+            temperature: (temperature > 0).then_some(f64::from(temperature)),
+            power_on: (power_on_hours > 0)
+                .then(|| Duration::from_secs(power_on_hours.saturating_mul(60 * 60))),
+            self_test: controller.smart_selftest_status().await?.into(),
+            updated: controller.smart_updated().await?,
+        })
+    }
+}
+
+/// Result of a SMART self-test, independent of the interface of the drive.
+///
+/// Can be created from both [`ata::SelfTestStatus`] and
+/// [`nvme::controller::SmartSelftestStatus`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SelfTestResult {
+    /// The self-test completed successfully.
+    Success,
+    /// The self-test was aborted or interrupted before it completed.
+    Aborted,
+    /// The self-test failed.
+    Failed,
+    /// A self-test is currently in progress.
+    InProgress,
+    /// The status is unknown, e.g. because no self-test was run.
+    Unknown,
+}
+
+impl SelfTestResult {
+    /// Whether a self-test is currently running.
+    pub fn is_in_progress(&self) -> bool {
+        matches!(self, Self::InProgress)
+    }
+
+    /// Whether the self-test failed.
+    ///
+    /// Aborted self-tests are not considered a failure.
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Self::Failed)
+    }
+}
+
+impl From<ata::SelfTestStatus> for SelfTestResult {
+    fn from(status: ata::SelfTestStatus) -> Self {
+        use ata::SelfTestStatus;
+
+        match status {
+            SelfTestStatus::Success => Self::Success,
+            SelfTestStatus::Aborted | SelfTestStatus::Interrupted => Self::Aborted,
+            SelfTestStatus::Fatal
+            | SelfTestStatus::ErrorUnknown
+            | SelfTestStatus::ErrorElectrical
+            | SelfTestStatus::ErrorServo
+            | SelfTestStatus::ErrorRead
+            | SelfTestStatus::ErrorHandling => Self::Failed,
+            SelfTestStatus::InProgress => Self::InProgress,
+            SelfTestStatus::Unknown => Self::Unknown,
+        }
+    }
+}
+
+impl From<nvme::controller::SmartSelftestStatus> for SelfTestResult {
+    fn from(status: nvme::controller::SmartSelftestStatus) -> Self {
+        use nvme::controller::SmartSelftestStatus;
+
+        match status {
+            SmartSelftestStatus::Success => Self::Success,
+            SmartSelftestStatus::Aborted
+            | SmartSelftestStatus::CtrlReset
+            | SmartSelftestStatus::NsRemoved
+            | SmartSelftestStatus::AbortedFormat
+            | SmartSelftestStatus::AbortedUnknown
+            | SmartSelftestStatus::AbortedSanitize => Self::Aborted,
+            SmartSelftestStatus::FatalError
+            | SmartSelftestStatus::UnknownSegFail
+            | SmartSelftestStatus::KnownSegFail => Self::Failed,
+            SmartSelftestStatus::InProgress => Self::InProgress,
+            SmartSelftestStatus::Unknown => Self::Unknown,
+        }
+    }
+}
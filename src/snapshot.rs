@@ -0,0 +1,134 @@
+use zbus::{
+    fdo::ManagedObjects,
+    proxy::{Builder, Defaults},
+    zvariant::{OwnedObjectPath, OwnedValue},
+};
+
+use crate::block;
+
+pub(crate) const BLOCK_INTERFACE: &str = "org.freedesktop.UDisks2.Block";
+pub(crate) const DRIVE_INTERFACE: &str = "org.freedesktop.UDisks2.Drive";
+pub(crate) const FILESYSTEM_INTERFACE: &str = "org.freedesktop.UDisks2.Filesystem";
+pub(crate) const LOOP_INTERFACE: &str = "org.freedesktop.UDisks2.Loop";
+pub(crate) const MDRAID_INTERFACE: &str = "org.freedesktop.UDisks2.MDRaid";
+pub(crate) const PARTITION_INTERFACE: &str = "org.freedesktop.UDisks2.Partition";
+
+/// Snapshot of all objects, their interfaces and properties exported by the daemon.
+///
+/// Used to answer lookups for many objects with a single call to
+/// [`zbus::fdo::ObjectManagerProxy::get_managed_objects`].
+pub(crate) struct ObjectSnapshot {
+    objects: ManagedObjects,
+    connection: zbus::Connection,
+}
+
+impl ObjectSnapshot {
+    pub(crate) fn new(objects: ManagedObjects, connection: zbus::Connection) -> Self {
+        Self {
+            objects,
+            connection,
+        }
+    }
+
+    /// Returns the object paths of all objects.
+    pub(crate) fn object_paths(&self) -> impl Iterator<Item = &OwnedObjectPath> {
+        self.objects.keys()
+    }
+
+    /// Whether the object at `object_path` implements `interface`.
+    pub(crate) fn has_interface(&self, object_path: &OwnedObjectPath, interface: &str) -> bool {
+        self.objects
+            .get(object_path)
+            .is_some_and(|interfaces| interfaces.contains_key(interface))
+    }
+
+    /// Returns the value of the given object path property, if it is not `/`.
+    pub(crate) fn object_path_property(
+        &self,
+        object_path: &OwnedObjectPath,
+        interface: &str,
+        property: &str,
+    ) -> Option<OwnedObjectPath> {
+        self.property(object_path, interface, property)
+            .and_then(|value| OwnedObjectPath::try_from(value.try_clone().ok()?).ok())
+            .filter(|path| path.as_str() != "/")
+    }
+
+    /// Returns the number of the partition at `object_path`, if it is a partition.
+    pub(crate) fn partition_number(&self, object_path: &OwnedObjectPath) -> Option<u32> {
+        self.property(object_path, PARTITION_INTERFACE, "Number")
+            .and_then(|value| u32::try_from(value).ok())
+    }
+
+    /// Returns the objects whose object path `property` of `interface` is set to `target`.
+    pub(crate) fn objects_with<'a>(
+        &'a self,
+        interface: &'a str,
+        property: &'a str,
+        target: &'a OwnedObjectPath,
+    ) -> impl Iterator<Item = &'a OwnedObjectPath> + 'a {
+        self.objects.keys().filter(move |object_path| {
+            self.object_path_property(object_path, interface, property)
+                .as_ref()
+                == Some(target)
+        })
+    }
+
+    fn property(
+        &self,
+        object_path: &OwnedObjectPath,
+        interface: &str,
+        property: &str,
+    ) -> Option<&OwnedValue> {
+        self.objects.get(object_path)?.get(interface)?.get(property)
+    }
+
+    /// Creates the proxy for `object_path`, if the object implements `interface`.
+    pub(crate) async fn proxy<P>(&self, object_path: &OwnedObjectPath, interface: &str) -> Option<P>
+    where
+        P: From<zbus::Proxy<'static>> + Defaults,
+    {
+        if !self.has_interface(object_path, interface) {
+            return None;
+        }
+        Builder::<P>::new(&self.connection)
+            .path(object_path.clone())
+            .ok()?
+            .build()
+            .await
+            .ok()
+    }
+
+    /// Returns the whole-disk block device of the given drive.
+    pub(crate) async fn block_for_drive(
+        &self,
+        drive_path: &OwnedObjectPath,
+    ) -> Option<block::BlockProxy<'static>> {
+        let block_path = self.top_level_block_with(drive_path, "Drive")?;
+        self.proxy(block_path, BLOCK_INTERFACE).await
+    }
+
+    /// Returns the block device of the given running RAID array.
+    pub(crate) async fn block_for_mdraid(
+        &self,
+        mdraid_path: &OwnedObjectPath,
+    ) -> Option<block::BlockProxy<'static>> {
+        let block_path = self.top_level_block_with(mdraid_path, "MDRaid")?;
+        self.proxy(block_path, BLOCK_INTERFACE).await
+    }
+
+    /// Returns a block device, which is not a partition and whose `property` is set to `target`.
+    fn top_level_block_with(
+        &self,
+        target: &OwnedObjectPath,
+        property: &str,
+    ) -> Option<&OwnedObjectPath> {
+        self.objects.keys().find(|object_path| {
+            !self.has_interface(object_path, PARTITION_INTERFACE)
+                && self
+                    .object_path_property(object_path, BLOCK_INTERFACE, property)
+                    .as_ref()
+                    == Some(target)
+        })
+    }
+}
@@ -0,0 +1,1832 @@
+//! Tests of [`udisks2::Client`] against the mock daemon in [`common`].
+//!
+//! Run with `cargo test --features integration-tests`.
+#![cfg(feature = "integration-tests")]
+
+mod common;
+
+use std::{collections::HashMap, path::Path, time::Duration};
+
+use common::{fixtures, MockAta, MockBlock, MockDaemon, MockEncrypted, MockMDRaid};
+use futures_util::StreamExt;
+use udisks2::block::{
+    ConfigurationItem, CrypttabEntry, DeviceRole, FstabEntry, MountAtBootOptions,
+};
+use udisks2::drive::MediaCompatibility;
+use udisks2::encrypted::UnlockOptions;
+use udisks2::filesystem::MountOptions;
+use udisks2::manager::DevSpec;
+use udisks2::partitiontable::PartitionTableType;
+use udisks2::{DeviceEvent, DeviceKind, SafetyConcern, SelfTestResult};
+use zbus::zvariant::OwnedValue;
+
+#[tokio::test]
+async fn drive_for_block() {
+    let daemon = MockDaemon::new().await;
+    let block = daemon
+        .client
+        .object(fixtures::PARTITION)
+        .unwrap()
+        .block()
+        .await
+        .unwrap();
+
+    let drive = daemon.client.drive_for_block(&block).await.unwrap();
+    assert_eq!(drive.inner().path().as_str(), fixtures::DRIVE);
+    assert_eq!(drive.model().await.unwrap(), "Drive");
+}
+
+#[tokio::test]
+async fn drive_for_block_without_drive() {
+    let daemon = MockDaemon::new().await;
+    let block = daemon
+        .client
+        .object(fixtures::LOOP)
+        .unwrap()
+        .block()
+        .await
+        .unwrap();
+
+    assert!(daemon.client.drive_for_block(&block).await.is_err());
+}
+
+#[tokio::test]
+async fn mdraid_for_block_without_array() {
+    let daemon = MockDaemon::new().await;
+    let block = daemon
+        .client
+        .object(fixtures::DISK)
+        .unwrap()
+        .block()
+        .await
+        .unwrap();
+
+    assert!(daemon.client.mdraid_for_block(&block).await.is_err());
+}
+
+#[tokio::test]
+async fn block_for_drive() {
+    let daemon = MockDaemon::new().await;
+    let drive = daemon
+        .client
+        .object(fixtures::DRIVE)
+        .unwrap()
+        .drive()
+        .await
+        .unwrap();
+
+    let block = daemon.client.block_for_drive(&drive, false).await.unwrap();
+    assert_eq!(block.inner().path().as_str(), fixtures::DISK);
+}
+
+#[tokio::test]
+async fn block_for_drive_multipath() {
+    let daemon = MockDaemon::new().await;
+    // a second path to the disk and the device-mapper device combining both paths
+    let path = "/org/freedesktop/UDisks2/block_devices/sdb";
+    let multipath = "/org/freedesktop/UDisks2/block_devices/dm_2d0";
+    daemon
+        .add(
+            path,
+            common::MockBlock {
+                device: common::bytestring("/dev/sdb"),
+                ..fixtures::disk_block()
+            },
+        )
+        .await;
+    daemon
+        .add(
+            multipath,
+            common::MockBlock {
+                device: common::bytestring("/dev/dm-0"),
+                ..fixtures::disk_block()
+            },
+        )
+        .await;
+    let drive = daemon
+        .client
+        .object(fixtures::DRIVE)
+        .unwrap()
+        .drive()
+        .await
+        .unwrap();
+    let block_path = |physical| {
+        let client = &daemon.client;
+        let drive = &drive;
+        async move {
+            client
+                .block_for_drive(drive, physical)
+                .await
+                .unwrap()
+                .inner()
+                .path()
+                .to_string()
+        }
+    };
+
+    assert_eq!(block_path(false).await, multipath);
+    assert_ne!(block_path(true).await, multipath);
+}
+
+#[tokio::test]
+async fn drives() {
+    let daemon = MockDaemon::new().await;
+
+    let drives = daemon.client.drives().await;
+    assert_eq!(drives.len(), 1);
+    assert_eq!(drives[0].inner().path().as_str(), fixtures::DRIVE);
+}
+
+#[tokio::test]
+async fn objects() {
+    let daemon = MockDaemon::new().await;
+
+    let objects = daemon.client.objects().await.unwrap();
+    for path in [
+        fixtures::DRIVE,
+        fixtures::DISK,
+        fixtures::PARTITION,
+        fixtures::LOOP,
+    ] {
+        assert!(objects
+            .iter()
+            .any(|object| object.object_path().as_str() == path));
+    }
+}
+
+#[tokio::test]
+async fn partitions() {
+    let daemon = MockDaemon::new().await;
+    let table = daemon
+        .client
+        .object(fixtures::DISK)
+        .unwrap()
+        .partition_table()
+        .await
+        .unwrap();
+
+    let partitions = daemon.client.partitions(&table).await;
+    assert_eq!(partitions.len(), 1);
+    assert_eq!(partitions[0].inner().path().as_str(), fixtures::PARTITION);
+    assert_eq!(partitions[0].number().await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn loop_for_block() {
+    let daemon = MockDaemon::new().await;
+    let block = daemon
+        .client
+        .object(fixtures::LOOP)
+        .unwrap()
+        .block()
+        .await
+        .unwrap();
+
+    let loop_device = daemon.client.loop_for_block(&block).await.unwrap();
+    assert_eq!(
+        loop_device.backing_file().await.unwrap(),
+        common::bytestring("/tmp/image.iso")
+    );
+}
+
+#[tokio::test]
+async fn holders_and_slaves() {
+    let daemon = MockDaemon::new().await;
+    let disk = daemon
+        .client
+        .object(fixtures::DISK)
+        .unwrap()
+        .block()
+        .await
+        .unwrap();
+    let partition = daemon
+        .client
+        .object(fixtures::PARTITION)
+        .unwrap()
+        .block()
+        .await
+        .unwrap();
+
+    let holders = daemon.client.holders(&disk).await;
+    assert_eq!(holders.len(), 1);
+    assert_eq!(holders[0].object_path().as_str(), fixtures::PARTITION);
+
+    let slaves = daemon.client.slaves(&partition).await;
+    assert_eq!(slaves.len(), 1);
+    assert_eq!(slaves[0].object_path().as_str(), fixtures::DISK);
+}
+
+#[tokio::test]
+async fn storage_report() {
+    let daemon = MockDaemon::new().await;
+
+    let report = daemon.client.storage_report().await;
+    assert_eq!(report.len(), 1);
+    assert_eq!(report[0].device.to_str(), Some("/dev/sda1"));
+    assert_eq!(report[0].filesystem_type, "ext4");
+    assert!(report[0].mount_points.is_empty());
+    assert_eq!(report[0].filesystem_size, None);
+}
+
+#[tokio::test]
+async fn unique_drives() {
+    let daemon = MockDaemon::new().await;
+    let duplicate = "/org/freedesktop/UDisks2/drives/Mock_Drive_5678";
+    let sibling = "/org/freedesktop/UDisks2/drives/Mock_Drive_9012";
+    daemon
+        .replace(
+            fixtures::DRIVE,
+            common::MockDrive {
+                wwn: "0x5000c500a1b2c3d4".to_owned(),
+                sibling_id: "/sys/devices/mock".to_owned(),
+                ..fixtures::drive()
+            },
+        )
+        .await;
+    daemon
+        .add(
+            duplicate,
+            common::MockDrive {
+                wwn: "0x5000c500a1b2c3d4".to_owned(),
+                sort_key: "01hotplug/2".to_owned(),
+                ..fixtures::drive()
+            },
+        )
+        .await;
+    // siblings are separate drives, e.g. the slots of a card reader
+    daemon
+        .add(
+            sibling,
+            common::MockDrive {
+                sibling_id: "/sys/devices/mock".to_owned(),
+                sort_key: "01hotplug/3".to_owned(),
+                ..fixtures::drive()
+            },
+        )
+        .await;
+
+    assert_eq!(daemon.client.drives().await.len(), 3);
+    let drives = daemon.client.unique_drives().await;
+    assert_eq!(drives.len(), 2);
+    assert_eq!(drives[0].inner().path().as_str(), fixtures::DRIVE);
+    assert_eq!(drives[1].inner().path().as_str(), sibling);
+}
+
+#[tokio::test]
+async fn drive_siblings() {
+    let daemon = MockDaemon::new().await;
+    let sibling = "/org/freedesktop/UDisks2/drives/Mock_Drive_5678";
+    daemon
+        .replace(
+            fixtures::DRIVE,
+            common::MockDrive {
+                sibling_id: "/sys/devices/mock".to_owned(),
+                ..fixtures::drive()
+            },
+        )
+        .await;
+    daemon
+        .add(
+            sibling,
+            common::MockDrive {
+                sibling_id: "/sys/devices/mock".to_owned(),
+                ..fixtures::drive()
+            },
+        )
+        .await;
+    let object = daemon.client.object(fixtures::DRIVE).unwrap();
+    let drive = object.drive().await.unwrap();
+
+    let siblings = daemon.client.drive_siblings(&drive).await;
+    assert_eq!(siblings.len(), 1);
+    assert_eq!(siblings[0].inner().path().as_str(), sibling);
+    assert!(object.path_eq(&drive));
+    assert!(!object.path_eq(&siblings[0]));
+    assert_eq!(object, daemon.client.object(fixtures::DRIVE).unwrap());
+}
+
+#[tokio::test]
+async fn untranslated_strings() {
+    let daemon = MockDaemon::empty().await;
+    let client = &daemon.client;
+
+    assert_eq!(
+        client.id_for_display("filesystem", "vfat", "FAT32", true),
+        "FAT (32-bit version)"
+    );
+    assert_eq!(client.size_for_display(0, false, false), "0 bytes");
+    assert_eq!(client.size_for_display(1, true, false), "1 byte");
+    assert_eq!(client.size_for_display(999, false, true), "999 bytes");
+    assert_eq!(client.size_for_display(1000, false, false), "1.0 KB");
+    assert_eq!(client.size_for_display(512, true, false), "512 bytes");
+}
+
+#[tokio::test]
+async fn device_role() {
+    let daemon = MockDaemon::new().await;
+    let role = |path| {
+        let client = &daemon.client;
+        async move {
+            let block = client.object(path).unwrap().block().await.unwrap();
+            client.device_role(&block).await.unwrap()
+        }
+    };
+
+    assert_eq!(role(fixtures::DISK).await, DeviceRole::PartitionTable);
+    assert_eq!(role(fixtures::PARTITION).await, DeviceRole::Filesystem);
+    // the loop device has no Filesystem interface, but its usage is set
+    assert_eq!(role(fixtures::LOOP).await, DeviceRole::Filesystem);
+
+    daemon
+        .replace(
+            fixtures::LOOP,
+            common::MockBlock {
+                id_usage: String::new(),
+                id_type: String::new(),
+                ..fixtures::loop_block()
+            },
+        )
+        .await;
+    assert_eq!(role(fixtures::LOOP).await, DeviceRole::Empty);
+
+    daemon
+        .replace(
+            fixtures::LOOP,
+            common::MockBlock {
+                id_usage: "raid".to_owned(),
+                id_type: "linux_raid_member".to_owned(),
+                ..fixtures::loop_block()
+            },
+        )
+        .await;
+    assert_eq!(role(fixtures::LOOP).await, DeviceRole::RaidMember);
+
+    // LVM physical volumes share the `raid` usage, but are not RAID members
+    daemon
+        .replace(
+            fixtures::LOOP,
+            common::MockBlock {
+                id_usage: "raid".to_owned(),
+                id_type: "LVM2_member".to_owned(),
+                ..fixtures::loop_block()
+            },
+        )
+        .await;
+    assert!(matches!(
+        role(fixtures::LOOP).await,
+        DeviceRole::Other { ty, .. } if ty == "LVM2_member"
+    ));
+}
+
+#[tokio::test]
+async fn object_info_parent() {
+    let daemon = MockDaemon::new().await;
+    let parent = |path| {
+        let client = &daemon.client;
+        async move {
+            let object = client.object(path).unwrap();
+            client
+                .object_info(&object)
+                .await
+                .parent
+                .map(|parent| parent.to_string())
+        }
+    };
+
+    assert_eq!(
+        parent(fixtures::PARTITION).await.as_deref(),
+        Some(fixtures::DISK)
+    );
+    assert_eq!(
+        parent(fixtures::DISK).await.as_deref(),
+        Some(fixtures::DRIVE)
+    );
+    assert_eq!(parent(fixtures::DRIVE).await, None);
+    assert_eq!(parent(fixtures::LOOP).await, None);
+}
+
+#[tokio::test]
+async fn wait_for_property() {
+    let daemon = MockDaemon::new().await;
+    let drive = daemon.client.object(fixtures::DRIVE).unwrap();
+    let wait = |expected: bool| {
+        daemon.client.wait_for_property(
+            &drive,
+            "org.freedesktop.UDisks2.Drive",
+            "MediaAvailable",
+            move |available: &bool| *available == expected,
+            Duration::from_millis(500),
+        )
+    };
+
+    assert_eq!(wait(true).await, Ok(true));
+    assert_eq!(wait(false).await, Err(udisks2::Error::TimedOut));
+
+    let eject = async {
+        let interface = daemon
+            .server()
+            .object_server()
+            .interface::<_, common::MockDrive>(fixtures::DRIVE)
+            .await
+            .unwrap();
+        interface.get_mut().await.media_available = false;
+        interface
+            .get()
+            .await
+            .media_available_changed(interface.signal_emitter())
+            .await
+            .unwrap();
+    };
+    let (available, _) = tokio::join!(wait(false), eject);
+    assert_eq!(available, Ok(false));
+}
+
+#[tokio::test]
+async fn set_mount_at_boot() {
+    let daemon = MockDaemon::new().await;
+    let object = daemon.client.object(fixtures::PARTITION).unwrap();
+    let block = object.block().await.unwrap();
+    let configured = |configured: bool| {
+        daemon.client.wait_for_property(
+            &object,
+            "org.freedesktop.UDisks2.Block",
+            "Configuration",
+            move |items: &Vec<(String, HashMap<String, OwnedValue>)>| {
+                items.is_empty() != configured
+            },
+            Duration::from_millis(500),
+        )
+    };
+
+    daemon
+        .client
+        .set_mount_at_boot(&block, true, MountAtBootOptions::new())
+        .await
+        .unwrap();
+    assert!(configured(true).await.is_ok());
+    assert_eq!(
+        block.configuration_items().await.unwrap(),
+        [ConfigurationItem::Fstab(FstabEntry {
+            fsname: "UUID=0b6e3e1a-7c1f-4d0e-9a52-2c1f8f7e4d11".to_owned(),
+            dir: "/mnt/0b6e3e1a-7c1f-4d0e-9a52-2c1f8f7e4d11".to_owned(),
+            type_: "auto".to_owned(),
+            opts: MountAtBootOptions::DEFAULT_MOUNT_OPTIONS.to_owned(),
+            freq: 0,
+            passno: 0,
+        })]
+    );
+
+    daemon
+        .client
+        .set_mount_at_boot(&block, false, MountAtBootOptions::new())
+        .await
+        .unwrap();
+    assert!(configured(false).await.is_ok());
+}
+
+#[tokio::test]
+async fn set_unlock_at_boot() {
+    let daemon = MockDaemon::new().await;
+    let object = daemon.client.object(fixtures::PARTITION).unwrap();
+    let block = object.block().await.unwrap();
+    assert_eq!(
+        daemon.client.set_unlock_at_boot(&block, true, None).await,
+        Err(udisks2::Error::NotSupported)
+    );
+
+    daemon
+        .replace(
+            fixtures::PARTITION,
+            MockBlock {
+                id_usage: "crypto".to_owned(),
+                id_type: "crypto_LUKS".to_owned(),
+                id_uuid: String::new(),
+                ..fixtures::partition_block()
+            },
+        )
+        .await;
+    let block = daemon
+        .client
+        .object(fixtures::PARTITION)
+        .unwrap()
+        .block()
+        .await
+        .unwrap();
+    assert_eq!(
+        daemon.client.set_unlock_at_boot(&block, true, None).await,
+        Err(udisks2::Error::NotSupported)
+    );
+
+    daemon
+        .replace(
+            fixtures::PARTITION,
+            MockBlock {
+                id_usage: "crypto".to_owned(),
+                id_type: "crypto_LUKS".to_owned(),
+                ..fixtures::partition_block()
+            },
+        )
+        .await;
+    let block = daemon
+        .client
+        .object(fixtures::PARTITION)
+        .unwrap()
+        .block()
+        .await
+        .unwrap();
+    let configured = |configured: bool| {
+        daemon.client.wait_for_property(
+            &object,
+            "org.freedesktop.UDisks2.Block",
+            "Configuration",
+            move |items: &Vec<(String, HashMap<String, OwnedValue>)>| {
+                items.is_empty() != configured
+            },
+            Duration::from_millis(500),
+        )
+    };
+
+    daemon
+        .client
+        .set_unlock_at_boot(&block, true, None)
+        .await
+        .unwrap();
+    assert!(configured(true).await.is_ok());
+    assert_eq!(
+        block.configuration_items().await.unwrap(),
+        [ConfigurationItem::Crypttab(CrypttabEntry {
+            name: "luks-0b6e3e1a-7c1f-4d0e-9a52-2c1f8f7e4d11".to_owned(),
+            device: "UUID=0b6e3e1a-7c1f-4d0e-9a52-2c1f8f7e4d11".to_owned(),
+            passphrase_path: String::new(),
+            options: "nofail".to_owned(),
+        })]
+    );
+
+    daemon
+        .client
+        .set_unlock_at_boot(&block, false, None)
+        .await
+        .unwrap();
+    assert!(configured(false).await.is_ok());
+}
+
+#[tokio::test]
+async fn block_cache() {
+    let daemon = MockDaemon::new().await;
+    let client = daemon.client.clone().with_cache().await.unwrap();
+    let drive = client
+        .object(fixtures::DRIVE)
+        .unwrap()
+        .drive()
+        .await
+        .unwrap();
+    let block_path = || async {
+        client
+            .block_for_drive(&drive, false)
+            .await
+            .map(|block| block.inner().path().to_string())
+    };
+
+    assert_eq!(block_path().await.as_deref(), Some(fixtures::DISK));
+
+    // the cache is emptied once the new block is signalled,
+    // which is preferred as device-mapper device combining multiple paths
+    let multipath = "/org/freedesktop/UDisks2/block_devices/dm_2d0";
+    daemon
+        .add(
+            multipath,
+            common::MockBlock {
+                device: common::bytestring("/dev/dm-0"),
+                drive: common::object_path(fixtures::DRIVE),
+                ..Default::default()
+            },
+        )
+        .await;
+    tokio::time::timeout(Duration::from_millis(500), async {
+        while block_path().await.as_deref() != Some(multipath) {
+            tokio::task::yield_now().await;
+        }
+    })
+    .await
+    .unwrap();
+
+    daemon.remove::<common::MockBlock>(multipath).await;
+    client.invalidate_cache();
+    assert_eq!(block_path().await.as_deref(), Some(fixtures::DISK));
+}
+
+#[tokio::test]
+async fn events() {
+    let daemon = MockDaemon::new().await;
+    let mut events = Box::pin(daemon.client.events().await.unwrap());
+    async fn next(
+        events: &mut (impl futures_util::Stream<Item = DeviceEvent> + Unpin),
+    ) -> DeviceEvent {
+        tokio::time::timeout(Duration::from_millis(500), events.next())
+            .await
+            .unwrap()
+            .unwrap()
+    }
+    let loop_object = daemon.client.object(fixtures::LOOP).unwrap();
+
+    let new = "/org/freedesktop/UDisks2/block_devices/loop1";
+    daemon.add(new, fixtures::loop_block()).await;
+    assert_eq!(
+        next(&mut events).await,
+        DeviceEvent::Added(daemon.client.object(new).unwrap())
+    );
+
+    daemon.add(fixtures::LOOP, fixtures::filesystem()).await;
+    assert_eq!(
+        next(&mut events).await,
+        DeviceEvent::InterfacesAdded {
+            object: loop_object.clone(),
+            interfaces: vec!["org.freedesktop.UDisks2.Filesystem".to_owned()],
+        }
+    );
+
+    daemon
+        .remove::<common::MockFilesystem>(fixtures::LOOP)
+        .await;
+    daemon.remove::<common::MockLoop>(fixtures::LOOP).await;
+    daemon.remove::<common::MockBlock>(fixtures::LOOP).await;
+    assert_eq!(
+        next(&mut events).await,
+        DeviceEvent::InterfacesRemoved {
+            object: loop_object.clone(),
+            interfaces: vec!["org.freedesktop.UDisks2.Filesystem".to_owned()],
+        }
+    );
+    assert!(matches!(
+        next(&mut events).await,
+        DeviceEvent::InterfacesRemoved { .. }
+    ));
+    assert_eq!(
+        next(&mut events).await,
+        DeviceEvent::Removed(loop_object.object_path().clone())
+    );
+}
+
+#[tokio::test]
+async fn mount_points_paths() {
+    use std::{ffi::OsStr, os::unix::ffi::OsStrExt, path::PathBuf};
+
+    let daemon = MockDaemon::new().await;
+    let filesystem = || async {
+        daemon
+            .client
+            .object(fixtures::PARTITION)
+            .unwrap()
+            .filesystem()
+            .await
+            .unwrap()
+    };
+    assert!(filesystem()
+        .await
+        .mount_points_paths()
+        .await
+        .unwrap()
+        .is_empty());
+
+    daemon
+        .replace(
+            fixtures::PARTITION,
+            common::MockFilesystem {
+                mount_points: vec![
+                    common::bytestring("/run/media/user/Data"),
+                    b"/mnt/\xff\0".to_vec(),
+                ],
+                ..fixtures::filesystem()
+            },
+        )
+        .await;
+    assert_eq!(
+        filesystem().await.mount_points_paths().await.unwrap(),
+        [
+            PathBuf::from("/run/media/user/Data"),
+            PathBuf::from(OsStr::from_bytes(b"/mnt/\xff")),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn block_for_mount_point() {
+    use std::path::PathBuf;
+
+    let daemon = MockDaemon::new().await;
+    let mount_point = std::env::temp_dir().canonicalize().unwrap();
+    daemon
+        .replace(
+            fixtures::PARTITION,
+            common::MockFilesystem {
+                mount_points: vec![mount_point.as_os_str().as_encoded_bytes().to_vec()],
+                ..fixtures::filesystem()
+            },
+        )
+        .await;
+    let client = &daemon.client;
+    let block_path = |path: PathBuf| async move {
+        client
+            .block_for_mount_point(&path)
+            .await
+            .unwrap()
+            .map(|block| block.inner().path().to_string())
+    };
+
+    // trailing slashes are ignored
+    assert_eq!(
+        block_path(mount_point.join("")).await.as_deref(),
+        Some(fixtures::PARTITION)
+    );
+    assert_eq!(block_path(PathBuf::from("/udisks-not-mounted")).await, None);
+}
+
+#[tokio::test]
+async fn device_paths() {
+    use std::path::PathBuf;
+
+    let daemon = MockDaemon::new().await;
+    daemon
+        .replace(
+            fixtures::DISK,
+            common::MockBlock {
+                symlinks: vec![common::bytestring("/dev/disk/by-id/mock-disk")],
+                ..fixtures::disk_block()
+            },
+        )
+        .await;
+    let block = daemon
+        .client
+        .object(fixtures::DISK)
+        .unwrap()
+        .block()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        block.device_path().await.unwrap(),
+        PathBuf::from("/dev/sda")
+    );
+    assert_eq!(
+        block.preferred_device_path().await.unwrap(),
+        PathBuf::from("/dev/sda")
+    );
+    assert_eq!(
+        block.symlink_paths().await.unwrap(),
+        [PathBuf::from("/dev/disk/by-id/mock-disk")]
+    );
+}
+
+#[tokio::test]
+async fn parse_size() {
+    let daemon = MockDaemon::empty().await;
+    let client = &daemon.client;
+
+    assert_eq!(client.parse_size("1.5GB"), Ok(1_500_000_000));
+    assert_eq!(client.parse_size("4096"), Ok(4096));
+    assert_eq!(
+        client.parse_size("10 XB"),
+        Err(udisks2::SizeParseError::UnknownUnit("XB".to_owned()))
+    );
+    assert_eq!(
+        client.parse_size("100000 PB"),
+        Err(udisks2::SizeParseError::Overflow)
+    );
+
+    // sizes that are displayed without rounding, with one decimal
+    for (size, use_pow2) in [
+        (0, false),
+        (1, true),
+        (512, false),
+        (999, false),
+        (1000, false),
+        (1_500_000, false),
+        (500 * 1000 * 1000, false),
+        (1_500_000_000, false),
+        (1_200_000_000_000, false),
+        (1024, true),
+        (1536, true),
+        (64 * 1024, true),
+        (3 * 1024 * 1024 * 1024 / 2, true),
+        (4 * 1024 * 1024 * 1024, true),
+        (5 * 1024u64.pow(4), true),
+    ] {
+        let display = client.size_for_display(size, use_pow2, false);
+        assert_eq!(client.parse_size(&display), Ok(size), "{}", display);
+    }
+
+    // the displayed value is rounded, so the parsed size is within the precision of one decimal
+    for (size, use_pow2) in [(1_234_567_890, false), (123_456_789, true)] {
+        let display = client.size_for_display(size, use_pow2, false);
+        let parsed = client.parse_size(&display).unwrap();
+        assert!(parsed.abs_diff(size) <= size / 20, "{display}: {parsed}");
+    }
+}
+
+#[tokio::test]
+async fn size_for_display() {
+    const KIB: u64 = 1024;
+    const TIB: u64 = KIB * KIB * KIB * KIB;
+    let daemon = MockDaemon::empty().await;
+    let client = &daemon.client;
+
+    // sizes below one kilobyte are always displayed in bytes
+    for (size, expected) in [
+        (0, "0 bytes"),
+        (1, "1 byte"),
+        (2, "2 bytes"),
+        (512, "512 bytes"),
+        (999, "999 bytes"),
+    ] {
+        for use_pow2 in [false, true] {
+            for long_string in [false, true] {
+                assert_eq!(
+                    client.size_for_display(size, use_pow2, long_string),
+                    expected,
+                    "size {size}, pow2 {use_pow2}, long {long_string}"
+                );
+            }
+        }
+    }
+
+    for (size, use_pow2, long_string, expected) in [
+        (2000, false, true, "2.0 KB (2000 bytes)"),
+        (2048, true, true, "2.0 KiB (2048 bytes)"),
+        (KIB * KIB * KIB, true, false, "1.0 GiB"),
+        (TIB - 1, true, false, "1024 GiB"),
+        (TIB, true, false, "1.0 TiB"),
+        (2 * TIB, true, false, "2.0 TiB"),
+    ] {
+        assert_eq!(
+            client.size_for_display(size, use_pow2, long_string),
+            expected,
+            "size {size}, pow2 {use_pow2}, long {long_string}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn unlock() {
+    const ENCRYPTED: &str = "/org/freedesktop/UDisks2/block_devices/sdb";
+    const CLEARTEXT: &str = "/org/freedesktop/UDisks2/block_devices/dm_2d0";
+    let daemon = MockDaemon::new().await;
+    daemon.add(ENCRYPTED, MockBlock::default()).await;
+    daemon
+        .add(
+            ENCRYPTED,
+            MockEncrypted {
+                passphrase: "secret".to_owned(),
+                cleartext_device: common::object_path(CLEARTEXT),
+            },
+        )
+        .await;
+    daemon
+        .add(
+            CLEARTEXT,
+            MockBlock {
+                crypto_backing_device: common::object_path(ENCRYPTED),
+                ..Default::default()
+            },
+        )
+        .await;
+    let encrypted = daemon
+        .client
+        .object(ENCRYPTED)
+        .unwrap()
+        .encrypted()
+        .await
+        .unwrap();
+
+    let cleartext = daemon
+        .client
+        .unlock(&encrypted, "secret", UnlockOptions::new().read_only(true))
+        .await
+        .unwrap();
+    assert_eq!(cleartext.inner().path().as_str(), CLEARTEXT);
+    assert_eq!(
+        cleartext.crypto_backing_device().await.unwrap().as_str(),
+        ENCRYPTED
+    );
+
+    let options = UnlockOptions::new().keyfile_contents(b"secret".to_vec());
+    assert!(daemon.client.unlock(&encrypted, "", options).await.is_ok());
+
+    let options = UnlockOptions::new();
+    assert!(daemon
+        .client
+        .unlock(&encrypted, "wrong", options)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn partition_type_for_name() {
+    let daemon = MockDaemon::new().await;
+    let client = &daemon.client;
+
+    let guid = client.partition_type_for_name("gpt", None, "linux filesystem");
+    assert_eq!(guid, Some("0fc63daf-8483-4772-8e79-3d69d8477de4"));
+    assert_eq!(
+        client
+            .partition_type_for_display(PartitionTableType::Gpt, guid.unwrap())
+            .as_deref(),
+        Some("Linux Filesystem")
+    );
+    assert_eq!(
+        client.partition_type_for_name("gpt", Some("linux"), "Linux Filesystem"),
+        guid
+    );
+    assert_eq!(
+        client.partition_type_for_name("gpt", Some("microsoft"), "Linux Filesystem"),
+        None
+    );
+    assert_eq!(
+        client.partition_type_for_name(PartitionTableType::Dos, Some("linux"), "Linux"),
+        Some("0x83")
+    );
+    assert_eq!(
+        client.partition_type_for_name("apm", None, "Linux Filesystem"),
+        None
+    );
+    assert_eq!(
+        client.partition_type_for_name("gpt", None, "Unknown Type"),
+        None
+    );
+}
+
+#[tokio::test]
+async fn media_compat_display() {
+    let daemon = MockDaemon::new().await;
+    let client = &daemon.client;
+
+    let optical = [
+        MediaCompatibility::OpticalHddvdR,
+        MediaCompatibility::OpticalBdRe,
+        MediaCompatibility::OpticalDvdPlusRw,
+        MediaCompatibility::OpticalDvd,
+        MediaCompatibility::OpticalCdR,
+        MediaCompatibility::OpticalCd,
+    ];
+    assert_eq!(
+        client.media_compat_display(&optical).as_deref(),
+        Some("CD/DVD/Blu-Ray/HDDVD")
+    );
+    assert_eq!(
+        client
+            .media_compat_display(&[MediaCompatibility::OpticalDvdR])
+            .as_deref(),
+        Some("DVD")
+    );
+    assert_eq!(
+        client
+            .media_compat_display(&[MediaCompatibility::FlashSd, MediaCompatibility::OpticalBd])
+            .as_deref(),
+        Some("SecureDigital/Blu-Ray")
+    );
+    assert_eq!(
+        client.media_compat_display(&[MediaCompatibility::Unknown]),
+        None
+    );
+}
+
+#[tokio::test]
+async fn resolve_device() {
+    let daemon = MockDaemon::new().await;
+    let resolve = |devspec| async {
+        daemon
+            .client
+            .resolve_device(devspec)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|object| object.object_path().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        resolve(DevSpec::new().path("/dev/sda1")).await,
+        [fixtures::PARTITION]
+    );
+    assert_eq!(
+        resolve(DevSpec::new().uuid("0b6e3e1a-7c1f-4d0e-9a52-2c1f8f7e4d11")).await,
+        [fixtures::PARTITION]
+    );
+    assert_eq!(
+        resolve(DevSpec::new().label("Data").partlabel("Data")).await,
+        [fixtures::PARTITION]
+    );
+    assert!(resolve(DevSpec::new().path("/dev/sda").label("Data"))
+        .await
+        .is_empty());
+
+    let blocks = daemon
+        .client
+        .block_for_partuuid("5F3C1A9E-2B7D-4E60-8C41-9D2E7A6B0F35")
+        .await;
+    assert_eq!(blocks.len(), 1);
+}
+
+#[tokio::test]
+async fn smart_status() {
+    let daemon = MockDaemon::new().await;
+    let drive = daemon.client.object(fixtures::DRIVE).unwrap();
+    assert_eq!(daemon.client.smart_status(&drive).await.unwrap(), None);
+
+    daemon
+        .add(
+            fixtures::DRIVE,
+            MockAta {
+                smart_supported: true,
+                smart_enabled: true,
+                smart_failing: false,
+                smart_temperature: 313.15,
+                smart_power_on_seconds: 3600,
+                smart_updated: 1_700_000_000,
+                ..Default::default()
+            },
+        )
+        .await;
+    let status = daemon.client.smart_status(&drive).await.unwrap().unwrap();
+    assert!(!status.failing);
+    assert_eq!(status.temperature_celsius().map(f64::round), Some(40.0));
+    assert_eq!(status.power_on, Some(Duration::from_secs(3600)));
+    assert_eq!(status.self_test, SelfTestResult::Success);
+    assert_eq!(
+        status.updated_time(),
+        Some(std::time::UNIX_EPOCH + Duration::from_secs(1_700_000_000))
+    );
+
+    let block = daemon.client.object(fixtures::DISK).unwrap();
+    assert_eq!(daemon.client.smart_status(&block).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn drive_system_times() {
+    let daemon = MockDaemon::new().await;
+    let drive = daemon
+        .client
+        .object(fixtures::DRIVE)
+        .unwrap()
+        .drive()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        drive.time_detected_system_time().await.unwrap(),
+        Some(std::time::UNIX_EPOCH + Duration::from_secs(1_700_000_000))
+    );
+    assert_eq!(drive.time_media_detected_system_time().await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn mount() {
+    let daemon = MockDaemon::new().await;
+    let filesystem = daemon
+        .client
+        .object(fixtures::PARTITION)
+        .unwrap()
+        .filesystem()
+        .await
+        .unwrap();
+
+    let options = MountOptions::new().fstype("ext4").as_user("alice");
+    let mount_point = daemon.client.mount(&filesystem, options).await.unwrap();
+    assert_eq!(mount_point, Path::new("/run/media/alice/Data"));
+
+    let options = MountOptions::new();
+    assert!(daemon.client.mount(&filesystem, options).await.is_err());
+}
+
+#[tokio::test]
+async fn is_destructive_safe_raid_member() {
+    const MEMBER: &str = "/org/freedesktop/UDisks2/block_devices/sdb";
+    const ARRAY: &str = "/org/freedesktop/UDisks2/mdraid/md0";
+    let daemon = MockDaemon::new().await;
+    daemon
+        .add(
+            MEMBER,
+            MockBlock {
+                mdraid_member: common::object_path(ARRAY),
+                id_usage: "raid".to_owned(),
+                id_type: "linux_raid_member".to_owned(),
+                ..Default::default()
+            },
+        )
+        .await;
+    daemon
+        .add(
+            ARRAY,
+            MockMDRaid {
+                name: "host:md0".to_owned(),
+                running: true,
+                ..Default::default()
+            },
+        )
+        .await;
+    let block = daemon.client.object(MEMBER).unwrap().block().await.unwrap();
+
+    let report = daemon.client.is_destructive_safe(&block).await.unwrap();
+    assert!(!report.is_safe());
+    assert!(report.contains(SafetyConcern::RaidMember));
+
+    daemon
+        .replace(
+            ARRAY,
+            MockMDRaid {
+                name: "host:md0".to_owned(),
+                running: false,
+                ..Default::default()
+            },
+        )
+        .await;
+    let report = daemon.client.is_destructive_safe(&block).await.unwrap();
+    assert!(!report.contains(SafetyConcern::RaidMember));
+}
+
+#[tokio::test]
+async fn check_filesystem_job() {
+    let daemon = MockDaemon::new().await;
+    let release = std::sync::Arc::new(tokio::sync::Notify::new());
+    daemon
+        .replace(
+            fixtures::PARTITION,
+            common::MockFilesystem {
+                check_release: Some(release.clone()),
+                ..fixtures::filesystem()
+            },
+        )
+        .await;
+    let client = &daemon.client;
+    let filesystem = client
+        .object(fixtures::PARTITION)
+        .unwrap()
+        .filesystem()
+        .await
+        .unwrap();
+
+    let object = client.object(fixtures::PARTITION).unwrap();
+    let check = client.check_filesystem(&filesystem, HashMap::new());
+    let in_flight = async {
+        // the job is exported while the check is still blocked in the daemon
+        while client.jobs_for_object(&object).await.is_empty() {
+            tokio::task::yield_now().await;
+        }
+        release.notify_one();
+    };
+    let (result, ()) = tokio::join!(check, in_flight);
+
+    let result = result.unwrap();
+    assert!(result.clean);
+    // the job has been removed, but its cached properties are still available
+    let job = result.job.unwrap();
+    assert_eq!(
+        job.inner().path().as_str(),
+        "/org/freedesktop/UDisks2/jobs/1"
+    );
+    assert_eq!(job.operation().await.unwrap(), "filesystem-check");
+}
+
+#[tokio::test]
+async fn resize_filesystem_remount() {
+    use udisks2::filesystem::ResizeTarget;
+
+    let daemon = MockDaemon::new().await;
+    let mounted = |mount_point: &str| common::MockFilesystem {
+        mount_points: vec![common::bytestring(mount_point)],
+        size: 4_000_000_000,
+        ..fixtures::filesystem()
+    };
+    daemon
+        .replace(fixtures::PARTITION, mounted("/run/media/mock/Data"))
+        .await;
+    let client = &daemon.client;
+    let filesystem = client
+        .object(fixtures::PARTITION)
+        .unwrap()
+        .filesystem()
+        .await
+        .unwrap();
+
+    // shrinking requires unmounting, the daemon mounts it at the same place again
+    client
+        .resize_filesystem(
+            &filesystem,
+            ResizeTarget::Size(2_000_000_000),
+            HashMap::new(),
+        )
+        .await
+        .unwrap();
+    // read the properties from the daemon, the cache may not have been updated yet
+    let resized = client
+        .object(fixtures::PARTITION)
+        .unwrap()
+        .filesystem()
+        .await
+        .unwrap();
+    assert_eq!(
+        resized.mount_points_paths().await.unwrap(),
+        [Path::new("/run/media/mock/Data")]
+    );
+    assert_eq!(resized.size().await.unwrap(), 2_000_000_000);
+
+    daemon
+        .replace(fixtures::PARTITION, mounted("/mnt/data"))
+        .await;
+    // the properties of the replaced interface are not cached yet
+    let filesystem = client
+        .object(fixtures::PARTITION)
+        .unwrap()
+        .filesystem()
+        .await
+        .unwrap();
+    assert_eq!(
+        client
+            .resize_filesystem(
+                &filesystem,
+                ResizeTarget::Size(1_000_000_000),
+                HashMap::new()
+            )
+            .await,
+        Err(udisks2::Error::MountPointChanged {
+            expected: "/mnt/data".into(),
+            actual: "/run/media/mock/Data".into(),
+        })
+    );
+}
+
+#[tokio::test]
+async fn object_info_kind() {
+    const ARRAY: &str = "/org/freedesktop/UDisks2/mdraid/mock";
+    const MD: &str = "/org/freedesktop/UDisks2/block_devices/md0";
+    const DM: &str = "/org/freedesktop/UDisks2/block_devices/dm_2d0";
+    const JOB: &str = "/org/freedesktop/UDisks2/jobs/mock";
+
+    let daemon = MockDaemon::new().await;
+    daemon
+        .add(
+            ARRAY,
+            MockMDRaid {
+                uuid: "0f9e3a5c:1b2d4e6f:8a7c9b0d:2e4f6a8c".to_owned(),
+                name: "host:md0".to_owned(),
+                level: "raid1".to_owned(),
+                size: 8_000_000_000,
+                running: true,
+            },
+        )
+        .await;
+    daemon
+        .add(
+            MD,
+            MockBlock {
+                device: common::bytestring("/dev/md0"),
+                preferred_device: common::bytestring("/dev/md0"),
+                mdraid: common::object_path(ARRAY),
+                ..Default::default()
+            },
+        )
+        .await;
+    daemon
+        .add(
+            DM,
+            MockBlock {
+                device: common::bytestring("/dev/dm-0"),
+                preferred_device: common::bytestring("/dev/dm-0"),
+                ..Default::default()
+            },
+        )
+        .await;
+    daemon.add(JOB, common::MockJob::default()).await;
+    let kind = |path| {
+        let client = &daemon.client;
+        async move {
+            let object = client.object(path).unwrap();
+            client.object_info(&object).await.kind()
+        }
+    };
+
+    // info_for_drive
+    assert_eq!(kind(fixtures::DRIVE).await, DeviceKind::Drive);
+    assert_eq!(kind(fixtures::PARTITION).await, DeviceKind::Drive);
+    // info_for_mdraid
+    assert_eq!(kind(ARRAY).await, DeviceKind::MDRaid);
+    assert_eq!(kind(MD).await, DeviceKind::MDRaid);
+    // info_for_loop
+    assert_eq!(kind(fixtures::LOOP).await, DeviceKind::Loop);
+    // info_for_block
+    assert_eq!(kind(DM).await, DeviceKind::Block);
+    // info_for_unknown
+    assert_eq!(kind(JOB).await, DeviceKind::Unknown);
+}
+
+#[tokio::test]
+async fn get_object() {
+    let daemon = MockDaemon::new().await;
+    let client = &daemon.client;
+
+    let object = client.get_object(fixtures::DRIVE).await.unwrap();
+    assert_eq!(object.object_path().as_str(), fixtures::DRIVE);
+
+    let missing = "/org/freedesktop/UDisks2/drives/Missing";
+    assert!(matches!(
+        client.get_object(missing).await,
+        Err(udisks2::Error::Zbus(zbus::Error::FDO(err)))
+            if matches!(*err, zbus::fdo::Error::UnknownObject(_))
+    ));
+    // the infallible lookup does not check whether the object exists
+    assert!(client.object(missing).is_ok());
+}
+
+#[tokio::test]
+async fn partition_info() {
+    let daemon = MockDaemon::new().await;
+    let client = &daemon.client;
+    let info = || async {
+        let partition = client
+            .object(fixtures::PARTITION)
+            .unwrap()
+            .partition()
+            .await
+            .unwrap();
+        client.partition_info(&partition).await.unwrap()
+    };
+
+    assert_eq!(info().await, "Linux Filesystem");
+
+    // GPT flags: system partition and hidden
+    daemon
+        .replace(
+            fixtures::PARTITION,
+            common::MockPartition {
+                flags: (1 << 0) | (1 << 62),
+                ..fixtures::partition()
+            },
+        )
+        .await;
+    assert_eq!(info().await, "Linux Filesystem (System, Hidden)");
+
+    daemon
+        .replace(
+            fixtures::PARTITION,
+            common::MockPartition {
+                type_: String::new(),
+                flags: 1 << 0,
+                ..fixtures::partition()
+            },
+        )
+        .await;
+    assert_eq!(info().await, "Unknown (System)");
+
+    // the flags are omitted, if the table type can not be read
+    daemon
+        .replace(
+            fixtures::DISK,
+            common::MockPartitionTable {
+                type_: String::new(),
+                ..fixtures::disk_partition_table()
+            },
+        )
+        .await;
+    let table = client
+        .object(fixtures::DISK)
+        .unwrap()
+        .partition_table()
+        .await
+        .unwrap();
+    assert!(table.table_type().await.is_err());
+    assert_eq!(info().await, "Unknown");
+
+    // or if the partition table does not exist
+    daemon
+        .remove::<common::MockPartitionTable>(fixtures::DISK)
+        .await;
+    assert!(client
+        .object(fixtures::DISK)
+        .unwrap()
+        .partition_table()
+        .await
+        .is_err());
+    assert_eq!(info().await, "Unknown");
+}
+
+#[tokio::test]
+async fn object_info_one_liner() {
+    const ARRAY: &str = "/org/freedesktop/UDisks2/mdraid/mock";
+    const MD: &str = "/org/freedesktop/UDisks2/block_devices/md0";
+    const DM: &str = "/org/freedesktop/UDisks2/block_devices/dm_2d0";
+    const JOB: &str = "/org/freedesktop/UDisks2/jobs/mock";
+
+    let daemon = MockDaemon::new().await;
+    daemon
+        .replace(
+            fixtures::DRIVE,
+            common::MockDrive {
+                revision: "1.0".to_owned(),
+                ..fixtures::drive()
+            },
+        )
+        .await;
+    daemon
+        .add(
+            ARRAY,
+            MockMDRaid {
+                uuid: "0f9e3a5c:1b2d4e6f:8a7c9b0d:2e4f6a8c".to_owned(),
+                name: "host:md0".to_owned(),
+                level: "raid1".to_owned(),
+                size: 8_000_000_000,
+                running: true,
+            },
+        )
+        .await;
+    daemon
+        .add(
+            DM,
+            MockBlock {
+                device: common::bytestring("/dev/dm-0"),
+                preferred_device: common::bytestring("/dev/dm-0"),
+                size: 2_000_000_000,
+                ..Default::default()
+            },
+        )
+        .await;
+    daemon.add(JOB, common::MockJob::default()).await;
+    let one_liner = |path| {
+        let client = &daemon.client;
+        async move {
+            let object = client.object(path).unwrap();
+            client.object_info(&object).await.one_liner.unwrap()
+        }
+    };
+
+    for (path, expected) in [
+        (
+            fixtures::DRIVE,
+            "8.0 GB Drive — Mock Drive [1.0] (/dev/sda)",
+        ),
+        (fixtures::DISK, "8.0 GB Drive — Mock Drive [1.0] (/dev/sda)"),
+        (
+            fixtures::PARTITION,
+            "Partition 1 of 8.0 GB Drive — Mock Drive [1.0] (/dev/sda)",
+        ),
+        (
+            fixtures::LOOP,
+            "4.0 MB Loop Device — /tmp/image.iso (/dev/loop0)",
+        ),
+        (ARRAY, "md0 — 8.0 GB RAID-1 Array"),
+        (DM, "2.0 GB Block Device (/dev/dm-0)"),
+        (JOB, "Unknown Device (mock)"),
+    ] {
+        assert_eq!(one_liner(path).await, expected, "{path}");
+    }
+
+    // running arrays include the device file of the array
+    daemon
+        .add(
+            MD,
+            MockBlock {
+                device: common::bytestring("/dev/md0"),
+                preferred_device: common::bytestring("/dev/md0"),
+                mdraid: common::object_path(ARRAY),
+                ..Default::default()
+            },
+        )
+        .await;
+    assert_eq!(
+        one_liner(ARRAY).await,
+        "md0 — 8.0 GB RAID-1 Array (/dev/md0)"
+    );
+}
+
+#[tokio::test]
+async fn all_object_infos_single_fetch() {
+    let daemon = MockDaemon::new().await;
+    let objects = daemon.client.objects().await.unwrap();
+    let mut calls = daemon.count_calls("GetManagedObjects");
+
+    let infos = daemon.client.all_object_infos().await;
+    assert_eq!(infos.len(), objects.len());
+    // all objects are computed from one snapshot of the managed objects
+    assert_eq!(calls.count(), 1);
+
+    // object_info fetches the managed objects for each object instead
+    for object in &objects {
+        daemon.client.object_info(object).await;
+    }
+    assert_eq!(calls.count(), 1 + objects.len());
+}
+
+#[tokio::test]
+async fn object_info_drive_media() {
+    let daemon = MockDaemon::new().await;
+    daemon
+        .replace(
+            fixtures::DRIVE,
+            common::MockDrive {
+                media: "flash_sd".to_owned(),
+                media_compatibility: vec!["flash_sd".to_owned()],
+                ..fixtures::drive()
+            },
+        )
+        .await;
+    let object = daemon.client.object(fixtures::DRIVE).unwrap();
+    let info = daemon.client.object_info(&object).await;
+
+    assert_eq!(info.name.as_deref(), Some("Mock Drive"));
+    assert_eq!(info.description.as_deref(), Some("SD Card Reader"));
+    assert_eq!(info.media_description.as_deref(), Some("SD Card"));
+    assert!(info.media_available);
+
+    // without media, only the drive is described
+    daemon
+        .replace(
+            fixtures::DRIVE,
+            common::MockDrive {
+                media_available: false,
+                media_compatibility: vec!["flash_sd".to_owned()],
+                ..fixtures::drive()
+            },
+        )
+        .await;
+    let info = daemon.client.object_info(&object).await;
+    assert_eq!(info.description.as_deref(), Some("SD Card Reader"));
+    assert_eq!(info.media_description, None);
+    assert!(!info.media_available);
+
+    // block devices of the drive share its media state, objects without a drive have no media
+    let partition = daemon.client.object(fixtures::PARTITION).unwrap();
+    assert!(!daemon.client.object_info(&partition).await.media_available);
+    let loop_object = daemon.client.object(fixtures::LOOP).unwrap();
+    let info = daemon.client.object_info(&loop_object).await;
+    assert!(!info.has_removable_media);
+    assert!(!info.media_available);
+}
+
+#[tokio::test]
+async fn free_regions() {
+    let daemon = MockDaemon::new().await;
+    // a device without sysfs entry, so the default logical block size of 512 bytes is used
+    daemon
+        .replace(
+            fixtures::DISK,
+            MockBlock {
+                device: common::bytestring("/dev/udisks-mock0"),
+                size: 10_000_000_000,
+                ..fixtures::disk_block()
+            },
+        )
+        .await;
+    let table = daemon
+        .client
+        .object(fixtures::DISK)
+        .unwrap()
+        .partition_table()
+        .await
+        .unwrap();
+
+    // the partition ends at 1 MiB + 7,999,000,000 bytes, the backup header takes 34 blocks
+    let regions = daemon.client.free_regions(&table).await.unwrap();
+    assert_eq!(regions.len(), 1);
+    assert_eq!(regions[0].offset, 8_000_048_576);
+    assert_eq!(regions[0].end(), 10_000_000_000 - 34 * 512);
+    assert!(!regions[0].contained);
+
+    daemon
+        .replace(
+            fixtures::DISK,
+            common::MockPartitionTable {
+                type_: "dos".to_owned(),
+                ..fixtures::disk_partition_table()
+            },
+        )
+        .await;
+    let table = daemon
+        .client
+        .object(fixtures::DISK)
+        .unwrap()
+        .partition_table()
+        .await
+        .unwrap();
+    let regions = daemon.client.free_regions(&table).await.unwrap();
+    assert_eq!(regions[0].end(), 10_000_000_000);
+}
+
+#[tokio::test]
+async fn secure_erase_estimate() {
+    use udisks2::ata::EraseEstimate;
+
+    let daemon = MockDaemon::new().await;
+    let drive = daemon
+        .client
+        .object(fixtures::DRIVE)
+        .unwrap()
+        .drive()
+        .await
+        .unwrap();
+    assert_eq!(
+        daemon.client.secure_erase_estimate(&drive, false).await,
+        Ok(None)
+    );
+
+    daemon
+        .add(
+            fixtures::DRIVE,
+            MockAta {
+                security_erase_unit_minutes: 120,
+                security_enhanced_erase_unit_minutes: 510,
+                ..Default::default()
+            },
+        )
+        .await;
+    assert_eq!(
+        daemon.client.secure_erase_estimate(&drive, false).await,
+        Ok(Some(EraseEstimate::Bounded(Duration::from_secs(
+            2 * 60 * 60
+        ))))
+    );
+    let enhanced = daemon
+        .client
+        .secure_erase_estimate(&drive, true)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(enhanced.is_unbounded());
+    assert_eq!(enhanced.duration(), Duration::from_secs(508 * 60));
+}
+
+#[tokio::test]
+async fn job_operation_labels() {
+    let daemon = MockDaemon::empty().await;
+    let client = &daemon.client;
+
+    for operation in [
+        "ata-smart-selftest",
+        "drive-eject",
+        "encrypted-unlock",
+        "encrypted-lock",
+        "encrypted-modify",
+        "encrypted-resize",
+        "swapspace-start",
+        "swapspace-stop",
+        "swapspace-modify",
+        "filesystem-check",
+        "filesystem-mount",
+        "filesystem-unmount",
+        "filesystem-modify",
+        "filesystem-repair",
+        "filesystem-resize",
+        "format-erase",
+        "format-mkfs",
+        "loop-setup",
+        "partition-modify",
+        "partition-delete",
+        "partition-create",
+        "cleanup",
+        "ata-secure-erase",
+        "ata-enhanced-secure-erase",
+        "md-raid-stop",
+        "md-raid-start",
+        "md-raid-fault-device",
+        "md-raid-remove-device",
+        "md-raid-add-device",
+        "md-raid-set-bitmap",
+        "md-raid-create",
+        "nvme-selftest",
+        "nvme-sanitize",
+        "nvme-format-ns",
+    ] {
+        let unknown = format!("Unknown ({})", operation);
+        assert_ne!(client.job_description_from_operation(operation), unknown);
+        assert_ne!(client.operation_short_label(operation), unknown);
+    }
+
+    assert_eq!(client.operation_short_label("format-mkfs"), "Format");
+    assert_eq!(
+        client.job_description_from_operation("format-mkfs"),
+        "Creating Filesystem"
+    );
+    assert_eq!(
+        client.operation_short_label("mock-operation"),
+        "Unknown (mock-operation)"
+    );
+}
+
+#[tokio::test]
+async fn blocks_for_namespace() {
+    const NAMESPACE: &str = "/org/freedesktop/UDisks2/block_devices/nvme0n1";
+    let partition = |number: u32| format!("{}p{}", NAMESPACE, number);
+
+    let daemon = MockDaemon::empty().await;
+    daemon
+        .add(NAMESPACE, common::MockNamespace { nsid: 1 })
+        .await;
+    daemon
+        .add(
+            NAMESPACE,
+            MockBlock {
+                device: common::bytestring("/dev/nvme0n1"),
+                ..Default::default()
+            },
+        )
+        .await;
+    for number in [10, 2, 1] {
+        daemon
+            .add(
+                &partition(number),
+                MockBlock {
+                    device: common::bytestring(&format!("/dev/nvme0n1p{}", number)),
+                    ..Default::default()
+                },
+            )
+            .await;
+        daemon
+            .add(
+                &partition(number),
+                common::MockPartition {
+                    number,
+                    table: common::object_path(NAMESPACE),
+                    ..fixtures::partition()
+                },
+            )
+            .await;
+    }
+
+    let namespace = daemon
+        .client
+        .object(NAMESPACE)
+        .unwrap()
+        .nvme_namespace()
+        .await
+        .unwrap();
+    let blocks: Vec<String> = daemon
+        .client
+        .blocks_for_namespace(&namespace)
+        .await
+        .iter()
+        .map(|block| block.inner().path().to_string())
+        .collect();
+    assert_eq!(
+        blocks,
+        [
+            NAMESPACE.to_owned(),
+            partition(1),
+            partition(2),
+            partition(10)
+        ]
+    );
+}
+
+#[tokio::test]
+async fn optical_disc_info() {
+    let daemon = MockDaemon::new().await;
+    let drive = || async {
+        daemon
+            .client
+            .object(fixtures::DRIVE)
+            .unwrap()
+            .drive()
+            .await
+            .unwrap()
+    };
+    assert!(daemon
+        .client
+        .optical_disc_info(&drive().await)
+        .await
+        .unwrap()
+        .is_none());
+
+    daemon
+        .replace(
+            fixtures::DRIVE,
+            common::MockDrive {
+                optical: true,
+                media: "optical_dvd_rw".to_owned(),
+                media_compatibility: vec!["optical_dvd_rw".to_owned()],
+                ..fixtures::drive()
+            },
+        )
+        .await;
+    let info = daemon
+        .client
+        .optical_disc_info(&drive().await)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(info.media_type.as_deref(), Some("DVD-RW"));
+
+    // unknown media is reported without a type
+    daemon
+        .replace(
+            fixtures::DRIVE,
+            common::MockDrive {
+                optical: true,
+                ..fixtures::drive()
+            },
+        )
+        .await;
+    let info = daemon
+        .client
+        .optical_disc_info(&drive().await)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(info.media_type, None);
+}
@@ -0,0 +1,1161 @@
+//! In-process mock of the UDisks daemon.
+//!
+//! The mock serves an `org.freedesktop.DBus.ObjectManager` at `/org/freedesktop/UDisks2` and
+//! the interfaces added with [`MockDaemon::add`] on a peer-to-peer connection, so tests run
+//! without a system bus, a running daemon or real hardware.
+//! [`MockDaemon::new`] starts the mock with the fixtures in [`fixtures`].
+#![allow(dead_code)]
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    os::unix::net::UnixStream,
+    sync::{Arc, Mutex},
+};
+
+use futures_util::{FutureExt, StreamExt};
+
+use zbus::{
+    connection,
+    object_server::Interface,
+    zvariant::{ObjectPath, OwnedObjectPath, OwnedValue},
+};
+
+/// Running mock daemon and a [`udisks2::Client`] connected to it.
+pub struct MockDaemon {
+    pub client: udisks2::Client,
+    server: zbus::Connection,
+    /// Paths of all objects added, used by [`MockManager`] to resolve devices.
+    paths: Arc<Mutex<BTreeSet<String>>>,
+}
+
+impl MockDaemon {
+    /// Starts the mock daemon, exporting all [`fixtures`].
+    pub async fn new() -> Self {
+        let daemon = Self::empty().await;
+        daemon.add(fixtures::DRIVE, fixtures::drive()).await;
+        daemon.add(fixtures::DISK, fixtures::disk_block()).await;
+        daemon
+            .add(fixtures::DISK, fixtures::disk_partition_table())
+            .await;
+        daemon
+            .add(fixtures::PARTITION, fixtures::partition_block())
+            .await;
+        daemon.add(fixtures::PARTITION, fixtures::partition()).await;
+        daemon
+            .add(fixtures::PARTITION, fixtures::filesystem())
+            .await;
+        daemon.add(fixtures::LOOP, fixtures::loop_block()).await;
+        daemon.add(fixtures::LOOP, fixtures::loop_device()).await;
+        daemon
+    }
+
+    /// Starts the mock daemon without any objects, except for the manager.
+    pub async fn empty() -> Self {
+        let (server, client) = UnixStream::pair().expect("failed to create socket pair");
+        let guid = zbus::Guid::generate();
+        let paths = Arc::default();
+        let server = connection::Builder::async_io_unix_stream(server)
+            .server(guid)
+            .expect("invalid guid")
+            .p2p()
+            .serve_at("/org/freedesktop/UDisks2", zbus::fdo::ObjectManager)
+            .expect("failed to serve object manager")
+            .serve_at(
+                "/org/freedesktop/UDisks2/Manager",
+                MockManager {
+                    paths: Arc::clone(&paths),
+                },
+            )
+            .expect("failed to serve manager")
+            .build();
+        let client = connection::Builder::async_io_unix_stream(client)
+            .p2p()
+            .build();
+        let (server, client) = futures_util::try_join!(server, client).expect("failed to connect");
+
+        let client = udisks2::Client::new_for_connection(client)
+            .await
+            .expect("failed to create client");
+        Self {
+            client,
+            server,
+            paths,
+        }
+    }
+
+    /// Returns the connection of the mock daemon, e.g. to emit property changes.
+    pub fn server(&self) -> &zbus::Connection {
+        &self.server
+    }
+
+    /// Counts the method calls of `member` received by the mock daemon from now on.
+    pub fn count_calls(&self, member: &'static str) -> CallCounter {
+        CallCounter {
+            messages: zbus::MessageStream::from(&self.server),
+            member,
+            count: 0,
+        }
+    }
+
+    /// Exports `interface` on the object at `path`.
+    pub async fn add<I: Interface>(&self, path: &str, interface: I) {
+        self.server
+            .object_server()
+            .at(path, interface)
+            .await
+            .expect("failed to export interface");
+        self.paths.lock().unwrap().insert(path.to_owned());
+    }
+
+    /// Replaces the interface `I` of the object at `path` with `interface`.
+    pub async fn replace<I: Interface>(&self, path: &str, interface: I) {
+        self.remove::<I>(path).await;
+        self.add(path, interface).await;
+    }
+
+    /// Removes the interface `I` from the object at `path`.
+    pub async fn remove<I: Interface>(&self, path: &str) {
+        self.server
+            .object_server()
+            .remove::<I, _>(path)
+            .await
+            .expect("failed to remove interface");
+    }
+}
+
+/// Counter of method calls received by the [`MockDaemon`], see [`MockDaemon::count_calls`].
+pub struct CallCounter {
+    messages: zbus::MessageStream,
+    member: &'static str,
+    count: usize,
+}
+
+impl CallCounter {
+    /// Returns the number of calls received so far.
+    ///
+    /// Calls are queued as soon as they are received, so all calls that have been replied to
+    /// are counted.
+    pub fn count(&mut self) -> usize {
+        while let Some(Some(Ok(message))) = self.messages.next().now_or_never() {
+            let header = message.header();
+            if header.message_type() == zbus::message::Type::MethodCall
+                && header
+                    .member()
+                    .is_some_and(|member| member.as_str() == self.member)
+            {
+                self.count += 1;
+            }
+        }
+        self.count
+    }
+}
+
+/// Returns the object path, used by the daemon for unset object path properties.
+pub fn no_object() -> OwnedObjectPath {
+    ObjectPath::from_static_str_unchecked("/").into()
+}
+
+/// Returns the given path as NUL-terminated bytestring, as used by the daemon for paths.
+pub fn bytestring(path: &str) -> Vec<u8> {
+    let mut bytes = path.as_bytes().to_vec();
+    bytes.push(0);
+    bytes
+}
+
+/// Returns the given object path.
+pub fn object_path(path: &str) -> OwnedObjectPath {
+    ObjectPath::try_from(path)
+        .expect("invalid object path")
+        .into()
+}
+
+/// Fixtures for a drive with a partitioned disk and a loop device.
+pub mod fixtures {
+    use super::*;
+
+    pub const DRIVE: &str = "/org/freedesktop/UDisks2/drives/Mock_Drive_1234";
+    pub const DISK: &str = "/org/freedesktop/UDisks2/block_devices/sda";
+    pub const PARTITION: &str = "/org/freedesktop/UDisks2/block_devices/sda1";
+    pub const LOOP: &str = "/org/freedesktop/UDisks2/block_devices/loop0";
+
+    /// A USB drive with removable media.
+    pub fn drive() -> MockDrive {
+        MockDrive {
+            vendor: "Mock".to_owned(),
+            model: "Drive".to_owned(),
+            serial: "1234".to_owned(),
+            id: "Mock-Drive-1234".to_owned(),
+            size: 8_000_000_000,
+            connection_bus: "usb".to_owned(),
+            removable: true,
+            media_removable: true,
+            media_available: true,
+            ejectable: true,
+            can_power_off: true,
+            sort_key: "01hotplug/1".to_owned(),
+            time_detected: 1_700_000_000_000_000,
+            ..Default::default()
+        }
+    }
+
+    /// The whole-disk block device of [`drive`].
+    pub fn disk_block() -> MockBlock {
+        MockBlock {
+            device: bytestring("/dev/sda"),
+            preferred_device: bytestring("/dev/sda"),
+            device_number: 8 << 8,
+            size: 8_000_000_000,
+            drive: object_path(DRIVE),
+            id_usage: String::new(),
+            hint_partitionable: true,
+            ..Default::default()
+        }
+    }
+
+    /// The GPT partition table of [`disk_block`].
+    pub fn disk_partition_table() -> MockPartitionTable {
+        MockPartitionTable {
+            type_: "gpt".to_owned(),
+            partitions: vec![object_path(PARTITION)],
+        }
+    }
+
+    /// The block device of the single partition of [`disk_block`].
+    pub fn partition_block() -> MockBlock {
+        MockBlock {
+            device: bytestring("/dev/sda1"),
+            preferred_device: bytestring("/dev/sda1"),
+            device_number: (8 << 8) | 1,
+            size: 7_999_000_000,
+            drive: object_path(DRIVE),
+            id_usage: "filesystem".to_owned(),
+            id_type: "ext4".to_owned(),
+            id_label: "Data".to_owned(),
+            id_uuid: "0b6e3e1a-7c1f-4d0e-9a52-2c1f8f7e4d11".to_owned(),
+            ..Default::default()
+        }
+    }
+
+    /// The partition of [`partition_block`].
+    pub fn partition() -> MockPartition {
+        MockPartition {
+            number: 1,
+            type_: "0fc63daf-8483-4772-8e79-3d69d8477de4".to_owned(),
+            offset: 1024 * 1024,
+            size: 7_999_000_000,
+            table: object_path(DISK),
+            name: "Data".to_owned(),
+            uuid: "5f3c1a9e-2b7d-4e60-8c41-9d2e7a6b0f35".to_owned(),
+            ..Default::default()
+        }
+    }
+
+    /// The unmounted filesystem of [`partition_block`].
+    pub fn filesystem() -> MockFilesystem {
+        MockFilesystem::default()
+    }
+
+    /// The block device of a loop device, which has no drive.
+    pub fn loop_block() -> MockBlock {
+        MockBlock {
+            device: bytestring("/dev/loop0"),
+            preferred_device: bytestring("/dev/loop0"),
+            device_number: 7 << 8,
+            size: 4_000_000,
+            id_usage: "filesystem".to_owned(),
+            id_type: "iso9660".to_owned(),
+            ..Default::default()
+        }
+    }
+
+    /// The loop device of [`loop_block`], backed by an ISO image.
+    pub fn loop_device() -> MockLoop {
+        MockLoop {
+            backing_file: bytestring("/tmp/image.iso"),
+            autoclear: true,
+            setup_by_uid: 1000,
+        }
+    }
+}
+
+/// Minimal `org.freedesktop.UDisks2.Manager` interface.
+pub struct MockManager {
+    paths: Arc<Mutex<BTreeSet<String>>>,
+}
+
+#[zbus::interface(name = "org.freedesktop.UDisks2.Manager")]
+impl MockManager {
+    /// Returns the blocks matching all criteria of `devspec`.
+    async fn resolve_device(
+        &self,
+        devspec: HashMap<String, OwnedValue>,
+        _options: HashMap<String, OwnedValue>,
+        #[zbus(object_server)] server: &zbus::ObjectServer,
+    ) -> zbus::fdo::Result<Vec<OwnedObjectPath>> {
+        let devspec = devspec
+            .into_iter()
+            .map(|(key, value)| Ok((key, String::try_from(value)?)))
+            .collect::<Result<HashMap<_, _>, zbus::zvariant::Error>>()
+            .map_err(|err| zbus::fdo::Error::InvalidArgs(err.to_string()))?;
+
+        let paths = self.paths.lock().unwrap().clone();
+        let mut resolved = Vec::new();
+        for path in paths {
+            let Ok(block) = server.interface::<_, MockBlock>(path.as_str()).await else {
+                continue;
+            };
+            let block = block.get().await.clone();
+            let partition = match server.interface::<_, MockPartition>(path.as_str()).await {
+                Ok(partition) => Some(partition.get().await.clone()),
+                Err(_) => None,
+            };
+
+            let matches = devspec.iter().all(|(key, value)| match key.as_str() {
+                "path" => {
+                    block.device == bytestring(value) || block.symlinks.contains(&bytestring(value))
+                }
+                "label" => &block.id_label == value,
+                "uuid" => block.id_uuid.eq_ignore_ascii_case(value),
+                "partuuid" => partition
+                    .as_ref()
+                    .is_some_and(|p| p.uuid.eq_ignore_ascii_case(value)),
+                "partlabel" => partition.as_ref().is_some_and(|p| &p.name == value),
+                _ => false,
+            });
+            if matches {
+                resolved.push(object_path(&path));
+            }
+        }
+        Ok(resolved)
+    }
+
+    /// Only `ext4` can be resized, which can not be shrunk while mounted.
+    fn can_resize(&self, type_: String) -> (bool, u64, String) {
+        match type_.as_str() {
+            // OfflineShrink | OfflineGrow | OnlineGrow
+            "ext4" => (true, (1 << 1) | (1 << 2) | (1 << 4), String::new()),
+            _ => (false, 0, "resize2fs".to_owned()),
+        }
+    }
+
+    #[zbus(property)]
+    fn version(&self) -> String {
+        "2.10.1".to_owned()
+    }
+
+    #[zbus(property)]
+    fn supported_filesystems(&self) -> Vec<String> {
+        ["ext4", "vfat", "ntfs", "exfat", "xfs", "btrfs", "swap"]
+            .map(str::to_owned)
+            .to_vec()
+    }
+
+    #[zbus(property)]
+    fn supported_encryption_types(&self) -> Vec<String> {
+        vec!["luks1".to_owned(), "luks2".to_owned()]
+    }
+
+    #[zbus(property)]
+    fn default_encryption_type(&self) -> String {
+        "luks2".to_owned()
+    }
+}
+
+/// `org.freedesktop.UDisks2.Drive` interface.
+#[derive(Debug, Clone, Default)]
+pub struct MockDrive {
+    pub vendor: String,
+    pub model: String,
+    pub revision: String,
+    pub serial: String,
+    pub wwn: String,
+    pub id: String,
+    pub size: u64,
+    pub connection_bus: String,
+    pub removable: bool,
+    pub media_removable: bool,
+    pub media_available: bool,
+    pub media: String,
+    pub media_compatibility: Vec<String>,
+    pub optical: bool,
+    pub ejectable: bool,
+    pub can_power_off: bool,
+    pub rotation_rate: i32,
+    pub seat: String,
+    pub sibling_id: String,
+    pub sort_key: String,
+    pub time_detected: u64,
+    pub time_media_detected: u64,
+}
+
+#[zbus::interface(name = "org.freedesktop.UDisks2.Drive")]
+impl MockDrive {
+    #[zbus(property)]
+    fn vendor(&self) -> String {
+        self.vendor.clone()
+    }
+
+    #[zbus(property)]
+    fn model(&self) -> String {
+        self.model.clone()
+    }
+
+    #[zbus(property)]
+    fn revision(&self) -> String {
+        self.revision.clone()
+    }
+
+    #[zbus(property)]
+    fn serial(&self) -> String {
+        self.serial.clone()
+    }
+
+    #[zbus(property, name = "WWN")]
+    fn wwn(&self) -> String {
+        self.wwn.clone()
+    }
+
+    #[zbus(property)]
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    #[zbus(property)]
+    fn configuration(&self) -> HashMap<String, OwnedValue> {
+        HashMap::new()
+    }
+
+    #[zbus(property)]
+    fn size(&self) -> u64 {
+        self.size
+    }
+
+    #[zbus(property)]
+    fn connection_bus(&self) -> String {
+        self.connection_bus.clone()
+    }
+
+    #[zbus(property)]
+    fn removable(&self) -> bool {
+        self.removable
+    }
+
+    #[zbus(property)]
+    fn media_removable(&self) -> bool {
+        self.media_removable
+    }
+
+    #[zbus(property)]
+    fn media_available(&self) -> bool {
+        self.media_available
+    }
+
+    #[zbus(property)]
+    fn media_change_detected(&self) -> bool {
+        self.media_available
+    }
+
+    #[zbus(property)]
+    fn media(&self) -> String {
+        self.media.clone()
+    }
+
+    #[zbus(property)]
+    fn media_compatibility(&self) -> Vec<String> {
+        self.media_compatibility.clone()
+    }
+
+    #[zbus(property)]
+    fn optical(&self) -> bool {
+        self.optical
+    }
+
+    #[zbus(property)]
+    fn optical_blank(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn optical_num_tracks(&self) -> u32 {
+        0
+    }
+
+    #[zbus(property)]
+    fn optical_num_audio_tracks(&self) -> u32 {
+        0
+    }
+
+    #[zbus(property)]
+    fn optical_num_data_tracks(&self) -> u32 {
+        0
+    }
+
+    #[zbus(property)]
+    fn optical_num_sessions(&self) -> u32 {
+        0
+    }
+
+    #[zbus(property)]
+    fn ejectable(&self) -> bool {
+        self.ejectable
+    }
+
+    #[zbus(property)]
+    fn can_power_off(&self) -> bool {
+        self.can_power_off
+    }
+
+    #[zbus(property)]
+    fn rotation_rate(&self) -> i32 {
+        self.rotation_rate
+    }
+
+    #[zbus(property)]
+    fn seat(&self) -> String {
+        self.seat.clone()
+    }
+
+    #[zbus(property)]
+    fn sibling_id(&self) -> String {
+        self.sibling_id.clone()
+    }
+
+    #[zbus(property)]
+    fn sort_key(&self) -> String {
+        self.sort_key.clone()
+    }
+
+    #[zbus(property)]
+    fn time_detected(&self) -> u64 {
+        self.time_detected
+    }
+
+    #[zbus(property)]
+    fn time_media_detected(&self) -> u64 {
+        self.time_media_detected
+    }
+}
+
+/// `org.freedesktop.UDisks2.Block` interface.
+#[derive(Debug, Clone)]
+pub struct MockBlock {
+    pub device: Vec<u8>,
+    pub preferred_device: Vec<u8>,
+    pub symlinks: Vec<Vec<u8>>,
+    pub device_number: u64,
+    pub id: String,
+    pub size: u64,
+    pub read_only: bool,
+    pub drive: OwnedObjectPath,
+    pub mdraid: OwnedObjectPath,
+    pub mdraid_member: OwnedObjectPath,
+    pub crypto_backing_device: OwnedObjectPath,
+    pub id_usage: String,
+    pub id_type: String,
+    pub id_version: String,
+    pub id_label: String,
+    pub id_uuid: String,
+    pub configuration: Vec<udisks2::block::ConfigurationItem>,
+    pub hint_partitionable: bool,
+    pub hint_system: bool,
+    pub hint_ignore: bool,
+    pub hint_auto: bool,
+    pub hint_name: String,
+    pub hint_icon_name: String,
+    pub hint_symbolic_icon_name: String,
+}
+
+impl Default for MockBlock {
+    fn default() -> Self {
+        Self {
+            device: Vec::new(),
+            preferred_device: Vec::new(),
+            symlinks: Vec::new(),
+            device_number: 0,
+            id: String::new(),
+            size: 0,
+            read_only: false,
+            drive: no_object(),
+            mdraid: no_object(),
+            mdraid_member: no_object(),
+            crypto_backing_device: no_object(),
+            id_usage: String::new(),
+            id_type: String::new(),
+            id_version: String::new(),
+            id_label: String::new(),
+            id_uuid: String::new(),
+            configuration: Vec::new(),
+            hint_partitionable: false,
+            hint_system: false,
+            hint_ignore: false,
+            hint_auto: true,
+            hint_name: String::new(),
+            hint_icon_name: String::new(),
+            hint_symbolic_icon_name: String::new(),
+        }
+    }
+}
+
+#[zbus::interface(name = "org.freedesktop.UDisks2.Block")]
+impl MockBlock {
+    #[zbus(property)]
+    fn device(&self) -> Vec<u8> {
+        self.device.clone()
+    }
+
+    #[zbus(property)]
+    fn preferred_device(&self) -> Vec<u8> {
+        self.preferred_device.clone()
+    }
+
+    #[zbus(property)]
+    fn symlinks(&self) -> Vec<Vec<u8>> {
+        self.symlinks.clone()
+    }
+
+    #[zbus(property)]
+    fn device_number(&self) -> u64 {
+        self.device_number
+    }
+
+    #[zbus(property)]
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    #[zbus(property)]
+    fn size(&self) -> u64 {
+        self.size
+    }
+
+    #[zbus(property)]
+    fn read_only(&self) -> bool {
+        self.read_only
+    }
+
+    #[zbus(property)]
+    fn drive(&self) -> OwnedObjectPath {
+        self.drive.clone()
+    }
+
+    #[zbus(property, name = "MDRaid")]
+    fn mdraid(&self) -> OwnedObjectPath {
+        self.mdraid.clone()
+    }
+
+    #[zbus(property, name = "MDRaidMember")]
+    fn mdraid_member(&self) -> OwnedObjectPath {
+        self.mdraid_member.clone()
+    }
+
+    #[zbus(property)]
+    fn crypto_backing_device(&self) -> OwnedObjectPath {
+        self.crypto_backing_device.clone()
+    }
+
+    #[zbus(property)]
+    fn id_usage(&self) -> String {
+        self.id_usage.clone()
+    }
+
+    #[zbus(property)]
+    fn id_type(&self) -> String {
+        self.id_type.clone()
+    }
+
+    #[zbus(property)]
+    fn id_version(&self) -> String {
+        self.id_version.clone()
+    }
+
+    #[zbus(property)]
+    fn id_label(&self) -> String {
+        self.id_label.clone()
+    }
+
+    #[zbus(property, name = "IdUUID")]
+    fn id_uuid(&self) -> String {
+        self.id_uuid.clone()
+    }
+
+    #[zbus(property)]
+    fn configuration(&self) -> Vec<(String, HashMap<String, OwnedValue>)> {
+        self.configuration
+            .iter()
+            .map(|item| {
+                let (ty, details) = item.clone().into_raw();
+                let details = details
+                    .into_iter()
+                    .map(|(key, value)| (key.to_owned(), OwnedValue::try_from(value).unwrap()))
+                    .collect();
+                (ty.to_owned(), details)
+            })
+            .collect()
+    }
+
+    async fn add_configuration_item(
+        &mut self,
+        item: (String, HashMap<String, OwnedValue>),
+        _options: HashMap<String, OwnedValue>,
+        #[zbus(signal_emitter)] emitter: zbus::object_server::SignalEmitter<'_>,
+    ) -> zbus::fdo::Result<()> {
+        let item = udisks2::block::ConfigurationItem::from_raw(&item.0, &item.1)
+            .ok_or_else(|| zbus::fdo::Error::InvalidArgs(item.0))?;
+        self.configuration.push(item);
+        self.configuration_changed(&emitter).await?;
+        Ok(())
+    }
+
+    async fn remove_configuration_item(
+        &mut self,
+        item: (String, HashMap<String, OwnedValue>),
+        _options: HashMap<String, OwnedValue>,
+        #[zbus(signal_emitter)] emitter: zbus::object_server::SignalEmitter<'_>,
+    ) -> zbus::fdo::Result<()> {
+        let item = udisks2::block::ConfigurationItem::from_raw(&item.0, &item.1);
+        self.configuration
+            .retain(|existing| Some(existing) != item.as_ref());
+        self.configuration_changed(&emitter).await?;
+        Ok(())
+    }
+
+    #[zbus(property)]
+    fn userspace_mount_options(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    #[zbus(property)]
+    fn hint_partitionable(&self) -> bool {
+        self.hint_partitionable
+    }
+
+    #[zbus(property)]
+    fn hint_system(&self) -> bool {
+        self.hint_system
+    }
+
+    #[zbus(property)]
+    fn hint_ignore(&self) -> bool {
+        self.hint_ignore
+    }
+
+    #[zbus(property)]
+    fn hint_auto(&self) -> bool {
+        self.hint_auto
+    }
+
+    #[zbus(property)]
+    fn hint_name(&self) -> String {
+        self.hint_name.clone()
+    }
+
+    #[zbus(property)]
+    fn hint_icon_name(&self) -> String {
+        self.hint_icon_name.clone()
+    }
+
+    #[zbus(property)]
+    fn hint_symbolic_icon_name(&self) -> String {
+        self.hint_symbolic_icon_name.clone()
+    }
+}
+
+/// `org.freedesktop.UDisks2.PartitionTable` interface.
+///
+/// Reading an empty [`Self::type_`] fails, e.g. as for a table that is being removed.
+#[derive(Debug, Clone, Default)]
+pub struct MockPartitionTable {
+    /// Type of the partition table, reading it fails with a D-Bus error if empty.
+    pub type_: String,
+    pub partitions: Vec<OwnedObjectPath>,
+}
+
+#[zbus::interface(name = "org.freedesktop.UDisks2.PartitionTable")]
+impl MockPartitionTable {
+    #[zbus(property, name = "Type")]
+    fn type_(&self) -> zbus::fdo::Result<String> {
+        if self.type_.is_empty() {
+            return Err(zbus::fdo::Error::Failed(
+                "No partition table type".to_owned(),
+            ));
+        }
+        Ok(self.type_.clone())
+    }
+
+    #[zbus(property)]
+    fn partitions(&self) -> Vec<OwnedObjectPath> {
+        self.partitions.clone()
+    }
+}
+
+/// `org.freedesktop.UDisks2.Partition` interface.
+#[derive(Debug, Clone)]
+pub struct MockPartition {
+    pub number: u32,
+    pub type_: String,
+    pub flags: u64,
+    pub offset: u64,
+    pub size: u64,
+    pub name: String,
+    pub uuid: String,
+    pub table: OwnedObjectPath,
+    pub is_container: bool,
+    pub is_contained: bool,
+}
+
+impl Default for MockPartition {
+    fn default() -> Self {
+        Self {
+            number: 0,
+            type_: String::new(),
+            flags: 0,
+            offset: 0,
+            size: 0,
+            name: String::new(),
+            uuid: String::new(),
+            table: no_object(),
+            is_container: false,
+            is_contained: false,
+        }
+    }
+}
+
+#[zbus::interface(name = "org.freedesktop.UDisks2.Partition")]
+impl MockPartition {
+    #[zbus(property)]
+    fn number(&self) -> u32 {
+        self.number
+    }
+
+    #[zbus(property, name = "Type")]
+    fn type_(&self) -> String {
+        self.type_.clone()
+    }
+
+    #[zbus(property)]
+    fn flags(&self) -> u64 {
+        self.flags
+    }
+
+    #[zbus(property)]
+    fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    #[zbus(property)]
+    fn size(&self) -> u64 {
+        self.size
+    }
+
+    #[zbus(property)]
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    #[zbus(property, name = "UUID")]
+    fn uuid(&self) -> String {
+        self.uuid.clone()
+    }
+
+    #[zbus(property)]
+    fn table(&self) -> OwnedObjectPath {
+        self.table.clone()
+    }
+
+    #[zbus(property)]
+    fn is_container(&self) -> bool {
+        self.is_container
+    }
+
+    #[zbus(property)]
+    fn is_contained(&self) -> bool {
+        self.is_contained
+    }
+}
+
+/// `org.freedesktop.UDisks2.Filesystem` interface.
+#[derive(Debug, Clone, Default)]
+pub struct MockFilesystem {
+    pub mount_points: Vec<Vec<u8>>,
+    pub size: u64,
+    /// If set, checks wait for a notification before completing.
+    pub check_release: Option<Arc<tokio::sync::Notify>>,
+}
+
+#[zbus::interface(name = "org.freedesktop.UDisks2.Filesystem")]
+impl MockFilesystem {
+    /// Checks the filesystem, exporting a [`MockJob`] while the check is running.
+    async fn check(
+        &self,
+        _options: HashMap<String, OwnedValue>,
+        #[zbus(header)] header: zbus::message::Header<'_>,
+        #[zbus(object_server)] server: &zbus::ObjectServer,
+    ) -> zbus::fdo::Result<bool> {
+        const JOB: &str = "/org/freedesktop/UDisks2/jobs/1";
+        let object = header.path().expect("method call without path").to_owned();
+        server
+            .at(
+                JOB,
+                MockJob {
+                    operation: "filesystem-check".to_owned(),
+                    objects: vec![object.into()],
+                },
+            )
+            .await?;
+        if let Some(release) = &self.check_release {
+            release.notified().await;
+        }
+        server.remove::<MockJob, _>(JOB).await?;
+        Ok(true)
+    }
+
+    /// Mounts the filesystem at `/run/media/<as-user>/Data`, where the user defaults to `mock`.
+    async fn mount(
+        &mut self,
+        options: HashMap<String, OwnedValue>,
+        #[zbus(signal_emitter)] emitter: zbus::object_server::SignalEmitter<'_>,
+    ) -> zbus::fdo::Result<String> {
+        if !self.mount_points.is_empty() {
+            return Err(zbus::fdo::Error::Failed("Already mounted".to_owned()));
+        }
+        let user = match options.get("as-user") {
+            Some(user) => String::try_from(user.try_clone().unwrap())
+                .map_err(|err| zbus::fdo::Error::InvalidArgs(err.to_string()))?,
+            None => "mock".to_owned(),
+        };
+        let mount_point = format!("/run/media/{user}/Data");
+        self.mount_points.push(bytestring(&mount_point));
+        self.mount_points_changed(&emitter).await?;
+        Ok(mount_point)
+    }
+
+    async fn unmount(
+        &mut self,
+        _options: HashMap<String, OwnedValue>,
+        #[zbus(signal_emitter)] emitter: zbus::object_server::SignalEmitter<'_>,
+    ) -> zbus::fdo::Result<()> {
+        if self.mount_points.is_empty() {
+            return Err(zbus::fdo::Error::Failed("Not mounted".to_owned()));
+        }
+        self.mount_points.clear();
+        self.mount_points_changed(&emitter).await?;
+        Ok(())
+    }
+
+    /// Resizes the filesystem, which must not be mounted.
+    async fn resize(
+        &mut self,
+        size: u64,
+        _options: HashMap<String, OwnedValue>,
+        #[zbus(signal_emitter)] emitter: zbus::object_server::SignalEmitter<'_>,
+    ) -> zbus::fdo::Result<()> {
+        if !self.mount_points.is_empty() {
+            return Err(zbus::fdo::Error::Failed("Mounted".to_owned()));
+        }
+        self.size = size;
+        self.size_changed(&emitter).await?;
+        Ok(())
+    }
+
+    #[zbus(property)]
+    fn mount_points(&self) -> Vec<Vec<u8>> {
+        self.mount_points.clone()
+    }
+
+    #[zbus(property)]
+    fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+/// `org.freedesktop.UDisks2.Job` interface.
+#[derive(Debug, Clone, Default)]
+pub struct MockJob {
+    pub operation: String,
+    pub objects: Vec<OwnedObjectPath>,
+}
+
+#[zbus::interface(name = "org.freedesktop.UDisks2.Job")]
+impl MockJob {
+    #[zbus(property)]
+    fn operation(&self) -> String {
+        self.operation.clone()
+    }
+
+    #[zbus(property)]
+    fn objects(&self) -> Vec<OwnedObjectPath> {
+        self.objects.clone()
+    }
+}
+
+/// `org.freedesktop.UDisks2.NVMe.Namespace` interface.
+#[derive(Debug, Clone, Default)]
+pub struct MockNamespace {
+    pub nsid: u32,
+}
+
+#[zbus::interface(name = "org.freedesktop.UDisks2.NVMe.Namespace")]
+impl MockNamespace {
+    #[zbus(property, name = "NSID")]
+    fn nsid(&self) -> u32 {
+        self.nsid
+    }
+}
+
+/// `org.freedesktop.UDisks2.Loop` interface.
+#[derive(Debug, Clone, Default)]
+pub struct MockLoop {
+    pub backing_file: Vec<u8>,
+    pub autoclear: bool,
+    pub setup_by_uid: u32,
+}
+
+#[zbus::interface(name = "org.freedesktop.UDisks2.Loop")]
+impl MockLoop {
+    #[zbus(property)]
+    fn backing_file(&self) -> Vec<u8> {
+        self.backing_file.clone()
+    }
+
+    #[zbus(property)]
+    fn autoclear(&self) -> bool {
+        self.autoclear
+    }
+
+    #[zbus(property, name = "SetupByUID")]
+    fn setup_by_uid(&self) -> u32 {
+        self.setup_by_uid
+    }
+}
+
+/// `org.freedesktop.UDisks2.Encrypted` interface.
+///
+/// Unlocking succeeds if the passphrase or the keyfile contents match [`Self::passphrase`].
+#[derive(Debug, Clone)]
+pub struct MockEncrypted {
+    pub passphrase: String,
+    pub cleartext_device: OwnedObjectPath,
+}
+
+#[zbus::interface(name = "org.freedesktop.UDisks2.Encrypted")]
+impl MockEncrypted {
+    fn unlock(
+        &self,
+        passphrase: String,
+        options: HashMap<String, OwnedValue>,
+    ) -> zbus::fdo::Result<OwnedObjectPath> {
+        let passphrase = match options.get("keyfile_contents") {
+            Some(contents) => Vec::<u8>::try_from(contents.try_clone().unwrap())
+                .map_err(|err| zbus::fdo::Error::InvalidArgs(err.to_string()))?,
+            None => passphrase.into_bytes(),
+        };
+        if passphrase != self.passphrase.as_bytes() {
+            return Err(zbus::fdo::Error::Failed("Wrong passphrase".to_owned()));
+        }
+        Ok(self.cleartext_device.clone())
+    }
+
+    #[zbus(property)]
+    fn cleartext_device(&self) -> OwnedObjectPath {
+        self.cleartext_device.clone()
+    }
+}
+
+/// `org.freedesktop.UDisks2.Drive.Ata` interface, reporting SMART data.
+#[derive(Debug, Clone, Default)]
+pub struct MockAta {
+    pub smart_supported: bool,
+    pub smart_enabled: bool,
+    pub smart_failing: bool,
+    pub smart_temperature: f64,
+    pub smart_power_on_seconds: u64,
+    pub smart_updated: u64,
+    pub security_erase_unit_minutes: i32,
+    pub security_enhanced_erase_unit_minutes: i32,
+}
+
+#[zbus::interface(name = "org.freedesktop.UDisks2.Drive.Ata")]
+impl MockAta {
+    #[zbus(property)]
+    fn security_erase_unit_minutes(&self) -> i32 {
+        self.security_erase_unit_minutes
+    }
+
+    #[zbus(property)]
+    fn security_enhanced_erase_unit_minutes(&self) -> i32 {
+        self.security_enhanced_erase_unit_minutes
+    }
+
+    #[zbus(property)]
+    fn smart_supported(&self) -> bool {
+        self.smart_supported
+    }
+
+    #[zbus(property)]
+    fn smart_enabled(&self) -> bool {
+        self.smart_enabled
+    }
+
+    #[zbus(property)]
+    fn smart_failing(&self) -> bool {
+        self.smart_failing
+    }
+
+    #[zbus(property)]
+    fn smart_temperature(&self) -> f64 {
+        self.smart_temperature
+    }
+
+    #[zbus(property)]
+    fn smart_power_on_seconds(&self) -> u64 {
+        self.smart_power_on_seconds
+    }
+
+    #[zbus(property)]
+    fn smart_selftest_status(&self) -> String {
+        "success".to_owned()
+    }
+
+    #[zbus(property)]
+    fn smart_updated(&self) -> u64 {
+        self.smart_updated
+    }
+}
+
+/// `org.freedesktop.UDisks2.MDRaid` interface.
+#[derive(Debug, Clone, Default)]
+pub struct MockMDRaid {
+    pub uuid: String,
+    pub name: String,
+    pub level: String,
+    pub size: u64,
+    pub running: bool,
+}
+
+#[zbus::interface(name = "org.freedesktop.UDisks2.MDRaid")]
+impl MockMDRaid {
+    #[zbus(property, name = "UUID")]
+    fn uuid(&self) -> String {
+        self.uuid.clone()
+    }
+
+    #[zbus(property)]
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    #[zbus(property)]
+    fn level(&self) -> String {
+        self.level.clone()
+    }
+
+    #[zbus(property)]
+    fn size(&self) -> u64 {
+        self.size
+    }
+
+    #[zbus(property)]
+    fn running(&self) -> bool {
+        self.running
+    }
+}